@@ -1,9 +1,11 @@
 //! Definition of all the Forcerelay subcommands
 
+mod ckb;
 mod clear;
 mod completions;
 mod config;
 mod create;
+mod debug;
 mod fee;
 mod forcerelay;
 mod health;
@@ -12,15 +14,17 @@ mod listen;
 mod misbehaviour;
 mod query;
 mod start;
+mod storage;
 mod tx;
 mod update;
 mod upgrade;
 mod version;
 
 use self::{
-    clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
-    fee::FeeCmd, forcerelay::EthCkbCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
-    misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds,
+    ckb::CkbCmds, clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd,
+    create::CreateCmds, debug::DebugCmds, fee::FeeCmd, forcerelay::EthCkbCmd,
+    health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd, misbehaviour::MisbehaviourCmd,
+    query::QueryCmd, start::StartCmd, storage::StorageCmd, tx::TxCmd, update::UpdateCmds,
     upgrade::UpgradeCmds, version::VersionCmd,
 };
 
@@ -95,6 +99,18 @@ pub enum CliCmd {
     /// Performs a health check of all chains in the the config
     HealthCheck(HealthCheckCmd),
 
+    /// Inspect and repair the beacon header storage of CKB chains
+    #[clap(subcommand)]
+    Storage(StorageCmd),
+
+    /// Manage the cells of Ckb4Ibc chains
+    #[clap(subcommand)]
+    Ckb(CkbCmds),
+
+    /// Inspect diagnostics saved by the relayer
+    #[clap(subcommand)]
+    Debug(DebugCmds),
+
     /// Generate auto-complete scripts for different shells.
     #[clap(display_order = 1000)]
     Completions(CompletionsCmd),
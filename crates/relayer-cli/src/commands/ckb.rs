@@ -0,0 +1,12 @@
+//! `ckb` subcommand
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod reclaim;
+
+/// `ckb` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum CkbCmds {
+    /// Consume the finished packet cells paid by the relayer to reclaim their capacity
+    Reclaim(reclaim::ReclaimCmd),
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc_relayer::chain::ckb4ibc::Ckb4IbcChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `ckb reclaim` CLI command.
+///
+/// `ckb reclaim --chain <CHAIN_ID>`
+///
+/// Consumes, in one go, the packet cells the background reclaimer would consume,
+/// following the `reclaim` settings of the chain even if the reclaimer is disabled.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct ReclaimCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+}
+
+impl Runnable for ReclaimCmd {
+    fn run(&self) {
+        let config = app_config();
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config @ ChainConfig::Ckb4Ibc(_)) => chain_config.clone(),
+            Some(_) => {
+                Output::error(format!("chain '{}' is not a Ckb4Ibc chain", self.chain_id)).exit()
+            }
+            None => Output::error(format!(
+                "chain '{}' not found in configuration file",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let rt = Arc::new(TokioRuntime::new().unwrap_or_else(|e| Output::error(e).exit()));
+        let result = Ckb4IbcChain::bootstrap(chain_config, rt)
+            .and_then(|chain| chain.packet_reclaimer())
+            .and_then(|reclaimer| reclaimer.reclaim());
+        match result {
+            Ok(reclaimed) => Output::success(reclaimed).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReclaimCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_reclaim() {
+        assert_eq!(
+            ReclaimCmd {
+                chain_id: ChainId::from_string("ckb4ibc-0")
+            },
+            ReclaimCmd::parse_from(["test", "--chain", "ckb4ibc-0"])
+        )
+    }
+
+    #[test]
+    fn test_reclaim_no_chain() {
+        assert!(ReclaimCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -9,9 +9,13 @@ use crate::prelude::*;
 
 /// In order to validate the configuration file the command will check that the file exists,
 /// that it is readable and not empty. It will then check the validity of the fields inside
-/// the file.
-#[derive(Command, Debug, Parser)]
-pub struct ValidateCmd {}
+/// the file, and optionally that the contracts referenced by each chain are deployed on it.
+#[derive(Command, Debug, Parser, PartialEq, Eq)]
+pub struct ValidateCmd {
+    /// Also resolve the contracts referenced by each chain on the chain itself
+    #[clap(long = "online")]
+    online: bool,
+}
 
 impl Runnable for ValidateCmd {
     /// Validate the loaded configuration.
@@ -40,9 +44,41 @@ impl Runnable for ValidateCmd {
 
         // No need to output the underlying error, this is done already when the application boots.
         // See `application::CliApp::after_config`.
-        match config::validate_config(&config) {
-            Ok(_) => Output::success("configuration is valid").exit(),
-            Err(_) => Output::error("configuration is invalid").exit(),
+        if config::validate_config(&config).is_err() {
+            Output::error("configuration is invalid").exit()
+        }
+
+        if self.online {
+            if let Err(config::Diagnostic::Error(e) | config::Diagnostic::Warning(e)) =
+                config::validate_config_online(&config)
+            {
+                Output::error(format!("configuration is invalid: {e}")).exit()
+            }
         }
+
+        Output::success("configuration is valid").exit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidateCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(
+            ValidateCmd { online: false },
+            ValidateCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_validate_online() {
+        assert_eq!(
+            ValidateCmd { online: true },
+            ValidateCmd::parse_from(["test", "--online"])
+        )
     }
 }
@@ -0,0 +1,12 @@
+//! `debug` subcommand
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod axon_proof;
+
+/// `debug` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum DebugCmds {
+    /// Verify an Axon block proof offline from the ingredients saved when its verification failed
+    AxonProof(axon_proof::AxonProofCmd),
+}
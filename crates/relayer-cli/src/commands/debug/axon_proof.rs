@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::axon::diagnostics::ProofBundle;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `debug axon-proof` CLI command.
+///
+/// `debug axon-proof --chain <CHAIN_ID> --height <HEIGHT>`
+///
+/// Loads the proof bundle saved in the diagnostics directory of the chain and verifies it
+/// again, without connecting to the Axon node. A bundle file can be given directly with
+/// `--bundle <PATH>` instead.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct AxonProofCmd {
+    #[clap(
+        long = "chain",
+        value_name = "CHAIN_ID",
+        required_unless_present = "bundle",
+        requires = "height",
+        help = "Identifier of the Axon chain"
+    )]
+    chain_id: Option<ChainId>,
+
+    #[clap(
+        long = "height",
+        value_name = "HEIGHT",
+        help = "Number of the Axon block whose proof failed to be verified"
+    )]
+    height: Option<u64>,
+
+    #[clap(
+        long = "bundle",
+        value_name = "PATH",
+        conflicts_with_all = &["chain_id", "height"],
+        help = "Path of a saved proof bundle"
+    )]
+    bundle: Option<PathBuf>,
+}
+
+impl AxonProofCmd {
+    fn bundle_path(&self) -> Result<PathBuf, String> {
+        if let Some(bundle) = &self.bundle {
+            return Ok(bundle.clone());
+        }
+        let (Some(chain_id), Some(height)) = (&self.chain_id, self.height) else {
+            return Err("either `--bundle` or `--chain` and `--height` must be given".to_owned());
+        };
+        let config = app_config();
+        let Some(ChainConfig::Axon(chain_config)) = config.find_chain(chain_id) else {
+            return Err(format!(
+                "chain '{chain_id}' is not an Axon chain in the config"
+            ));
+        };
+        let dir = chain_config
+            .diagnostics
+            .dir(chain_id)
+            .ok_or_else(|| format!("diagnostics are disabled for chain '{chain_id}'"))?;
+        Ok(ProofBundle::path(&dir, height))
+    }
+}
+
+impl Runnable for AxonProofCmd {
+    fn run(&self) {
+        let path = self
+            .bundle_path()
+            .unwrap_or_else(|e| Output::error(e).exit());
+        let bundle = ProofBundle::load(&path).unwrap_or_else(|e| {
+            Output::error(format!("failed to load {}: {e}", path.display())).exit()
+        });
+        let block_number = bundle.ingredients.block_number();
+
+        match bundle.ingredients.verify() {
+            Ok(()) => Output::success_msg(format!(
+                "proof of axon block #{block_number} is valid, the saved error was: {}",
+                bundle.error
+            ))
+            .exit(),
+            Err(e) => Output::error(format!(
+                "proof of axon block #{block_number} is invalid: {e}"
+            ))
+            .exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AxonProofCmd;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_debug_axon_proof_height() {
+        assert_eq!(
+            AxonProofCmd {
+                chain_id: Some(ChainId::from_string("axon-0")),
+                height: Some(42),
+                bundle: None,
+            },
+            AxonProofCmd::parse_from(["test", "--chain", "axon-0", "--height", "42"])
+        )
+    }
+
+    #[test]
+    fn test_debug_axon_proof_bundle() {
+        assert_eq!(
+            AxonProofCmd {
+                chain_id: None,
+                height: None,
+                bundle: Some(PathBuf::from("axon_block_42.json")),
+            },
+            AxonProofCmd::parse_from(["test", "--bundle", "axon_block_42.json"])
+        )
+    }
+
+    #[test]
+    fn test_debug_axon_proof_no_height() {
+        assert!(AxonProofCmd::try_parse_from(["test", "--chain", "axon-0"]).is_err())
+    }
+
+    #[test]
+    fn test_debug_axon_proof_no_args() {
+        assert!(AxonProofCmd::try_parse_from(["test"]).is_err())
+    }
+
+    #[test]
+    fn test_debug_axon_proof_conflicts() {
+        assert!(AxonProofCmd::try_parse_from([
+            "test",
+            "--chain",
+            "axon-0",
+            "--height",
+            "42",
+            "--bundle",
+            "axon_block_42.json"
+        ])
+        .is_err())
+    }
+}
@@ -13,7 +13,8 @@ use ibc_relayer::{
     chain::ChainType,
     config::{AddressType, ChainConfig, Config},
     keyring::{
-        AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair, SigningKeyPairSized, Store,
+        passphrase, AnySigningKeyPair, KeyRing, Secp256k1KeyPair, SigningKeyPair,
+        SigningKeyPairSized,
     },
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -38,7 +39,11 @@ use crate::conclude::Output;
 ///
 /// `keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>`
 ///
-/// The key-file, mnemonic-file, secret-file flags can't be given at the same time, this will cause a terminating error.
+/// The command to import a key from an encrypted Web3 keystore file:
+///
+/// `keys add [OPTIONS] --chain <CHAIN_ID> --keystore-file <KEYSTORE_FILE>`
+///
+/// The key-file, mnemonic-file, secret-file, keystore-file flags can't be given at the same time, this will cause a terminating error.
 /// If successful the key will be created or restored, depending on which flag was given.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
@@ -46,7 +51,9 @@ use crate::conclude::Output;
 
     forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --mnemonic-file <MNEMONIC_FILE>
 
-    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>"
+    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --secret-file <SECRET_FILE>
+
+    forcerelay keys add [OPTIONS] --chain <CHAIN_ID> --keystore-file <KEYSTORE_FILE>"
 )]
 pub struct KeysAddCmd {
     #[clap(
@@ -87,6 +94,24 @@ pub struct KeysAddCmd {
     )]
     secret_file: Option<PathBuf>,
 
+    #[clap(
+        long = "keystore-file",
+        required = true,
+        value_name = "KEYSTORE_FILE",
+        help_heading = "FLAGS",
+        help = "Path to an encrypted Web3 keystore file to import the key from",
+        group = "add-restore"
+    )]
+    keystore_file: Option<PathBuf>,
+
+    #[clap(
+        long = "passphrase-file",
+        value_name = "PASSPHRASE_FILE",
+        requires = "keystore_file",
+        help = "Path to file containing the passphrase of the keystore file (defaults to the passphrase of encrypted keys)"
+    )]
+    passphrase_file: Option<PathBuf>,
+
     #[clap(
         long = "key-name",
         value_name = "KEY_NAME",
@@ -147,13 +172,14 @@ impl Runnable for KeysAddCmd {
             Ok(result) => result,
         };
 
-        // Check if --key-file or --mnemonic-file or --secret-file was given as input.
+        // Check if --key-file or --mnemonic-file or --secret-file or --keystore-file was given as input.
         match (
             self.key_file.clone(),
             self.mnemonic_file.clone(),
             self.secret_file.clone(),
+            self.keystore_file.clone(),
         ) {
-            (Some(key_file), _, _) => {
+            (Some(key_file), _, _, _) => {
                 let key = add_key(
                     &opts.config,
                     &opts.name,
@@ -176,7 +202,7 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
-            (_, Some(mnemonic_file), _) => {
+            (_, Some(mnemonic_file), _, _) => {
                 let key = restore_key(
                     &mnemonic_file,
                     &opts.name,
@@ -200,7 +226,7 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
-            (_, _, Some(secret_file)) => {
+            (_, _, Some(secret_file), _) => {
                 let key =
                     parse_key_from_secret(&secret_file, &opts.name, &opts.config, self.overwrite);
 
@@ -219,6 +245,30 @@ impl Runnable for KeysAddCmd {
                     .exit(),
                 }
             }
+            (_, _, _, Some(keystore_file)) => {
+                let key = import_keystore(
+                    &keystore_file,
+                    self.passphrase_file.as_deref(),
+                    &opts.name,
+                    &opts.config,
+                    self.overwrite,
+                );
+
+                match key {
+                    Ok(key) => Output::success_msg(format!(
+                        "Imported key '{}' ({}) on chain {}",
+                        opts.name,
+                        key.account(),
+                        opts.config.id(),
+                    ))
+                    .exit(),
+                    Err(e) => Output::error(format!(
+                        "An error occurred importing the key on chain {} from keystore {:?}: {}",
+                        self.chain_id, keystore_file, e
+                    ))
+                    .exit(),
+                }
+            }
             // This case should never trigger.
             // The 'required' parameter for the flags will trigger an error if both flags have not been given.
             // And the 'group' parameter for the flags will trigger an error if both flags are given.
@@ -245,7 +295,8 @@ pub fn add_key(
         ChainType::Ckb4Ibc => "ckb4ibc",
     };
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -278,13 +329,16 @@ pub fn restore_key(
     };
     let address_type = match config.r#type() {
         ChainType::CosmosSdk => &config.cosmos().address_type,
-        ChainType::Eth => todo!(),
+        ChainType::Eth => &AddressType::Ethermint {
+            pk_type: Default::default(),
+        },
         ChainType::Axon => &AddressType::Axon,
         ChainType::Ckb | ChainType::Ckb4Ibc => &AddressType::Ckb,
     };
 
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -323,7 +377,8 @@ pub fn parse_key_from_secret(
         ChainType::Ckb4Ibc => ("ckb4ibc", AddressType::Ckb),
     };
     let key_pair = {
-        let mut keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
 
         check_key_exists(&keyring, key_name, overwrite);
 
@@ -335,6 +390,47 @@ pub fn parse_key_from_secret(
     Ok(key_pair)
 }
 
+pub fn import_keystore(
+    keystore_file: &Path,
+    passphrase_file: Option<&Path>,
+    key_name: &str,
+    config: &ChainConfig,
+    overwrite: bool,
+) -> eyre::Result<AnySigningKeyPair> {
+    let passphrase = match passphrase_file {
+        Some(file) => fs::read_to_string(file)
+            .map_err(|_| eyre!("error reading the passphrase file"))?
+            .trim_end_matches(['\r', '\n'])
+            .to_string(),
+        None => passphrase()?,
+    };
+
+    let (account_prefix, address_type) = match config.r#type() {
+        ChainType::CosmosSdk => (config.cosmos().account_prefix.as_str(), AddressType::Cosmos),
+        ChainType::Eth => (
+            "eth",
+            AddressType::Ethermint {
+                pk_type: Default::default(),
+            },
+        ),
+        ChainType::Axon => ("axon", AddressType::Axon),
+        ChainType::Ckb => ("ckb", AddressType::Ckb),
+        ChainType::Ckb4Ibc => ("ckb4ibc", AddressType::Ckb),
+    };
+    let key_pair = {
+        let mut keyring =
+            KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
+
+        check_key_exists(&keyring, key_name, overwrite);
+
+        let key_pair = Secp256k1KeyPair::from_keystore(keystore_file, &passphrase, &address_type)?;
+
+        keyring.add_key(key_name, key_pair.clone())?;
+        key_pair.into()
+    };
+    Ok(key_pair)
+}
+
 /// Check if the key with the given key name already exists.
 /// If it already exists and overwrite is false, abort the command with an error.
 /// If overwrite is true, output a warning message informing the key will be overwritten.
@@ -365,6 +461,8 @@ mod tests {
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
                 secret_file: None,
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -381,6 +479,8 @@ mod tests {
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
                 secret_file: None,
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false
@@ -403,6 +503,8 @@ mod tests {
                 key_file: None,
                 mnemonic_file: None,
                 secret_file: Some(PathBuf::from("secret_file")),
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: false,
@@ -417,6 +519,46 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_keys_add_keystore_file() {
+        assert_eq!(
+            KeysAddCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                key_file: None,
+                mnemonic_file: None,
+                secret_file: None,
+                keystore_file: Some(PathBuf::from("keystore_file")),
+                passphrase_file: Some(PathBuf::from("passphrase_file")),
+                key_name: None,
+                hd_path: "m/44'/118'/0'/0/0".to_string(),
+                overwrite: false,
+            },
+            KeysAddCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--keystore-file",
+                "keystore_file",
+                "--passphrase-file",
+                "passphrase_file",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_keys_add_passphrase_without_keystore() {
+        assert!(KeysAddCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--secret-file",
+            "secret_file",
+            "--passphrase-file",
+            "passphrase_file",
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_keys_add_key_file_overwrite() {
         assert_eq!(
@@ -425,6 +567,8 @@ mod tests {
                 key_file: Some(PathBuf::from("key_file")),
                 mnemonic_file: None,
                 secret_file: None,
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -448,6 +592,8 @@ mod tests {
                 key_file: None,
                 mnemonic_file: Some(PathBuf::from("mnemonic_file")),
                 secret_file: None,
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -471,6 +617,8 @@ mod tests {
                 key_file: None,
                 mnemonic_file: None,
                 secret_file: Some(PathBuf::from("secret_file")),
+                keystore_file: None,
+                passphrase_file: None,
                 key_name: None,
                 hd_path: "m/44'/118'/0'/0/0".to_string(),
                 overwrite: true,
@@ -5,7 +5,7 @@ use eyre::eyre;
 use ibc_relayer::{
     chain::ChainType,
     config::{ChainConfig, Config},
-    keyring::{KeyRing, Store},
+    keyring::{KeyRing, Secp256k1KeyPair},
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -113,35 +113,29 @@ impl Runnable for KeysDeleteCmd {
     }
 }
 
+/// The keyring of the chain, the keys of all chain types are secp256k1 keys
+fn keyring(config: &ChainConfig) -> eyre::Result<KeyRing<Secp256k1KeyPair>> {
+    let account_prefix = match config.r#type() {
+        ChainType::CosmosSdk => config.cosmos().account_prefix.as_str(),
+        ChainType::Eth => "eth",
+        ChainType::Axon => "axon",
+        ChainType::Ckb => "ckb",
+        ChainType::Ckb4Ibc => "ckb4ibc",
+    };
+    let keyring = KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
+    Ok(keyring)
+}
+
 pub fn delete_key(config: &ChainConfig, key_name: &str) -> eyre::Result<()> {
-    match config.r#type() {
-        ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.cosmos().account_prefix, config.id())?;
-            keyring.remove_key(key_name)?;
-        }
-        ChainType::Eth => todo!(),
-        ChainType::Axon => todo!(),
-        ChainType::Ckb => todo!(),
-        ChainType::Ckb4Ibc => todo!(),
-    }
+    keyring(config)?.remove_key(key_name)?;
     Ok(())
 }
 
 pub fn delete_all_keys(config: &ChainConfig) -> eyre::Result<()> {
-    match config.r#type() {
-        ChainType::CosmosSdk => {
-            let mut keyring =
-                KeyRing::new_secp256k1(Store::Test, &config.cosmos().account_prefix, config.id())?;
-            let keys = keyring.keys()?;
-            for (key_name, _) in keys {
-                keyring.remove_key(&key_name)?;
-            }
-        }
-        ChainType::Eth => todo!(),
-        ChainType::Axon => todo!(),
-        ChainType::Ckb => todo!(),
-        ChainType::Ckb4Ibc => todo!(),
+    let mut keyring = keyring(config)?;
+    let keys = keyring.keys()?;
+    for (key_name, _) in keys {
+        keyring.remove_key(&key_name)?;
     }
     Ok(())
 }
@@ -0,0 +1,70 @@
+//! `storage` subcommand
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::ckb::mmr_storage::MmrStorage;
+use ibc_relayer::config::{ckb::ChainConfig as CkbChainConfig, ChainConfig};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+mod dump;
+mod export;
+mod import;
+mod prune;
+mod reinit;
+mod rollback;
+mod status;
+mod verify;
+
+/// `storage` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum StorageCmd {
+    /// Show the base and tip slots of the beacon headers stored for a CKB chain
+    Status(status::StorageStatusCmd),
+
+    /// Dump the stored header digests of a range of slots
+    Dump(dump::StorageDumpCmd),
+
+    /// Verify the stored MMR root against the on-chain multi-client cell
+    Verify(verify::StorageVerifyCmd),
+
+    /// Roll the stored beacon headers back to a slot
+    Rollback(rollback::StorageRollbackCmd),
+
+    /// Wipe the stored beacon headers and reinitialize them from a checkpoint
+    Reinit(reinit::StorageReinitCmd),
+
+    /// Export the stored MMR into a snapshot file
+    Export(export::StorageExportCmd),
+
+    /// Seed an empty storage with a snapshot file
+    Import(import::StorageImportCmd),
+
+    /// Remove the MMR nodes which are no longer needed after a slot
+    Prune(prune::StoragePruneCmd),
+}
+
+/// Find the config of the CKB chain `chain_id` and open its storage, exit on failure.
+///
+/// The storage is locked by a running relayer, so it has to be stopped first.
+fn open_storage(chain_id: &ChainId) -> (CkbChainConfig, MmrStorage) {
+    let config = app_config();
+    let chain_config = match config.find_chain(chain_id) {
+        Some(ChainConfig::Ckb(chain_config)) => chain_config.clone(),
+        Some(_) => Output::error(format!("chain '{chain_id}' is not a CKB chain")).exit(),
+        None => Output::error(format!(
+            "chain '{chain_id}' not found in configuration file"
+        ))
+        .exit(),
+    };
+    let storage = MmrStorage::open(&chain_config).unwrap_or_else(|e| {
+        Output::error(format!(
+            "failed to open the storage at {}, make sure the relayer is stopped: {e}",
+            chain_config.data_dir.display()
+        ))
+        .exit()
+    });
+    (chain_config, storage)
+}
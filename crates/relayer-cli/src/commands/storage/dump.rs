@@ -0,0 +1,93 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage dump` CLI command.
+///
+/// `storage dump --chain <CHAIN_ID> --from <SLOT> [--to <SLOT>]`
+///
+/// Without `--to`, only the digest of the slot given by `--from` is dumped.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageDumpCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "from",
+        required = true,
+        value_name = "SLOT",
+        help_heading = "REQUIRED",
+        help = "First slot to dump"
+    )]
+    from: u64,
+
+    #[clap(
+        long = "to",
+        value_name = "SLOT",
+        help = "Last slot to dump (inclusive)"
+    )]
+    to: Option<u64>,
+}
+
+impl Runnable for StorageDumpCmd {
+    fn run(&self) {
+        let to = self.to.unwrap_or(self.from);
+        if to < self.from {
+            Output::error(format!("--to {to} is lower than --from {}", self.from)).exit()
+        }
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.digests(self.from, to) {
+            Ok(digests) => Output::success(digests).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageDumpCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_dump_range() {
+        assert_eq!(
+            StorageDumpCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                from: 100,
+                to: Some(200),
+            },
+            StorageDumpCmd::parse_from([
+                "test", "--chain", "ckb-0", "--from", "100", "--to", "200"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_storage_dump_single() {
+        assert_eq!(
+            StorageDumpCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                from: 100,
+                to: None,
+            },
+            StorageDumpCmd::parse_from(["test", "--chain", "ckb-0", "--from", "100"])
+        )
+    }
+
+    #[test]
+    fn test_storage_dump_no_from() {
+        assert!(StorageDumpCmd::try_parse_from(["test", "--chain", "ckb-0"]).is_err())
+    }
+}
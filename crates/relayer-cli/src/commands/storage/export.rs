@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage export` CLI command.
+///
+/// `storage export --chain <CHAIN_ID> --file <PATH>`
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageExportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "file",
+        required = true,
+        value_name = "PATH",
+        help_heading = "REQUIRED",
+        help = "Path of the snapshot file to write"
+    )]
+    file: PathBuf,
+}
+
+impl Runnable for StorageExportCmd {
+    fn run(&self) {
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.export_snapshot(&self.file) {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageExportCmd;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_export() {
+        assert_eq!(
+            StorageExportCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                file: PathBuf::from("mmr.snapshot"),
+            },
+            StorageExportCmd::parse_from(["test", "--chain", "ckb-0", "--file", "mmr.snapshot"])
+        )
+    }
+}
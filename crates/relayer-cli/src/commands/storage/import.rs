@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage import` CLI command.
+///
+/// `storage import --chain <CHAIN_ID> --file <PATH>`
+///
+/// The storage must be empty, i.e. `data_dir` of a new relayer instance.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageImportCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "file",
+        required = true,
+        value_name = "PATH",
+        help_heading = "REQUIRED",
+        help = "Path of the snapshot file exported by `storage export`"
+    )]
+    file: PathBuf,
+}
+
+impl Runnable for StorageImportCmd {
+    fn run(&self) {
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.import_snapshot(&self.file) {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageImportCmd;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_import() {
+        assert_eq!(
+            StorageImportCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                file: PathBuf::from("mmr.snapshot"),
+            },
+            StorageImportCmd::parse_from(["test", "--chain", "ckb-0", "--file", "mmr.snapshot"])
+        )
+    }
+
+    #[test]
+    fn test_storage_import_no_file() {
+        assert!(StorageImportCmd::try_parse_from(["test", "--chain", "ckb-0"]).is_err())
+    }
+}
@@ -0,0 +1,68 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage prune` CLI command.
+///
+/// `storage prune --chain <CHAIN_ID> --slot <SLOT>`
+///
+/// Only the MMR nodes needed to append headers and to prove the ones from `<SLOT>`
+/// are kept, so the storage can no longer be rolled back before it. `<SLOT>` should
+/// not be higher than the minimal slot of the on-chain multi-client.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StoragePruneCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "slot",
+        required = true,
+        value_name = "SLOT",
+        help_heading = "REQUIRED",
+        help = "The lowest slot whose header is kept provable"
+    )]
+    slot: u64,
+}
+
+impl Runnable for StoragePruneCmd {
+    fn run(&self) {
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.prune_before(self.slot) {
+            Ok(removed) => Output::success_msg(format!(
+                "removed {removed} MMR nodes before slot {}",
+                self.slot
+            ))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StoragePruneCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_prune() {
+        assert_eq!(
+            StoragePruneCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                slot: 42,
+            },
+            StoragePruneCmd::parse_from(["test", "--chain", "ckb-0", "--slot", "42"])
+        )
+    }
+}
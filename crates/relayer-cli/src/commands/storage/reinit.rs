@@ -0,0 +1,84 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage reinit` CLI command.
+///
+/// `storage reinit --chain <CHAIN_ID> --slot <SLOT> --digest <HEX>`
+///
+/// All the stored beacon headers are dropped, and the MMR restarts from the checkpoint
+/// `<SLOT>`, whose packed header digest (as printed by `storage dump`) is `<HEX>`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageReinitCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "slot",
+        required = true,
+        value_name = "SLOT",
+        help_heading = "REQUIRED",
+        help = "Slot of the checkpoint"
+    )]
+    slot: u64,
+
+    #[clap(
+        long = "digest",
+        required = true,
+        value_name = "HEX",
+        help_heading = "REQUIRED",
+        help = "Hex of the packed header digest of the checkpoint"
+    )]
+    digest: String,
+}
+
+impl Runnable for StorageReinitCmd {
+    fn run(&self) {
+        let digest = subtle_encoding::hex::decode(self.digest.trim_start_matches("0x"))
+            .unwrap_or_else(|e| Output::error(format!("invalid --digest: {e}")).exit());
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.reinitialize(self.slot, &digest) {
+            Ok(()) => Output::success_msg(format!("reinitialized from slot {}", self.slot)).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageReinitCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_reinit() {
+        assert_eq!(
+            StorageReinitCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                slot: 42,
+                digest: "0x00".to_owned(),
+            },
+            StorageReinitCmd::parse_from([
+                "test", "--chain", "ckb-0", "--slot", "42", "--digest", "0x00"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_storage_reinit_no_digest() {
+        assert!(
+            StorageReinitCmd::try_parse_from(["test", "--chain", "ckb-0", "--slot", "42"]).is_err()
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage rollback` CLI command.
+///
+/// `storage rollback --chain <CHAIN_ID> --slot <SLOT>`
+///
+/// The slots after `<SLOT>` are dropped, they will be fetched again on the next client update.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageRollbackCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "slot",
+        required = true,
+        value_name = "SLOT",
+        help_heading = "REQUIRED",
+        help = "The new tip slot"
+    )]
+    slot: u64,
+}
+
+impl Runnable for StorageRollbackCmd {
+    fn run(&self) {
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.rollback_to(self.slot) {
+            Ok(()) => Output::success_msg(format!("rolled back to slot {}", self.slot)).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageRollbackCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_rollback() {
+        assert_eq!(
+            StorageRollbackCmd {
+                chain_id: ChainId::from_string("ckb-0"),
+                slot: 42,
+            },
+            StorageRollbackCmd::parse_from(["test", "--chain", "ckb-0", "--slot", "42"])
+        )
+    }
+
+    #[test]
+    fn test_storage_rollback_no_slot() {
+        assert!(StorageRollbackCmd::try_parse_from(["test", "--chain", "ckb-0"]).is_err())
+    }
+}
@@ -0,0 +1,55 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage status` CLI command.
+///
+/// `storage status --chain <CHAIN_ID>`
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageStatusCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+}
+
+impl Runnable for StorageStatusCmd {
+    fn run(&self) {
+        let (_, storage) = open_storage(&self.chain_id);
+        match storage.status() {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageStatusCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_status() {
+        assert_eq!(
+            StorageStatusCmd {
+                chain_id: ChainId::from_string("ckb-0")
+            },
+            StorageStatusCmd::parse_from(["test", "--chain", "ckb-0"])
+        )
+    }
+
+    #[test]
+    fn test_storage_status_no_chain() {
+        assert!(StorageStatusCmd::try_parse_from(["test"]).is_err())
+    }
+}
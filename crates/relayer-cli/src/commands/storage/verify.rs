@@ -0,0 +1,56 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use super::open_storage;
+use crate::conclude::Output;
+
+/// The data structure that represents the arguments when invoking the `storage verify` CLI command.
+///
+/// `storage verify --chain <CHAIN_ID>`
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct StorageVerifyCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the CKB chain"
+    )]
+    chain_id: ChainId,
+}
+
+impl Runnable for StorageVerifyCmd {
+    fn run(&self) {
+        let (chain_config, storage) = open_storage(&self.chain_id);
+        let rt = TokioRuntime::new().unwrap_or_else(|e| Output::error(e).exit());
+        match storage.check_onchain_root(&rt, &chain_config) {
+            Ok(check) if check.is_consistent() => Output::success(check).exit(),
+            Ok(check) => Output::error(format!(
+                "stored MMR root is inconsistent with the on-chain one: {check:?}"
+            ))
+            .exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageVerifyCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_storage_verify() {
+        assert_eq!(
+            StorageVerifyCmd {
+                chain_id: ChainId::from_string("ckb-0")
+            },
+            StorageVerifyCmd::parse_from(["test", "--chain", "ckb-0"])
+        )
+    }
+}
@@ -8,7 +8,13 @@ use alloc::collections::BTreeSet;
 use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
-use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use ibc_relayer::config::{
+    axon::{AxonChainConfig, EventSource},
+    ckb::ChainConfig as CkbChainConfig,
+    ckb4ibc::{ChainConfig as Ckb4IbcChainConfig, MIN_ABANDONED_BLOCK_GAP},
+    eth::EthChainConfig,
+    ChainConfig, Config, ModeConfig,
+};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
@@ -73,6 +79,43 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        InvalidChainField
+            {
+                chain_id: ChainId,
+                field: String,
+                reason: String,
+            }
+            |e| {
+                format!("config file specifies an invalid `{1}` for the chain '{0}', caused by: {2}",
+                    e.chain_id, e.field, e.reason)
+            },
+
+        MissingLightClients
+            { chain_id: ChainId }
+            |e| {
+                format!("config file specifies no `onchain_light_clients` for the chain '{0}'",
+                    e.chain_id)
+            },
+
+        MismatchedEndpoints
+            {
+                chain_id: ChainId,
+                websocket_addr: String,
+                rpc_addr: String,
+            }
+            |e| {
+                format!("`websocket_addr` ({1}) and `rpc_addr` ({2}) of the chain '{0}' do not point to the same node",
+                    e.chain_id, e.websocket_addr, e.rpc_addr)
+            },
+
+        UnresolvedContract
+            { chain_id: ChainId }
+            [ ibc_relayer::config::Error ]
+            |e| {
+                format!("config file references a missing contract for the chain '{0}'",
+                    e.chain_id)
+            },
     }
 }
 
@@ -84,6 +127,9 @@ pub enum Diagnostic<E> {
 
 /// Method for syntactic validation of the input configuration file.
 pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
+    // The first warning is only reported once no error has been found
+    let mut warning = None;
+
     // Check for duplicate chain configuration and invalid trust thresholds
     let mut unique_chain_ids = BTreeSet::new();
     for c in config.chains.iter() {
@@ -103,11 +149,32 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(c.id(), c)?;
+
+        // Validate the settings specific to the chain type
+        match validate_chain_settings(c) {
+            Err(Diagnostic::Warning(e)) => {
+                warning.get_or_insert(e);
+            }
+            result => result?,
+        }
     }
 
     // Check for invalid mode config
     validate_mode(&config.mode)?;
 
+    match warning {
+        Some(e) => Err(Diagnostic::Warning(e)),
+        None => Ok(()),
+    }
+}
+
+/// Resolve on-chain the contracts referenced by each chain of the configuration.
+pub fn validate_config_online(config: &Config) -> Result<(), Diagnostic<Error>> {
+    let rt = tokio::runtime::Runtime::new().expect("failed to create Tokio runtime");
+    for c in config.chains.iter() {
+        ibc_relayer::config::online::resolve_contracts(&rt, c)
+            .map_err(|e| Diagnostic::Error(Error::unresolved_contract(c.id().clone(), e)))?;
+    }
     Ok(())
 }
 
@@ -179,3 +246,362 @@ fn validate_gas_settings(id: &ChainId, config: &ChainConfig) -> Result<(), Diagn
 
     Ok(())
 }
+
+fn validate_chain_settings(config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    match config {
+        ChainConfig::Cosmos(_) => Ok(()),
+        ChainConfig::Eth(c) => validate_eth_settings(c),
+        ChainConfig::Ckb(c) => validate_ckb_settings(c),
+        ChainConfig::Ckb4Ibc(c) => validate_ckb4ibc_settings(c),
+        ChainConfig::Axon(c) => validate_axon_settings(c),
+    }
+}
+
+fn invalid_field(id: &ChainId, field: &str, reason: impl Into<String>) -> Diagnostic<Error> {
+    Diagnostic::Error(Error::invalid_chain_field(
+        id.clone(),
+        field.to_string(),
+        reason.into(),
+    ))
+}
+
+/// Check that the RPC pool is not empty, the initial checkpoint is set and
+/// the forks are activated in order.
+fn validate_eth_settings(config: &EthChainConfig) -> Result<(), Diagnostic<Error>> {
+    if config.rpc_addr_pool.is_empty() {
+        return Err(invalid_field(
+            &config.id,
+            "rpc_addr_pool",
+            "at least one RPC address is required",
+        ));
+    }
+
+    if config.initial_checkpoint == [0u8; 32] {
+        return Err(invalid_field(
+            &config.id,
+            "initial_checkpoint",
+            "the checkpoint is not set",
+        ));
+    }
+
+    let forks = &config.forks;
+    let epochs = [
+        ("genesis", forks.genesis.epoch),
+        ("altair", forks.altair.epoch),
+        ("bellatrix", forks.bellatrix.epoch),
+        ("capella", forks.capella.epoch),
+    ];
+    for pair in epochs.windows(2) {
+        let ((prev_name, prev_epoch), (name, epoch)) = (pair[0], pair[1]);
+        if epoch < prev_epoch {
+            return Err(invalid_field(
+                &config.id,
+                "forks",
+                format!("`{name}` epoch {epoch} is before `{prev_name}` epoch {prev_epoch}"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the multi-client has at least one client cell besides its info
+/// cell, and that the light client contracts are set.
+fn validate_ckb_settings(config: &CkbChainConfig) -> Result<(), Diagnostic<Error>> {
+    if config.client_type_args.cells_count < 2 {
+        return Err(invalid_field(
+            &config.id,
+            "client_type_args.cells_count",
+            format!(
+                "{} cells cannot hold the info cell and at least one client cell",
+                config.client_type_args.cells_count
+            ),
+        ));
+    }
+
+    if config.minimal_updates_count == 0 {
+        return Err(invalid_field(
+            &config.id,
+            "minimal_updates_count",
+            "at least one update is required",
+        ));
+    }
+
+    validate_type_args(
+        &config.id,
+        &[
+            (
+                "lightclient_contract_typeargs",
+                &config.lightclient_contract_typeargs,
+            ),
+            (
+                "lightclient_lock_typeargs",
+                &config.lightclient_lock_typeargs,
+            ),
+        ],
+    )
+}
+
+/// Check that the IBC contracts are set and distinct, and that every on-chain
+/// light client is usable and points to its own client cell.
+fn validate_ckb4ibc_settings(config: &Ckb4IbcChainConfig) -> Result<(), Diagnostic<Error>> {
+    if config.store_prefix.is_empty() {
+        return Err(invalid_field(
+            &config.id,
+            "store_prefix",
+            "the prefix cannot be empty",
+        ));
+    }
+
+    validate_type_args(
+        &config.id,
+        &[
+            ("client_code_hash", &config.client_code_hash),
+            ("connection_type_args", &config.connection_type_args),
+            ("channel_type_args", &config.channel_type_args),
+            ("packet_type_args", &config.packet_type_args),
+        ],
+    )?;
+
+    if config.onchain_light_clients.is_empty() {
+        return Err(Diagnostic::Error(Error::missing_light_clients(
+            config.id.clone(),
+        )));
+    }
+
+    let mut client_cells = Vec::new();
+    let mut client_ids = BTreeSet::new();
+    for (client_type, light_client) in config.onchain_light_clients.iter() {
+        let field = format!("onchain_light_clients.{client_type}");
+        if light_client.chain_id == config.id {
+            return Err(invalid_field(
+                &config.id,
+                &field,
+                "the light client must track another chain",
+            ));
+        }
+        if light_client.client_cell_type_args == Default::default() {
+            return Err(invalid_field(
+                &config.id,
+                &field,
+                "`client_cell_type_args` is not set",
+            ));
+        }
+        if client_cells.contains(&&light_client.client_cell_type_args) {
+            return Err(invalid_field(
+                &config.id,
+                &field,
+                "`client_cell_type_args` is shared with another light client",
+            ));
+        }
+        client_cells.push(&light_client.client_cell_type_args);
+        let client_id = config
+            .lc_client_id(*client_type)
+            .map_err(|e| invalid_field(&config.id, &field, e.to_string()))?;
+        if !client_ids.insert(client_id.clone()) {
+            return Err(invalid_field(
+                &config.id,
+                &field,
+                format!("client id {client_id} is shared with another light client"),
+            ));
+        }
+    }
+
+    match config.reclaim.abandoned_block_gap {
+        Some(gap) if gap < MIN_ABANDONED_BLOCK_GAP => Err(invalid_field(
+            &config.id,
+            "reclaim.abandoned_block_gap",
+            format!("applications must be given at least {MIN_ABANDONED_BLOCK_GAP} blocks"),
+        )),
+        None if config.reclaim.enabled => Err(Diagnostic::Warning(Error::invalid_chain_field(
+            config.id.clone(),
+            "reclaim.enabled".to_owned(),
+            "nothing is reclaimed unless `reclaim.abandoned_block_gap` is set".to_owned(),
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the contracts are set and distinct, and that the WebSocket and
+/// RPC endpoints are those of the same node.
+fn validate_axon_settings(config: &AxonChainConfig) -> Result<(), Diagnostic<Error>> {
+    if config.store_prefix.is_empty() {
+        return Err(invalid_field(
+            &config.id,
+            "store_prefix",
+            "the prefix cannot be empty",
+        ));
+    }
+
+    if config.contract_address.is_zero() {
+        return Err(invalid_field(
+            &config.id,
+            "contract_address",
+            "the address is not set",
+        ));
+    }
+    if config.transfer_contract_address.is_zero() {
+        return Err(invalid_field(
+            &config.id,
+            "transfer_contract_address",
+            "the address is not set",
+        ));
+    }
+    if config.contract_address == config.transfer_contract_address {
+        return Err(invalid_field(
+            &config.id,
+            "transfer_contract_address",
+            "the address is the same as `contract_address`",
+        ));
+    }
+    if let Some(fee_contract_address) = config.fee_contract_address {
+        if fee_contract_address.is_zero()
+            || fee_contract_address == config.contract_address
+            || fee_contract_address == config.transfer_contract_address
+        {
+            return Err(invalid_field(
+                &config.id,
+                "fee_contract_address",
+                "the address is not set or is the one of another contract",
+            ));
+        }
+    }
+
+    // the balances of the tokens are queried from their ERC20 contracts
+    for token in &config.balance.tokens {
+        let address = token.trim_start_matches("0x");
+        if address.len() != 40 || subtle_encoding::hex::decode(address).is_err() {
+            return Err(invalid_field(
+                &config.id,
+                "balance.tokens",
+                format!("`{token}` is not an ERC20 contract address"),
+            ));
+        }
+    }
+
+    if config.event_source == EventSource::Polling && config.poll_interval.is_zero() {
+        return Err(invalid_field(
+            &config.id,
+            "poll_interval",
+            "the interval cannot be zero when polling events",
+        ));
+    }
+
+    let rpc_addr = &config.rpc_addr;
+    if !rpc_addr.to_string().starts_with("http") {
+        return Err(invalid_field(
+            &config.id,
+            "rpc_addr",
+            "an HTTP or HTTPS URL is required",
+        ));
+    }
+    let websocket_addr = tendermint_rpc::Url::from(config.websocket_addr.clone());
+    if websocket_addr.host() != rpc_addr.host()
+        || websocket_addr.is_secure() != rpc_addr.is_secure()
+    {
+        return Err(Diagnostic::Warning(Error::mismatched_endpoints(
+            config.id.clone(),
+            websocket_addr.to_string(),
+            rpc_addr.to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the type args of the contracts are set and pairwise distinct.
+fn validate_type_args<H: Default + PartialEq>(
+    id: &ChainId,
+    type_args: &[(&str, &H)],
+) -> Result<(), Diagnostic<Error>> {
+    for (i, (field, args)) in type_args.iter().enumerate() {
+        if **args == H::default() {
+            return Err(invalid_field(id, field, "the type args are not set"));
+        }
+        if let Some((other, _)) = type_args[..i].iter().find(|(_, other)| other == args) {
+            return Err(invalid_field(
+                id,
+                field,
+                format!("the type args are the same as `{other}`"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn axon_config(websocket_addr: &str, transfer_contract_address: &str) -> AxonChainConfig {
+        serde_json::from_value(serde_json::json!({
+            "id": "axon-0",
+            "websocket_addr": websocket_addr,
+            "rpc_addr": "http://127.0.0.1:8000",
+            "contract_address": "0x1111111111111111111111111111111111111111",
+            "transfer_contract_address": transfer_contract_address,
+            "restore_block_count": 10000,
+            "key_name": "relayer",
+            "store_prefix": "forcerelay",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_axon_settings() {
+        let transfer_contract_address = "0x2222222222222222222222222222222222222222";
+        let config = axon_config("ws://127.0.0.1:8010", transfer_contract_address);
+        assert!(validate_axon_settings(&config).is_ok());
+
+        let config = axon_config("ws://10.0.0.1:8010", transfer_contract_address);
+        assert!(matches!(
+            validate_axon_settings(&config),
+            Err(Diagnostic::Warning(_))
+        ));
+
+        let config = axon_config(
+            "ws://127.0.0.1:8010",
+            "0x1111111111111111111111111111111111111111",
+        );
+        let Err(Diagnostic::Error(e)) = validate_axon_settings(&config) else {
+            panic!("same contract addresses must be rejected");
+        };
+        assert!(e.to_string().contains("`transfer_contract_address`"));
+
+        let mut config = axon_config("ws://127.0.0.1:8010", transfer_contract_address);
+        config.balance.tokens = vec![transfer_contract_address.to_owned()];
+        assert!(validate_axon_settings(&config).is_ok());
+        config.balance.tokens = vec!["sudt".to_owned()];
+        let Err(Diagnostic::Error(e)) = validate_axon_settings(&config) else {
+            panic!("tokens must be ERC20 contract addresses");
+        };
+        assert!(e.to_string().contains("`balance.tokens`"));
+
+        let mut config = axon_config("ws://127.0.0.1:8010", transfer_contract_address);
+        config.fee_contract_address = Some(
+            "0x3333333333333333333333333333333333333333"
+                .parse()
+                .unwrap(),
+        );
+        assert!(validate_axon_settings(&config).is_ok());
+        config.fee_contract_address = Some(config.transfer_contract_address);
+        let Err(Diagnostic::Error(e)) = validate_axon_settings(&config) else {
+            panic!("the fee contract must be a contract of its own");
+        };
+        assert!(e.to_string().contains("`fee_contract_address`"));
+    }
+
+    #[test]
+    fn test_validate_type_args() {
+        let id = ChainId::from_string("ckb4ibc-0");
+        let (zero, one, two) = ([0u8; 2], [1u8; 2], [2u8; 2]);
+
+        assert!(validate_type_args(&id, &[("a", &one), ("b", &two)]).is_ok());
+        assert!(validate_type_args(&id, &[("a", &one), ("b", &zero)]).is_err());
+
+        let Err(Diagnostic::Error(e)) = validate_type_args(&id, &[("a", &one), ("b", &one)]) else {
+            panic!("duplicate type args must be rejected");
+        };
+        assert!(e.to_string().contains("the same as `a`"));
+    }
+}
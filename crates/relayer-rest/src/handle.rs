@@ -1,18 +1,27 @@
 use core::fmt::Debug;
+use core::str::FromStr;
 
 use tracing::error;
 
 use crossbeam_channel as channel;
 
-use ibc_relayer::supervisor::dump_state::SupervisorState;
+use ibc_relayer::supervisor::dump_state::{SupervisorState, WorkerDesc};
 use ibc_relayer::{
+    client_state::IdentifiedAnyClientState,
     config::ChainConfig,
     rest::{
-        request::{reply_channel, ReplySender, Request, VersionInfo},
+        request::{reply_channel, PendingPacketCounts, ReplySender, Request, VersionInfo},
         RestApiError,
     },
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::{
+    ics03_connection::connection::IdentifiedConnectionEnd,
+    ics04_channel::channel::IdentifiedChannelEnd,
+    ics24_host::{
+        error::ValidationError,
+        identifier::{ChainId, ChannelId, ClientId, PortId},
+    },
+};
 
 pub const NAME: &str = env!(
     "CARGO_PKG_NAME",
@@ -58,6 +67,93 @@ pub fn chain_config(
     })
 }
 
+fn parse_id<T>(id: &str) -> Result<T, RestApiError>
+where
+    T: FromStr<Err = ValidationError>,
+{
+    id.parse()
+        .map_err(|e: ValidationError| RestApiError::InvalidIdentifier(id.to_string(), e.0))
+}
+
+pub fn chain_clients(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<Vec<IdentifiedAnyClientState>, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetClients {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn chain_connections(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<Vec<IdentifiedConnectionEnd>, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetConnections {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn chain_channels(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<Vec<IdentifiedChannelEnd>, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetChannels {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn pending_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    port_id: &str,
+    channel_id: &str,
+) -> Result<PendingPacketCounts, RestApiError> {
+    let port_id: PortId = parse_id(port_id)?;
+    let channel_id: ChannelId = parse_id(channel_id)?;
+    submit_request(sender, |reply_to| Request::GetPendingPackets {
+        chain_id: ChainId::from_string(chain_id),
+        port_id,
+        channel_id,
+        reply_to,
+    })
+}
+
+pub fn chain_workers(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<Vec<WorkerDesc>, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetWorkers {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn clear_packets(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::ClearPackets {
+        chain_id: ChainId::from_string(chain_id),
+        reply_to,
+    })
+}
+
+pub fn refresh_client(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    client_id: &str,
+) -> Result<bool, RestApiError> {
+    let client_id: ClientId = parse_id(client_id)?;
+    submit_request(sender, |reply_to| Request::RefreshClient {
+        chain_id: ChainId::from_string(chain_id),
+        client_id,
+        reply_to,
+    })
+}
+
 pub fn supervisor_state(
     sender: &channel::Sender<Request>,
 ) -> Result<SupervisorState, RestApiError> {
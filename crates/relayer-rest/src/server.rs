@@ -7,7 +7,11 @@ use tracing::{info, trace};
 use ibc_relayer::rest::request::Request;
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{
+        all_chain_ids, assemble_version_info, chain_channels, chain_clients, chain_config,
+        chain_connections, chain_workers, clear_packets, pending_packets, refresh_client,
+        supervisor_state,
+    },
     Config,
 };
 
@@ -76,6 +80,48 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (GET) (/chain/{id: String}/clients) => {
+                trace!("[rest] GET /chain/{}/clients", id);
+                let result = chain_clients(&sender, &id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/chain/{id: String}/connections) => {
+                trace!("[rest] GET /chain/{}/connections", id);
+                let result = chain_connections(&sender, &id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/chain/{id: String}/channels) => {
+                trace!("[rest] GET /chain/{}/channels", id);
+                let result = chain_channels(&sender, &id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/chain/{id: String}/channel/{port: String}/{channel: String}/pending) => {
+                trace!("[rest] GET /chain/{}/channel/{}/{}/pending", id, port, channel);
+                let result = pending_packets(&sender, &id, &port, &channel);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (GET) (/chain/{id: String}/workers) => {
+                trace!("[rest] GET /chain/{}/workers", id);
+                let result = chain_workers(&sender, &id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/chain/{id: String}/clear_packets) => {
+                trace!("[rest] POST /chain/{}/clear_packets", id);
+                let result = clear_packets(&sender, &id);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
+            (POST) (/chain/{id: String}/client/{client: String}/refresh) => {
+                trace!("[rest] POST /chain/{}/client/{}/refresh", id, client);
+                let result = refresh_client(&sender, &id, &client);
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
             (GET) (/state) => {
                 trace!("[rest] GET /state");
                 let result = supervisor_state(&sender);
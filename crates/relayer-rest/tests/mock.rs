@@ -4,10 +4,10 @@ use serde::{Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
-    rest::request::{Request, VersionInfo},
+    rest::request::{PendingPacketCounts, Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
 };
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
 use ibc_relayer_rest::{server::spawn, Config};
 
@@ -25,6 +25,14 @@ enum JsonResult<R, E> {
 }
 
 fn run_test<R, F>(port: u16, path: &str, expected: R, handler: F)
+where
+    R: Serialize,
+    F: FnOnce(Request) -> TestResult + Send + 'static,
+{
+    run_request_test(port, "GET", path, expected, handler)
+}
+
+fn run_request_test<R, F>(port: u16, method: &str, path: &str, expected: R, handler: F)
 where
     R: Serialize,
     F: FnOnce(Request) -> TestResult + Send + 'static,
@@ -41,7 +49,7 @@ where
         Err(e) => panic!("got an error: {e}"),
     });
 
-    let response = ureq::get(&format!("http://127.0.0.1:{port}{path}"))
+    let response = ureq::request(method, &format!("http://127.0.0.1:{port}{path}"))
         .call()
         .unwrap()
         .into_string()
@@ -137,3 +145,90 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+#[test]
+fn get_workers() {
+    let result: JsonResult<Vec<()>, ()> = JsonResult::Success(vec![]);
+
+    run_test(19105, "/chain/mock-0/workers", result, |req| match req {
+        Request::GetWorkers { chain_id, reply_to } if chain_id.as_str() == "mock-0" => {
+            reply_to.send(Ok(vec![])).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    });
+}
+
+#[test]
+fn get_pending_packets() {
+    let counts = PendingPacketCounts {
+        unreceived_packets: 3,
+        unreceived_acks: 1,
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(counts.clone());
+
+    run_test(
+        19106,
+        "/chain/mock-0/channel/transfer/channel-0/pending",
+        result,
+        |req| match req {
+            Request::GetPendingPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } if chain_id.as_str() == "mock-0"
+                && port_id == PortId::transfer()
+                && channel_id == ChannelId::new(0) =>
+            {
+                reply_to.send(Ok(counts)).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn clear_packets() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_request_test(
+        19107,
+        "POST",
+        "/chain/mock-0/clear_packets",
+        result,
+        |req| match req {
+            Request::ClearPackets { chain_id, reply_to } if chain_id.as_str() == "mock-0" => {
+                reply_to.send(Ok(())).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
+
+#[test]
+fn refresh_client() {
+    let result: JsonResult<_, ()> = JsonResult::Success(true);
+
+    run_request_test(
+        19108,
+        "POST",
+        "/chain/mock-0/client/07-tendermint-0/refresh",
+        result,
+        |req| match req {
+            Request::RefreshClient {
+                chain_id,
+                client_id,
+                reply_to,
+            } if chain_id.as_str() == "mock-0"
+                && client_id == ClientId::from_str("07-tendermint-0").unwrap() =>
+            {
+                reply_to.send(Ok(true)).unwrap();
+                TestResult::Success
+            }
+            req => TestResult::WrongRequest(req),
+        },
+    );
+}
@@ -0,0 +1,206 @@
+//! A RocksDB database shared by the subsystems of one chain.
+//!
+//! Each subsystem owns some column families, see [`columns`], and accesses its
+//! data through the typed [`Schema`]s. The layout of the database is versioned,
+//! the migrations are applied when it is opened.
+//!
+//! [`Schema`]: crate::schemas::typed::Schema
+
+use std::{path::Path, sync::Arc};
+
+use rocksdb::{
+    prelude::{
+        Delete as _, DeleteCF as _, GetColumnFamilys as _, GetPinned as _, GetPinnedCF as _,
+        IterateCF as _, OpenCF as _, Put as _, PutCF as _,
+    },
+    ColumnFamily, ColumnFamilyDescriptor, DBPinnableSlice, IteratorMode, Options, DB,
+};
+
+use crate::{
+    error::{Error, Result},
+    schemas::{
+        columns::{self, Column},
+        keys,
+        typed::{Codec, Schema},
+    },
+};
+
+/// The version of the database layout
+pub const VERSION: u64 = 1;
+
+/// Upgrade the database from `version - 1` to `version`
+type Migration = fn(&Database) -> Result<()>;
+
+/// Migrations indexed by the version they upgrade from
+const MIGRATIONS: [Migration; VERSION as usize] = [
+    // v0 -> v1: the columns besides the beacon header MMR are created when opening
+    |_| Ok(()),
+];
+
+#[derive(Clone)]
+pub struct Database {
+    db: Arc<DB>,
+}
+
+impl Database {
+    /// Open the database at `path` with all the columns and migrate it to [`VERSION`]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let cf_descriptors: Vec<_> = columns::ALL
+            .iter()
+            .map(|c| ColumnFamilyDescriptor::new(*c, Options::default()))
+            .collect();
+
+        let opts = {
+            let mut opts = Options::default();
+            opts.create_if_missing(true);
+            opts.create_missing_column_families(true);
+            opts
+        };
+
+        let db = DB::open_cf_descriptors(&opts, path.as_ref(), cf_descriptors)?;
+        let database = Self { db: Arc::new(db) };
+        database.migrate()?;
+
+        Ok(database)
+    }
+
+    /// The version of the database layout, `0` for databases created before versioning
+    pub fn version(&self) -> Result<u64> {
+        self.get(keys::MIGRATION_VERSION_KEY)?
+            .map(|raw| u64::decode(&raw))
+            .transpose()
+            .map(|version| version.unwrap_or_default())
+    }
+
+    fn migrate(&self) -> Result<()> {
+        let version = self.version()?;
+        if version > VERSION {
+            return Err(Error::storage(format!(
+                "database version {version} is newer than the supported version {VERSION}, \
+                please upgrade the relayer"
+            )));
+        }
+        for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            migration(self)?;
+            self.put(keys::MIGRATION_VERSION_KEY, (from as u64 + 1).encode())?;
+        }
+        Ok(())
+    }
+
+    pub fn get_typed<T: Schema>(&self, key: &T::Key) -> Result<Option<T::Value>> {
+        self.get_cf(T::COLUMN, key.encode())?
+            .map(|raw| T::Value::decode(&raw))
+            .transpose()
+    }
+
+    pub fn put_typed<T: Schema>(&self, key: &T::Key, value: &T::Value) -> Result<()> {
+        self.put_cf(T::COLUMN, key.encode(), value.encode())
+    }
+
+    pub fn delete_typed<T: Schema>(&self, key: &T::Key) -> Result<()> {
+        self.delete_cf(T::COLUMN, key.encode())
+    }
+
+    /// All the entries of the schema, in the order of the encoded keys
+    pub fn iter_typed<T: Schema>(&self) -> Result<Vec<(T::Key, T::Value)>> {
+        let cf = cf_handle(&self.db, T::COLUMN)?;
+        self.db
+            .iterator_cf(cf, IteratorMode::Start)?
+            .map(|(key, value)| Ok((T::Key::decode(&key)?, T::Value::decode(&value)?)))
+            .collect()
+    }
+
+    pub(crate) fn get<K: AsRef<[u8]>>(&self, key: K) -> Result<Option<DBPinnableSlice>> {
+        self.db.get_pinned(key.as_ref()).map_err(Into::into)
+    }
+
+    pub(crate) fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, key: K, value: V) -> Result<()> {
+        self.db
+            .put(key.as_ref(), value.as_ref())
+            .map_err(Into::into)
+    }
+
+    pub(crate) fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<()> {
+        self.db.delete(key.as_ref()).map_err(Into::into)
+    }
+
+    pub(crate) fn get_cf<K: AsRef<[u8]>>(
+        &self,
+        col: Column,
+        key: K,
+    ) -> Result<Option<DBPinnableSlice>> {
+        let cf = cf_handle(&self.db, col)?;
+        self.db.get_pinned_cf(cf, key.as_ref()).map_err(Into::into)
+    }
+
+    pub(crate) fn put_cf<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        col: Column,
+        key: K,
+        value: V,
+    ) -> Result<()> {
+        let cf = cf_handle(&self.db, col)?;
+        self.db
+            .put_cf(cf, key.as_ref(), value.as_ref())
+            .map_err(Into::into)
+    }
+
+    pub(crate) fn delete_cf<K: AsRef<[u8]>>(&self, col: Column, key: K) -> Result<()> {
+        let cf = cf_handle(&self.db, col)?;
+        self.db.delete_cf(cf, key.as_ref()).map_err(Into::into)
+    }
+}
+
+fn cf_handle(db: &DB, col: Column) -> Result<&ColumnFamily> {
+    db.cf_handle(col)
+        .ok_or_else(|| Error::storage(format!("column {} not found", col)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct PendingTxs;
+
+    impl Schema for PendingTxs {
+        const COLUMN: Column = columns::COLUMN_PENDING_TXS;
+        type Key = [u8; 32];
+        type Value = u64;
+    }
+
+    #[test]
+    fn test_typed_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path()).unwrap();
+        assert_eq!(db.version().unwrap(), VERSION);
+
+        db.put_typed::<PendingTxs>(&[2; 32], &20).unwrap();
+        db.put_typed::<PendingTxs>(&[1; 32], &10).unwrap();
+        assert_eq!(db.get_typed::<PendingTxs>(&[1; 32]).unwrap(), Some(10));
+        assert_eq!(
+            db.iter_typed::<PendingTxs>().unwrap(),
+            vec![([1; 32], 10), ([2; 32], 20)]
+        );
+
+        db.delete_typed::<PendingTxs>(&[1; 32]).unwrap();
+        assert_eq!(db.get_typed::<PendingTxs>(&[1; 32]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = Database::open(dir.path()).unwrap();
+            // a database created before versioning
+            db.delete(keys::MIGRATION_VERSION_KEY).unwrap();
+            assert_eq!(db.version().unwrap(), 0);
+        }
+        {
+            let db = Database::open(dir.path()).unwrap();
+            assert_eq!(db.version().unwrap(), VERSION);
+            db.put(keys::MIGRATION_VERSION_KEY, (VERSION + 1).encode())
+                .unwrap();
+        }
+        assert!(Database::open(dir.path()).is_err());
+    }
+}
@@ -1,8 +1,11 @@
+pub mod database;
 pub mod error;
 pub mod prelude;
 pub mod schemas;
+pub mod snapshot;
 
 pub type Slot = u64;
 
 mod storage;
+pub use database::Database;
 pub use storage::Storage;
@@ -12,6 +12,7 @@ use crate::{
 pub trait StorageReader<S: EthSpec>: Send + Sync + Sized {
     fn get_base_beacon_header_slot(&self) -> Result<Option<Slot>>;
     fn get_tip_beacon_header_slot(&self) -> Result<Option<Slot>>;
+    fn get_pruned_beacon_header_slot(&self) -> Result<Option<Slot>>;
 
     fn get_beacon_header_digest(&self, position: u64) -> Result<Option<packed::HeaderDigest>>;
 }
@@ -19,11 +20,14 @@ pub trait StorageReader<S: EthSpec>: Send + Sync + Sized {
 pub trait StorageWriter<S: EthSpec>: Send + Sync + Sized {
     fn put_base_beacon_header_slot(&self, slot: Slot) -> Result<()>;
     fn put_tip_beacon_header_slot(&self, slot: Slot) -> Result<()>;
+    fn put_pruned_beacon_header_slot(&self, slot: Slot) -> Result<()>;
 
     fn delete_base_beacon_header_slot(&self) -> Result<()>;
     fn delete_tip_beacon_header_slot(&self) -> Result<()>;
+    fn delete_pruned_beacon_header_slot(&self) -> Result<()>;
 
     fn put_beacon_header_digest(&self, position: u64, digest: &packed::HeaderDigest) -> Result<()>;
+    fn delete_beacon_header_digest(&self, position: u64) -> Result<()>;
 }
 
 pub trait StorageAsMMRStore<S: EthSpec>:
@@ -41,10 +45,19 @@ pub trait StorageAsMMRStore<S: EthSpec>:
 
     fn rollback_to(&self, slot_opt: Option<Slot>) -> Result<()> {
         if let Some(slot) = slot_opt {
+            // the nodes to re-append the headers after a pruned slot are gone
+            if let Some(pruned) = self.get_pruned_beacon_header_slot()? {
+                if slot < pruned {
+                    return Err(Error::data(format!(
+                        "can't rollback to slot {slot} which is before the pruned slot {pruned}"
+                    )));
+                }
+            }
             self.put_tip_beacon_header_slot(slot)?;
         } else {
             self.delete_base_beacon_header_slot()?;
             self.delete_tip_beacon_header_slot()?;
+            self.delete_pruned_beacon_header_slot()?;
         }
         Ok(())
     }
@@ -57,6 +70,63 @@ pub trait StorageAsMMRStore<S: EthSpec>:
         Ok(())
     }
 
+    /// Removes the MMR nodes which are only needed to prove the headers before `slot`,
+    /// returns the number of removed nodes.
+    ///
+    /// Appending new headers and proving the ones from `slot` only requires the peaks
+    /// of the MMR of the headers before `slot` and the nodes after it. `slot` is kept
+    /// as the watermark of the next pruning, which only visits the peaks kept by this
+    /// one and the nodes appended since.
+    fn prune_before(&self, slot: Slot) -> Result<u64> {
+        let base = self
+            .get_base_beacon_header_slot()?
+            .ok_or_else(|| Error::data("no headers"))?;
+        let tip = self
+            .get_tip_beacon_header_slot()?
+            .ok_or_else(|| Error::data("no headers"))?;
+        if slot < base || slot > tip {
+            return Err(Error::data(format!(
+                "slot {slot} is out of the stored range [{base}, {tip}]"
+            )));
+        }
+        let pruned = self.get_pruned_beacon_header_slot()?.unwrap_or(base);
+        if slot <= pruned {
+            return Ok(0);
+        }
+        // the position of a leaf is the size of the MMR of the leaves before it
+        let pruned_size = mmr::lib::leaf_index_to_pos(pruned - base);
+        let mmr_size = mmr::lib::leaf_index_to_pos(slot - base);
+        let pruned_peaks = if pruned_size == 0 {
+            vec![]
+        } else {
+            mmr::lib::helper::get_peaks(pruned_size)
+        };
+        let peaks = mmr::lib::helper::get_peaks(mmr_size);
+        let mut removed = 0;
+        for position in pruned_peaks.into_iter().chain(pruned_size..mmr_size) {
+            if peaks.contains(&position) {
+                continue;
+            }
+            if self.get_beacon_header_digest(position)?.is_some() {
+                self.delete_beacon_header_digest(position)?;
+                removed += 1;
+            }
+        }
+        self.put_pruned_beacon_header_slot(slot)?;
+        Ok(removed)
+    }
+
+    /// Returns the number of nodes of the MMR up to the tip slot.
+    fn mmr_size(&self) -> Result<u64> {
+        match (
+            self.get_base_beacon_header_slot()?,
+            self.get_tip_beacon_header_slot()?,
+        ) {
+            (Some(base), Some(tip)) => Ok(mmr::lib::leaf_index_to_mmr_size(tip - base)),
+            _ => Ok(0),
+        }
+    }
+
     /// Returns the chain root MMR for a provided slot.
     fn chain_root_mmr(&self, curr: Slot) -> Result<ClientRootMMR<Self>> {
         if let Some(base) = self.get_base_beacon_header_slot()? {
@@ -4,7 +4,24 @@
 pub type Column = &'static str;
 
 /// Total column number
-pub const COUNT: usize = 1;
+pub const COUNT: usize = 5;
 
 /// Column to store MMR for beacon headers
 pub const COLUMN_BEACON_HEADER_MMR: Column = "beacon-header-mmr";
+/// Column to store the cursors of the cells scanned on Ckb4Ibc chains
+pub const COLUMN_CKB4IBC_CURSORS: Column = "ckb4ibc-cursors";
+/// Column to store the positions of the logs processed by Axon event monitors
+pub const COLUMN_AXON_MONITOR: Column = "axon-monitor";
+/// Column to store the denominations registered by cross-chain transfers
+pub const COLUMN_DENOM_REGISTRY: Column = "denom-registry";
+/// Column to store the transactions sent but not yet committed
+pub const COLUMN_PENDING_TXS: Column = "pending-txs";
+
+/// All the columns, which are created when opening the database
+pub const ALL: [Column; COUNT] = [
+    COLUMN_BEACON_HEADER_MMR,
+    COLUMN_CKB4IBC_CURSORS,
+    COLUMN_AXON_MONITOR,
+    COLUMN_DENOM_REGISTRY,
+    COLUMN_PENDING_TXS,
+];
@@ -7,3 +7,5 @@ pub const MIGRATION_VERSION_KEY: &[u8] = b"db-version";
 pub const BASE_BEACON_HEADER_SLOT: &[u8] = b"base-beacon-header-slot";
 /// The current tip beacon header.
 pub const TIP_BEACON_HEADER_SLOT: &[u8] = b"tip-beacon-header-slot";
+/// The lowest slot whose header can still be proven after pruning the MMR.
+pub const PRUNED_BEACON_HEADER_SLOT: &[u8] = b"pruned-beacon-header-slot";
@@ -0,0 +1,86 @@
+//! Typed key-value schemas on top of the column families.
+//!
+//! A subsystem defines its own [`Schema`] binding a column to the types of its
+//! keys and values, then reads and writes them through a shared [`Database`].
+//!
+//! [`Database`]: crate::Database
+
+use crate::error::{Error, Result};
+
+use super::columns::Column;
+
+/// Binary encoding of the keys and values stored in the database
+pub trait Codec: Sized {
+    fn encode(&self) -> Vec<u8>;
+    fn decode(raw: &[u8]) -> Result<Self>;
+}
+
+pub trait Schema {
+    const COLUMN: Column;
+    type Key: Codec;
+    type Value: Codec;
+}
+
+/// Encoded in big-endian, so that the keys are iterated in numeric order
+impl Codec for u64 {
+    fn encode(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn decode(raw: &[u8]) -> Result<Self> {
+        let bytes = raw
+            .try_into()
+            .map_err(|_| Error::data(format!("invalid u64 of {} bytes", raw.len())))?;
+        Ok(u64::from_be_bytes(bytes))
+    }
+}
+
+impl Codec for [u8; 32] {
+    fn encode(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn decode(raw: &[u8]) -> Result<Self> {
+        raw.try_into()
+            .map_err(|_| Error::data(format!("invalid hash of {} bytes", raw.len())))
+    }
+}
+
+impl Codec for Vec<u8> {
+    fn encode(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn decode(raw: &[u8]) -> Result<Self> {
+        Ok(raw.to_vec())
+    }
+}
+
+impl Codec for String {
+    fn encode(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn decode(raw: &[u8]) -> Result<Self> {
+        String::from_utf8(raw.to_vec()).map_err(Error::data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec_roundtrip() {
+        assert_eq!(u64::decode(&42u64.encode()).unwrap(), 42);
+        assert!(1u64.encode() < 256u64.encode());
+        assert!(u64::decode(&[0; 4]).is_err());
+
+        assert_eq!(<[u8; 32]>::decode(&[7; 32].encode()).unwrap(), [7; 32]);
+        assert!(<[u8; 32]>::decode(&[7; 31]).is_err());
+
+        let denom = "transfer/channel-0/uckb".to_owned();
+        assert_eq!(String::decode(&denom.encode()).unwrap(), denom);
+        assert!(String::decode(&[0xff]).is_err());
+    }
+}
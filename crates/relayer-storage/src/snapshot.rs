@@ -0,0 +1,195 @@
+//! Portable snapshots of the beacon header MMR, used to seed a new relayer
+//! instance without replaying all the headers.
+//!
+//! Layout of a snapshot file, all integers are little-endian:
+//!
+//! ```text
+//! magic (8 bytes) | base slot (u64) | tip slot (u64) | digests count (u64)
+//! | (position (u64) | digest length (u32) | digest) * count
+//! | sha256 checksum of all the previous bytes (32 bytes)
+//! ```
+
+use std::io::{Read, Write};
+
+use eth2_types::EthSpec;
+use eth_light_client_in_ckb_verification::{
+    mmr,
+    types::{packed, prelude::*},
+};
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    error::{Error, Result},
+    prelude::StorageAsMMRStore,
+    Slot,
+};
+
+const MAGIC: &[u8; 8] = b"FRMMR\0\0\x01";
+const CHECKSUM_LEN: usize = 32;
+
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub base_slot: Slot,
+    pub tip_slot: Slot,
+    /// All the stored MMR nodes as `(position, digest)`, pruned positions are absent
+    pub digests: Vec<(u64, packed::HeaderDigest)>,
+}
+
+impl Snapshot {
+    /// Collect the MMR nodes of `storage` up to its tip slot
+    pub fn export<S, E>(storage: &S) -> Result<Self>
+    where
+        S: StorageAsMMRStore<E>,
+        E: EthSpec,
+    {
+        let (Some(base_slot), Some(tip_slot)) = (
+            storage.get_base_beacon_header_slot()?,
+            storage.get_tip_beacon_header_slot()?,
+        ) else {
+            return Err(Error::data("no headers"));
+        };
+        let mmr_size = mmr::lib::leaf_index_to_mmr_size(tip_slot - base_slot);
+        let mut digests = vec![];
+        for position in 0..mmr_size {
+            if let Some(digest) = storage.get_beacon_header_digest(position)? {
+                digests.push((position, digest));
+            }
+        }
+        Ok(Self {
+            base_slot,
+            tip_slot,
+            digests,
+        })
+    }
+
+    /// Seed the empty `storage` with the snapshot
+    pub fn import<S, E>(&self, storage: &S) -> Result<()>
+    where
+        S: StorageAsMMRStore<E>,
+        E: EthSpec,
+    {
+        if storage.is_initialized()? {
+            return Err(Error::storage(
+                "MMR snapshot can only be imported into an empty storage",
+            ));
+        }
+        for (position, digest) in &self.digests {
+            storage.put_beacon_header_digest(*position, digest)?;
+        }
+        storage.put_base_beacon_header_slot(self.base_slot)?;
+        storage.put_tip_beacon_header_slot(self.tip_slot)?;
+        Ok(())
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let mut buf = MAGIC.to_vec();
+        buf.extend_from_slice(&self.base_slot.to_le_bytes());
+        buf.extend_from_slice(&self.tip_slot.to_le_bytes());
+        buf.extend_from_slice(&(self.digests.len() as u64).to_le_bytes());
+        for (position, digest) in &self.digests {
+            buf.extend_from_slice(&position.to_le_bytes());
+            buf.extend_from_slice(&(digest.as_slice().len() as u32).to_le_bytes());
+            buf.extend_from_slice(digest.as_slice());
+        }
+        let checksum = Sha256::digest(&buf);
+        buf.extend_from_slice(&checksum);
+        writer.write_all(&buf).map_err(Error::storage)
+    }
+
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).map_err(Error::storage)?;
+        if buf.len() < MAGIC.len() + CHECKSUM_LEN || !buf.starts_with(MAGIC) {
+            return Err(Error::data("not an MMR snapshot"));
+        }
+        let (content, checksum) = buf.split_at(buf.len() - CHECKSUM_LEN);
+        if Sha256::digest(content).as_slice() != checksum {
+            return Err(Error::data("checksum mismatch of MMR snapshot"));
+        }
+
+        let mut cursor = &content[MAGIC.len()..];
+        let base_slot = read_u64(&mut cursor)?;
+        let tip_slot = read_u64(&mut cursor)?;
+        let count = read_u64(&mut cursor)?;
+        if tip_slot < base_slot {
+            return Err(Error::data(
+                "tip slot of MMR snapshot is lower than its base slot",
+            ));
+        }
+        let mut digests = vec![];
+        for _ in 0..count {
+            let position = read_u64(&mut cursor)?;
+            let len = u32::from_le_bytes(read_bytes(&mut cursor, 4)?.try_into().expect("4 bytes"));
+            let raw = read_bytes(&mut cursor, len as usize)?;
+            let digest = packed::HeaderDigestReader::from_slice(raw)?.to_entity();
+            digests.push((position, digest));
+        }
+        if !cursor.is_empty() {
+            return Err(Error::data("trailing bytes in MMR snapshot"));
+        }
+        Ok(Self {
+            base_slot,
+            tip_slot,
+            digests,
+        })
+    }
+}
+
+fn read_bytes<'a>(cursor: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if cursor.len() < len {
+        return Err(Error::data("truncated MMR snapshot"));
+    }
+    let (bytes, rest) = cursor.split_at(len);
+    *cursor = rest;
+    Ok(bytes)
+}
+
+fn read_u64(cursor: &mut &[u8]) -> Result<u64> {
+    let bytes = read_bytes(cursor, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            base_slot: 100,
+            tip_slot: 101,
+            digests: vec![
+                (0, packed::HeaderDigest::default()),
+                (2, packed::HeaderDigest::default()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut buf = vec![];
+        snapshot().write_to(&mut buf).unwrap();
+        let decoded = Snapshot::read_from(&mut buf.as_slice()).unwrap();
+        assert_eq!((decoded.base_slot, decoded.tip_slot), (100, 101));
+        assert_eq!(
+            decoded
+                .digests
+                .iter()
+                .map(|(position, digest)| (*position, digest.as_slice().to_vec()))
+                .collect::<Vec<_>>(),
+            snapshot()
+                .digests
+                .iter()
+                .map(|(position, digest)| (*position, digest.as_slice().to_vec()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_corrupted() {
+        let mut buf = vec![];
+        snapshot().write_to(&mut buf).unwrap();
+        buf[MAGIC.len()] ^= 1;
+        assert!(Snapshot::read_from(&mut buf.as_slice()).is_err());
+        assert!(Snapshot::read_from(&mut &buf[..10]).is_err());
+    }
+}
@@ -1,17 +1,8 @@
 use std::{marker::PhantomData, path::Path, sync::Arc};
 
-use rocksdb::{
-    prelude::{
-        Delete as _, GetColumnFamilys as _, GetPinned as _, GetPinnedCF as _, OpenCF as _,
-        Put as _, PutCF as _,
-    },
-    ColumnFamily, ColumnFamilyDescriptor, DBPinnableSlice, Options, DB,
-};
+use rocksdb::DBPinnableSlice;
 
-use crate::{
-    error::{Error, Result},
-    schemas::columns::{self, Column},
-};
+use crate::{database::Database, error::Result, schemas::columns::Column};
 
 mod cache;
 mod mmr;
@@ -22,53 +13,39 @@ use cache::Cache;
 
 #[derive(Clone)]
 pub struct Storage<S> {
-    pub(crate) db: Arc<DB>,
+    pub(crate) db: Database,
     pub(crate) cache: Arc<Cache>,
     _phantom_data: PhantomData<S>,
 }
 
 impl<S> Storage<S> {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let cf_names = {
-            let mut cf_names = Vec::with_capacity(columns::COUNT);
-            cf_names.push(columns::COLUMN_BEACON_HEADER_MMR.to_string());
-            cf_names
-        };
-        let cf_descriptors: Vec<_> = cf_names
-            .iter()
-            .map(|c| ColumnFamilyDescriptor::new(c, Options::default()))
-            .collect();
-
-        let opts = {
-            let mut opts = Options::default();
-            opts.create_if_missing(true);
-            opts.create_missing_column_families(true);
-            opts
-        };
+        Database::open(path).map(Self::with_database)
+    }
 
-        let db = DB::open_cf_descriptors(&opts, path.as_ref(), cf_descriptors)?;
-        let cache = Cache::default();
-        let storage = Self {
-            db: Arc::new(db),
-            cache: Arc::new(cache),
+    /// Store the beacon headers in a database shared with other subsystems
+    pub fn with_database(db: Database) -> Self {
+        Self {
+            db,
+            cache: Arc::new(Cache::default()),
             _phantom_data: PhantomData,
-        };
+        }
+    }
 
-        Ok(storage)
+    pub fn database(&self) -> &Database {
+        &self.db
     }
 
     pub(crate) fn get<K: AsRef<[u8]>>(&self, key: K) -> Result<Option<DBPinnableSlice>> {
-        self.db.get_pinned(key.as_ref()).map_err(Into::into)
+        self.db.get(key)
     }
 
     pub(crate) fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, key: K, value: V) -> Result<()> {
-        self.db
-            .put(key.as_ref(), value.as_ref())
-            .map_err(Into::into)
+        self.db.put(key, value)
     }
 
     pub(crate) fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<()> {
-        self.db.delete(key.as_ref()).map_err(Into::into)
+        self.db.delete(key)
     }
 
     pub(crate) fn get_cf<K: AsRef<[u8]>>(
@@ -76,8 +53,7 @@ impl<S> Storage<S> {
         col: Column,
         key: K,
     ) -> Result<Option<DBPinnableSlice>> {
-        let cf = cf_handle(&self.db, col)?;
-        self.db.get_pinned_cf(cf, key.as_ref()).map_err(Into::into)
+        self.db.get_cf(col, key)
     }
 
     pub(crate) fn put_cf<K: AsRef<[u8]>, V: AsRef<[u8]>>(
@@ -86,14 +62,10 @@ impl<S> Storage<S> {
         key: K,
         value: V,
     ) -> Result<()> {
-        let cf = cf_handle(&self.db, col)?;
-        self.db
-            .put_cf(cf, key.as_ref(), value.as_ref())
-            .map_err(Into::into)
+        self.db.put_cf(col, key, value)
     }
-}
 
-pub(crate) fn cf_handle(db: &DB, col: Column) -> Result<&ColumnFamily> {
-    db.cf_handle(col)
-        .ok_or_else(|| Error::storage(format!("column {} not found", col)))
+    pub(crate) fn delete_cf<K: AsRef<[u8]>>(&self, col: Column, key: K) -> Result<()> {
+        self.db.delete_cf(col, key)
+    }
 }
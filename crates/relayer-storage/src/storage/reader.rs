@@ -43,6 +43,13 @@ where
             .map_err(Into::into)
     }
 
+    fn get_pruned_beacon_header_slot(&self) -> Result<Option<Slot>> {
+        self.get(keys::PRUNED_BEACON_HEADER_SLOT)?
+            .map(|raw| packed::Uint64Reader::from_slice(&raw).map(|reader| reader.unpack()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
     fn get_beacon_header_digest(&self, position: u64) -> Result<Option<packed::HeaderDigest>> {
         let key: packed::Uint64 = position.pack();
         self.get_cf(columns::COLUMN_BEACON_HEADER_MMR, key.as_slice())?
@@ -29,6 +29,11 @@ where
         self.put(keys::TIP_BEACON_HEADER_SLOT, value.as_slice())
     }
 
+    fn put_pruned_beacon_header_slot(&self, slot: Slot) -> Result<()> {
+        let value = slot.pack();
+        self.put(keys::PRUNED_BEACON_HEADER_SLOT, value.as_slice())
+    }
+
     fn delete_base_beacon_header_slot(&self) -> Result<()> {
         let mut writer = self
             .cache
@@ -44,6 +49,10 @@ where
         self.delete(keys::TIP_BEACON_HEADER_SLOT)
     }
 
+    fn delete_pruned_beacon_header_slot(&self) -> Result<()> {
+        self.delete(keys::PRUNED_BEACON_HEADER_SLOT)
+    }
+
     fn put_beacon_header_digest(&self, position: u64, digest: &packed::HeaderDigest) -> Result<()> {
         let key: packed::Uint64 = position.pack();
         self.put_cf(
@@ -52,4 +61,9 @@ where
             digest.as_slice(),
         )
     }
+
+    fn delete_beacon_header_digest(&self, position: u64) -> Result<()> {
+        let key: packed::Uint64 = position.pack();
+        self.delete_cf(columns::COLUMN_BEACON_HEADER_MMR, key.as_slice())
+    }
 }
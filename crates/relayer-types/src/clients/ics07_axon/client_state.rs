@@ -10,6 +10,7 @@ use crate::{
     Height,
 };
 use core::convert::TryFrom;
+use core::time::Duration;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::Protobuf;
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,42 @@ pub const AXON_CLIENT_STATE_TYPE_URL: &str = "/axon.client.v1.state";
 pub struct AxonClientState {
     pub chain_id: ChainId,
     pub latest_height: Height,
+    /// How long the latest consensus state is trusted, `None` if it never expires
+    #[serde(default)]
+    pub trusting_period: Option<Duration>,
+    /// Height of the misbehaviour which froze the client
+    #[serde(default)]
+    pub frozen_height: Option<Height>,
+}
+
+impl AxonClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Self {
+        Self {
+            chain_id,
+            latest_height,
+            trusting_period: None,
+            frozen_height: None,
+        }
+    }
+
+    pub fn with_trusting_period(self, trusting_period: Option<Duration>) -> Self {
+        Self {
+            trusting_period,
+            ..self
+        }
+    }
+
+    pub fn with_frozen_height(self, h: Height) -> Self {
+        Self {
+            frozen_height: Some(h),
+            ..self
+        }
+    }
+
+    /// Get the refresh time to ensure the state does not expire
+    pub fn refresh_time(&self) -> Option<Duration> {
+        self.trusting_period.map(|period| 2 * period / 3)
+    }
 }
 
 impl Ics02ClientState for AxonClientState {
@@ -36,20 +73,25 @@ impl Ics02ClientState for AxonClientState {
     }
 
     fn frozen_height(&self) -> Option<Height> {
-        None
+        self.frozen_height
     }
 
-    fn expired(&self, _elapsed: core::time::Duration) -> bool {
-        false
+    fn expired(&self, elapsed: Duration) -> bool {
+        self.trusting_period
+            .map_or(false, |trusting_period| elapsed > trusting_period)
     }
 
+    /// The upgraded chain has no options to carry over, the client restarts
+    /// unfrozen from the upgrade height of the new chain
     fn upgrade(
         &mut self,
-        _upgrade_height: Height,
+        upgrade_height: Height,
         _upgrade_options: &dyn UpgradeOptions,
-        _chain_id: ChainId,
+        chain_id: ChainId,
     ) {
-        todo!()
+        self.latest_height = upgrade_height;
+        self.frozen_height = None;
+        self.chain_id = chain_id;
     }
 }
 
@@ -77,3 +119,61 @@ impl From<AxonClientState> for Any {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::ics07_tendermint::client_state::UpgradeOptions as TmUpgradeOptions;
+
+    fn client_state() -> AxonClientState {
+        AxonClientState::new(
+            ChainId::new("axon".to_owned(), 0),
+            Height::new(0, 10).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_expiry() {
+        let client_state = client_state();
+        assert!(!client_state.expired(Duration::MAX));
+        assert_eq!(client_state.refresh_time(), None);
+
+        let client_state = client_state.with_trusting_period(Some(Duration::from_secs(30)));
+        assert!(!client_state.expired(Duration::from_secs(30)));
+        assert!(client_state.expired(Duration::from_secs(31)));
+        assert_eq!(client_state.refresh_time(), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_upgrade_unfreezes() {
+        let mut client_state = client_state().with_frozen_height(Height::new(0, 5).unwrap());
+        assert_eq!(
+            client_state.frozen_height(),
+            Some(Height::new(0, 5).unwrap())
+        );
+
+        let upgrade_options = TmUpgradeOptions {
+            unbonding_period: Duration::ZERO,
+        };
+        let chain_id = ChainId::new("axon".to_owned(), 1);
+        client_state.upgrade(
+            Height::new(1, 1).unwrap(),
+            &upgrade_options,
+            chain_id.clone(),
+        );
+        assert_eq!(client_state.frozen_height(), None);
+        assert_eq!(client_state.latest_height(), Height::new(1, 1).unwrap());
+        assert_eq!(client_state.chain_id(), chain_id);
+    }
+
+    #[test]
+    fn test_decode_without_trusting_period() {
+        let any = Any {
+            type_url: AXON_CLIENT_STATE_TYPE_URL.to_owned(),
+            value: br#"{"chain_id":"axon-0","latest_height":{"revision_number":0,"revision_height":10}}"#
+                .to_vec(),
+        };
+        let decoded = AxonClientState::try_from(any).unwrap();
+        assert_eq!(decoded, client_state());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    clients::ics07_axon::light_block::AxonLightBlock,
     core::{ics02_client::client_type::ClientType, ics23_commitment::commitment::CommitmentRoot},
     timestamp::Timestamp,
 };
@@ -10,12 +11,23 @@ use crate::core::ics02_client::error::Error as Ics02Error;
 
 pub const AXON_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.axon.v1.ConsensusState";
 
+/// Consensus state of Axon at the height of a block, the storage proofs are verified
+/// against its `state_root`
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AxonConsensusState {
     pub root: CommitmentRoot,
     pub timestamp: Timestamp,
 }
 
+impl From<AxonLightBlock> for AxonConsensusState {
+    fn from(block: AxonLightBlock) -> Self {
+        Self {
+            root: CommitmentRoot::from_bytes(&block.state_root),
+            timestamp: block.timestamp,
+        }
+    }
+}
+
 impl crate::core::ics02_client::consensus_state::ConsensusState for AxonConsensusState {
     fn client_type(&self) -> ClientType {
         ClientType::Axon
@@ -35,13 +47,20 @@ impl Protobuf<Any> for AxonConsensusState {}
 impl TryFrom<Any> for AxonConsensusState {
     type Error = Ics02Error;
 
-    fn try_from(_value: Any) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            AXON_CONSENSUS_STATE_TYPE_URL => serde_json::from_slice::<Self>(&raw.value)
+                .map_err(|e| Ics02Error::unknown_consensus_state_type(e.to_string())),
+            _ => Err(Ics02Error::unknown_consensus_state_type(raw.type_url)),
+        }
     }
 }
 
 impl From<AxonConsensusState> for Any {
-    fn from(_value: AxonConsensusState) -> Self {
-        todo!()
+    fn from(value: AxonConsensusState) -> Self {
+        Any {
+            type_url: AXON_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: serde_json::to_vec(&value).expect("encoding to `Any` from `AxonConsensusState`"),
+        }
     }
 }
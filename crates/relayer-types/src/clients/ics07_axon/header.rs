@@ -1,3 +1,4 @@
+use super::misbehaviour::AxonSignedBlock;
 use crate::core::ics02_client::{self, error::Error as Ics02Error};
 use crate::prelude::*;
 use crate::timestamp::Timestamp;
@@ -8,9 +9,13 @@ use serde::{Deserialize, Serialize};
 
 pub const AXON_HEADER_TYPE_URL: &str = "/axon.v1.header";
 
-// FIXME: useless header which cannot be ignored by Hermes runtime
-#[derive(Clone, PartialEq, Eq, Deserialize, Serialize, Debug)]
-pub struct AxonHeader {}
+/// The header of an Axon client update, carrying the signed block the client is
+/// updated to, which allows the relayers to check the update against their own node
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize, Debug)]
+pub struct AxonHeader {
+    #[serde(default)]
+    pub signed_block: Option<AxonSignedBlock>,
+}
 
 impl ics02_client::header::Header for AxonHeader {
     fn client_type(&self) -> ics02_client::client_type::ClientType {
@@ -18,11 +23,20 @@ impl ics02_client::header::Header for AxonHeader {
     }
 
     fn height(&self) -> Height {
-        Height::default()
+        match &self.signed_block {
+            Some(signed_block) => Height::from_noncosmos_height(signed_block.number()),
+            None => Height::default(),
+        }
     }
 
     fn timestamp(&self) -> Timestamp {
-        Timestamp::none()
+        self.signed_block
+            .as_ref()
+            .and_then(|signed_block| {
+                let seconds = signed_block.block.header.timestamp;
+                Timestamp::from_nanoseconds(seconds.checked_mul(1_000_000_000)?).ok()
+            })
+            .unwrap_or_else(Timestamp::none)
     }
 }
 
@@ -1,2 +1,9 @@
+use crate::{timestamp::Timestamp, Height};
+
+/// The fields of a verified Axon block which make up the consensus state at its height
 #[derive(Debug, Clone, Default)]
-pub struct AxonLightBlock {}
+pub struct AxonLightBlock {
+    pub height: Height,
+    pub state_root: Vec<u8>,
+    pub timestamp: Timestamp,
+}
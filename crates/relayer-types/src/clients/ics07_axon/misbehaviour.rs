@@ -1,13 +1,87 @@
 use crate::prelude::*;
 
+use axon_tools::types::{Block as AxonBlock, Proof as AxonProof};
+use ethereum_types::H256;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::{Error as ProtoError, Protobuf};
 use serde::{Deserialize, Serialize};
 
-use crate::clients::ics07_tendermint::header::Header;
+use crate::core::ics02_client::error::Error as Ics02Error;
 use crate::core::ics24_host::identifier::ClientId;
+use crate::Height;
 
+pub const AXON_MISBEHAVIOUR_TYPE_URL: &str = "/axon.v1.misbehaviour";
+
+/// An Axon block along with the proof of the validators committing it
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Misbehaviour {
+pub struct AxonSignedBlock {
+    pub block: AxonBlock,
+    pub previous_state_root: H256,
+    pub proof: AxonProof,
+}
+
+impl AxonSignedBlock {
+    pub fn number(&self) -> u64 {
+        self.block.header.number
+    }
+
+    pub fn hash(&self) -> H256 {
+        self.proof.block_hash
+    }
+}
+
+/// Two different Axon blocks at the same height, both signed by the validators
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AxonMisbehaviour {
     pub client_id: ClientId,
-    pub header1: Header,
-    pub header2: Header,
+    pub block1: AxonSignedBlock,
+    pub block2: AxonSignedBlock,
+}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for AxonMisbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        Height::from_noncosmos_height(self.block1.number())
+    }
+}
+
+impl Protobuf<Any> for AxonMisbehaviour {}
+
+impl TryFrom<Any> for AxonMisbehaviour {
+    type Error = Ics02Error;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        if any.type_url != AXON_MISBEHAVIOUR_TYPE_URL {
+            return Err(Ics02Error::unknown_misbehaviour_type(any.type_url));
+        }
+        serde_json::from_slice(&any.value).map_err(|e| {
+            Ics02Error::decode_raw_misbehaviour(ProtoError::try_from_protobuf(e.to_string()))
+        })
+    }
+}
+
+impl From<AxonMisbehaviour> for Any {
+    fn from(misbehaviour: AxonMisbehaviour) -> Self {
+        let json = serde_json::to_string(&misbehaviour).expect("jsonify axon misbehaviour");
+        Any {
+            type_url: AXON_MISBEHAVIOUR_TYPE_URL.to_owned(),
+            value: json.into_bytes(),
+        }
+    }
+}
+
+impl core::fmt::Display for AxonMisbehaviour {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "{} h: {} block1: {:#x} block2: {:#x}",
+            self.client_id,
+            self.block1.number(),
+            self.block1.hash(),
+            self.block2.hash(),
+        )
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
     Height,
 };
 use core::convert::TryFrom;
+use core::time::Duration;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::Protobuf;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,56 @@ pub const CKB_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.ckb.v1.ClientStat
 pub struct CkbClientState {
     pub chain_id: ChainId,
     pub latest_height: Height,
+    /// Hash of the CKB genesis block, the block hashes of the consensus states and of the
+    /// transaction proofs are only meaningful on the chain starting from it
+    #[serde(default)]
+    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(deserialize_with = "crate::serializers::deser_hex_upper")]
+    pub genesis_hash: Vec<u8>,
+    /// How long the latest consensus state is trusted, `None` if it never expires
+    #[serde(default)]
+    pub trusting_period: Option<Duration>,
+    /// Height of the misbehaviour which froze the client
+    #[serde(default)]
+    pub frozen_height: Option<Height>,
+}
+
+impl CkbClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Self {
+        Self {
+            chain_id,
+            latest_height,
+            genesis_hash: vec![],
+            trusting_period: None,
+            frozen_height: None,
+        }
+    }
+
+    pub fn with_genesis_hash(self, genesis_hash: Vec<u8>) -> Self {
+        Self {
+            genesis_hash,
+            ..self
+        }
+    }
+
+    pub fn with_trusting_period(self, trusting_period: Option<Duration>) -> Self {
+        Self {
+            trusting_period,
+            ..self
+        }
+    }
+
+    pub fn with_frozen_height(self, h: Height) -> Self {
+        Self {
+            frozen_height: Some(h),
+            ..self
+        }
+    }
+
+    /// Get the refresh time to ensure the state does not expire
+    pub fn refresh_time(&self) -> Option<Duration> {
+        self.trusting_period.map(|period| 2 * period / 3)
+    }
 }
 
 impl Ics02ClientState for CkbClientState {
@@ -37,19 +88,25 @@ impl Ics02ClientState for CkbClientState {
     }
 
     fn frozen_height(&self) -> Option<Height> {
-        None
+        self.frozen_height
     }
 
-    fn expired(&self, _elapsed: core::time::Duration) -> bool {
-        false
+    fn expired(&self, elapsed: Duration) -> bool {
+        self.trusting_period
+            .map_or(false, |trusting_period| elapsed > trusting_period)
     }
 
+    /// The upgraded chain has no options to carry over, the client restarts
+    /// unfrozen from the upgrade height of the new chain
     fn upgrade(
         &mut self,
-        _upgrade_height: Height,
+        upgrade_height: Height,
         _upgrade_options: &dyn UpgradeOptions,
-        _chain_id: ChainId,
+        chain_id: ChainId,
     ) {
+        self.latest_height = upgrade_height;
+        self.frozen_height = None;
+        self.chain_id = chain_id;
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::{
+    clients::ics07_ckb::light_block::CkbLightBlock,
     core::{ics02_client::client_type::ClientType, ics23_commitment::commitment::CommitmentRoot},
     timestamp::Timestamp,
 };
@@ -10,8 +11,27 @@ use crate::core::ics02_client::error::Error as Ics02Error;
 
 pub const CKB_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.ckb.v1.ConsensusState";
 
+/// Consensus state of CKB at the height of a block, the transaction proofs generated
+/// from that block are verified against its `transactions_root`
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CkbConsensusState {}
+pub struct CkbConsensusState {
+    /// `transactions_root` of the block header
+    pub root: CommitmentRoot,
+    pub timestamp: Timestamp,
+    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(deserialize_with = "crate::serializers::deser_hex_upper")]
+    pub block_hash: Vec<u8>,
+}
+
+impl From<CkbLightBlock> for CkbConsensusState {
+    fn from(block: CkbLightBlock) -> Self {
+        Self {
+            root: CommitmentRoot::from_bytes(&block.transactions_root),
+            timestamp: block.timestamp,
+            block_hash: block.block_hash,
+        }
+    }
+}
 
 impl crate::core::ics02_client::consensus_state::ConsensusState for CkbConsensusState {
     fn client_type(&self) -> ClientType {
@@ -19,11 +39,11 @@ impl crate::core::ics02_client::consensus_state::ConsensusState for CkbConsensus
     }
 
     fn root(&self) -> &CommitmentRoot {
-        todo!()
+        &self.root
     }
 
     fn timestamp(&self) -> Timestamp {
-        Timestamp::none()
+        self.timestamp
     }
 }
 
@@ -32,13 +52,20 @@ impl Protobuf<Any> for CkbConsensusState {}
 impl TryFrom<Any> for CkbConsensusState {
     type Error = Ics02Error;
 
-    fn try_from(_value: Any) -> Result<Self, Self::Error> {
-        todo!()
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            CKB_CONSENSUS_STATE_TYPE_URL => serde_json::from_slice::<Self>(&raw.value)
+                .map_err(|e| Ics02Error::unknown_consensus_state_type(e.to_string())),
+            _ => Err(Ics02Error::unknown_consensus_state_type(raw.type_url)),
+        }
     }
 }
 
 impl From<CkbConsensusState> for Any {
-    fn from(_value: CkbConsensusState) -> Self {
-        todo!()
+    fn from(value: CkbConsensusState) -> Self {
+        Any {
+            type_url: CKB_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: serde_json::to_vec(&value).expect("encoding to `Any` from `CkbConsensusState`"),
+        }
     }
 }
@@ -1,2 +1,10 @@
+use crate::{timestamp::Timestamp, Height};
+
+/// The fields of a CKB block header which make up the consensus state at its height
 #[derive(Debug, Clone, Default)]
-pub struct CkbLightBlock {}
+pub struct CkbLightBlock {
+    pub height: Height,
+    pub transactions_root: Vec<u8>,
+    pub timestamp: Timestamp,
+    pub block_hash: Vec<u8>,
+}
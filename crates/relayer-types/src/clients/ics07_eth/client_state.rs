@@ -6,6 +6,7 @@ use crate::{
     prelude::*,
 };
 use core::convert::TryFrom;
+use core::time::Duration;
 use ibc_proto::google::protobuf::Any;
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +22,42 @@ pub const ETH_CLIENT_STATE_TYPE_URL: &str = "/eth.client.v1.state";
 pub struct EthClientState {
     pub chain_id: ChainId,
     pub lightclient_update: Update,
+    /// How long the latest consensus state is trusted, `None` if it never expires
+    #[serde(default)]
+    pub trusting_period: Option<Duration>,
+    /// Height of the misbehaviour which froze the client
+    #[serde(default)]
+    pub frozen_height: Option<crate::Height>,
+}
+
+impl EthClientState {
+    pub fn new(chain_id: ChainId, lightclient_update: Update) -> Self {
+        Self {
+            chain_id,
+            lightclient_update,
+            trusting_period: None,
+            frozen_height: None,
+        }
+    }
+
+    pub fn with_trusting_period(self, trusting_period: Option<Duration>) -> Self {
+        Self {
+            trusting_period,
+            ..self
+        }
+    }
+
+    pub fn with_frozen_height(self, h: crate::Height) -> Self {
+        Self {
+            frozen_height: Some(h),
+            ..self
+        }
+    }
+
+    /// Get the refresh time to ensure the state does not expire
+    pub fn refresh_time(&self) -> Option<Duration> {
+        self.trusting_period.map(|period| 2 * period / 3)
+    }
 }
 
 impl Ics02ClientState for EthClientState {
@@ -38,20 +75,24 @@ impl Ics02ClientState for EthClientState {
     }
 
     fn frozen_height(&self) -> Option<crate::Height> {
-        None
+        self.frozen_height
     }
 
-    fn expired(&self, _elapsed: core::time::Duration) -> bool {
-        false
+    fn expired(&self, elapsed: Duration) -> bool {
+        self.trusting_period
+            .map_or(false, |trusting_period| elapsed > trusting_period)
     }
 
+    /// The latest height is derived from the light client update, which only
+    /// changes with the next update relayed from the upgraded chain
     fn upgrade(
         &mut self,
         _upgrade_height: crate::Height,
         _upgrade_options: &dyn UpgradeOptions,
-        _chain_id: ChainId,
+        chain_id: ChainId,
     ) {
-        todo!()
+        self.frozen_height = None;
+        self.chain_id = chain_id;
     }
 }
 
@@ -86,10 +127,8 @@ mod tests {
 
     #[test]
     fn test_eth_client_state_serde() {
-        let client_state = EthClientState {
-            chain_id: ChainId::new("eth".to_owned(), 0),
-            lightclient_update: Default::default(),
-        };
+        let client_state =
+            EthClientState::new(ChainId::new("eth".to_owned(), 0), Default::default());
         let any: Any = client_state.into();
         let _: EthClientState = any.try_into().expect("serde error");
     }
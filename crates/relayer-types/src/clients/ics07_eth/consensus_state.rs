@@ -8,20 +8,26 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::ics02_client::error::Error as Ics02Error;
 
+/// Consensus state of Ethereum at a finalized slot
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ConsensusState {}
+pub struct ConsensusState {
+    /// `state_root` of the finalized beacon block header
+    pub root: CommitmentRoot,
+    /// Time of the finalized slot
+    pub timestamp: Timestamp,
+}
 
 impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
     fn client_type(&self) -> ClientType {
-        todo!()
+        ClientType::Eth
     }
 
     fn root(&self) -> &CommitmentRoot {
-        todo!()
+        &self.root
     }
 
     fn timestamp(&self) -> Timestamp {
-        todo!()
+        self.timestamp
     }
 }
 
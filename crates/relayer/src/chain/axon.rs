@@ -1,11 +1,15 @@
-use std::{str::FromStr, sync::Arc, thread, time::Duration};
+use std::{
+    str::FromStr,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
-use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
+use axon_tools::types::ValidatorExtend;
 use ckb_ics_axon::{
     axon_client::{commitment_slot, AxonCommitmentProof},
     commitment::{channel_path, connection_path},
 };
-use eth2_types::Hash256;
 use k256::ecdsa::SigningKey;
 use tracing::{debug, warn};
 
@@ -28,6 +32,7 @@ use crate::{
     misbehaviour::MisbehaviourEvidence,
 };
 use ethers::{
+    abi::Detokenize,
     prelude::*,
     providers::{Http, Middleware, Provider},
     signers::{Signer as _, Wallet},
@@ -37,7 +42,9 @@ use ibc_proto::{
     ibc::apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
 };
 use ibc_relayer_types::{
-    applications::ics31_icq::response::CrossChainQueryResponse,
+    applications::ics31_icq::{
+        error::Error as CrossChainQueryError, response::CrossChainQueryResponse,
+    },
     clients::ics07_axon::{
         client_state::AxonClientState, consensus_state::AxonConsensusState, header::AxonHeader,
         light_block::AxonLightBlock,
@@ -45,8 +52,9 @@ use ibc_relayer_types::{
     core::{
         ics02_client::{
             error::Error as ClientError,
-            events::UpdateClient,
-            msgs::{create_client, update_client},
+            events::{ClientMisbehaviour, UpdateClient},
+            header::downcast_header,
+            msgs::{create_client, misbehaviour, update_client},
         },
         ics03_connection::{
             connection::{self, ConnectionEnd, IdentifiedConnectionEnd},
@@ -57,6 +65,7 @@ use ibc_relayer_types::{
             msgs::{
                 acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack,
                 chan_open_confirm, chan_open_init, chan_open_try, recv_packet, timeout,
+                timeout_on_close,
             },
             packet::{PacketMsgType, Sequence},
         },
@@ -73,6 +82,7 @@ use ibc_relayer_types::{
     tx_msg::Msg,
     Height,
 };
+use tendermint::merkle::proof::{ProofOp, ProofOps};
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
 use self::{contract::OwnableIBCHandler, monitor::AxonEventMonitor};
@@ -81,6 +91,12 @@ type ContractProvider = SignerMiddleware<Provider<Http>, Wallet<SigningKey>>;
 type IBCContract = OwnableIBCHandler<ContractProvider>;
 type ERC20Contract = ERC20<ContractProvider>;
 type ICS20TransferERC20Contract = ICS20TransferERC20<ContractProvider>;
+type ICS29FeeContract = fee::ICS29Fee<ContractProvider>;
+
+const TX_RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Denomination of the native token balance, whose amount is in wei
+pub const NATIVE_DENOM: &str = "axon";
 
 use super::{
     client::ClientSettings,
@@ -105,12 +121,23 @@ use super::{
 use tokio::runtime::Runtime as TokioRuntime;
 
 pub mod contract;
+pub mod diagnostics;
 mod eth_err;
+mod fee;
+mod gas;
+#[cfg(test)]
+pub mod mock_node;
 mod monitor;
 mod msg;
+mod nonce;
 mod rpc;
 pub mod utils;
 
+#[cfg(test)]
+pub(crate) mod tests;
+
+use diagnostics::{ProofBundle, ProofIngredients};
+use nonce::NonceManager;
 pub use rpc::AxonRpc;
 use utils::*;
 
@@ -143,6 +170,7 @@ pub struct AxonChain {
     client: Provider<Http>,
     keybase: KeyRing<Secp256k1KeyPair>,
     chain_id: u64,
+    nonce_manager: NonceManager,
 }
 
 impl AxonChain {
@@ -171,6 +199,16 @@ impl AxonChain {
         ))
     }
 
+    fn fee_contract(&self) -> Result<Option<ICS29FeeContract>, Error> {
+        let Some(address) = self.config.fee_contract_address else {
+            return Ok(None);
+        };
+        Ok(Some(ICS29FeeContract::new(
+            address,
+            self.contract_provider()?,
+        )))
+    }
+
     fn erc20_contract(&self, address: H160) -> Result<ERC20Contract, Error> {
         Ok(ERC20::new(address, self.contract_provider()?))
     }
@@ -189,7 +227,7 @@ impl ChainEndpoint for AxonChain {
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
         let config: AxonChainConfig = config.try_into()?;
-        let keybase = KeyRing::new_secp256k1(Default::default(), "axon", &config.id)
+        let keybase = KeyRing::new_secp256k1(config.key_store_type, "axon", &config.id)
             .map_err(Error::key_base)?;
 
         let url = config.rpc_addr.clone();
@@ -219,6 +257,7 @@ impl ChainEndpoint for AxonChain {
             chain_id,
             rpc_client,
             client,
+            nonce_manager: NonceManager::default(),
         })
     }
 
@@ -308,16 +347,31 @@ impl ChainEndpoint for AxonChain {
         Ok(responses)
     }
 
-    // TODO the light client is unimplemented
+    // the block at the target height is checked against the signatures of the validators,
+    // its state root and time make up the consensus state of the counterparty client
     fn verify_header(
         &mut self,
-        trusted: Height,
+        _trusted: Height,
         target: Height,
-        client_state: &AnyClientState,
+        _client_state: &AnyClientState,
     ) -> Result<Self::LightBlock, Error> {
-        self.light_client
-            .verify(trusted, target, client_state)
-            .map(|v| v.target)
+        let block_number = target.revision_height();
+        let ingredients = self
+            .rt
+            .block_on(self.get_proofs_ingredients(block_number.into()))?;
+        if let Err(err) = ingredients.verify() {
+            let err_msg = format!("unverified axon block #{block_number}, err: {err}");
+            return Err(Error::rpc_response(err_msg));
+        }
+        self.light_client.observe(ingredients.signed_block());
+        let header = &ingredients.block.header;
+        let timestamp =
+            Timestamp::from_nanoseconds(header.timestamp * SEC_TO_NANO).map_err(Error::other)?;
+        Ok(AxonLightBlock {
+            height: target,
+            state_root: header.state_root.as_bytes().to_vec(),
+            timestamp,
+        })
     }
 
     // TODO the light client is unimplemented
@@ -326,36 +380,80 @@ impl ChainEndpoint for AxonChain {
         update: &UpdateClient,
         client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        // the block at the updated height may differ from the ones observed before
+        let block_number = update.consensus_height().revision_height();
+        let ingredients = self
+            .rt
+            .block_on(self.get_proofs_ingredients(block_number.into()))?;
+        match ingredients.verify() {
+            Ok(()) => self.light_client.observe(ingredients.signed_block()),
+            Err(err) => warn!("skip unverified axon block #{block_number}, err: {err}"),
+        }
+
+        // the block the client was updated to conflicts with the one of the relayer's
+        // node if both are signed by the validators
+        let updated_block = update
+            .header
+            .as_ref()
+            .and_then(|header| downcast_header::<AxonHeader>(header.as_ref()))
+            .and_then(|header| header.signed_block.clone());
+        if let Some(signed_block) = updated_block {
+            let updated = ProofIngredients {
+                block: signed_block.block,
+                previous_state_root: signed_block.previous_state_root,
+                block_proof: signed_block.proof,
+                validators: ingredients.validators,
+            };
+            match updated.verify() {
+                Ok(()) => self.light_client.observe(updated.signed_block()),
+                Err(err) => warn!(
+                    "skip unverified axon block #{} of the update of {}, err: {err}",
+                    updated.block_number(),
+                    update.client_id()
+                ),
+            }
+        }
         self.light_client.check_misbehaviour(update, client_state)
     }
 
     fn query_balance(&self, key_name: Option<&str>, denom: Option<&str>) -> Result<Balance, Error> {
         let key_name = key_name.unwrap_or(&self.config.key_name);
-        let denom: &str =
-            denom.ok_or_else(|| Error::other_error("do not support default denom".into()))?;
+        let wallet = self.get_wallet(key_name)?;
+        // the native token pays the fees when no ERC20 contract is given
+        let Some(denom) = denom else {
+            let amount = self
+                .rt
+                .block_on(self.client.get_balance(wallet.address(), None))
+                .map_err(|err| Error::query(format!("{err:?}")))?;
+            return Ok(Balance {
+                amount: amount.to_string(),
+                denom: NATIVE_DENOM.to_owned(),
+            });
+        };
         let erc20_address = {
             let denom = denom.trim_start_matches("0x");
             let bytes = hex::decode(denom).map_err(Error::other)?;
             H160::from_slice(&bytes)
         };
         let contract = self.erc20_contract(erc20_address)?;
-        let wallet = self.get_wallet(key_name)?;
         let amount = self
             .rt
             .block_on(contract.balance_of(wallet.address()).call())
             .map_err(|err| Error::query(format!("{err:?}")))?;
 
         Ok(Balance {
-            amount: format!("{amount:#x}"),
+            amount: amount.to_string(),
             denom: denom.to_string(),
         })
     }
 
-    // FIXME implement this after use a real ics token contract
-    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
-        // TODO: implement the real `query_all_balances` function later
-        warn!("axon query_all_balances() cannot implement");
-        Ok(vec![])
+    /// The native balance followed by the balances of the ERC20 tokens in `balance.tokens`
+    fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        let mut balances = vec![self.query_balance(key_name, None)?];
+        for token in &self.config.balance.tokens {
+            balances.push(self.query_balance(key_name, Some(token))?);
+        }
+        Ok(balances)
     }
 
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
@@ -1048,15 +1146,32 @@ impl ChainEndpoint for AxonChain {
         Ok(AxonConsensusState { root, timestamp })
     }
 
-    // TODO do we need to implement this?
     fn query_incentivized_packet(
         &self,
-        _request: QueryIncentivizedPacketRequest,
+        request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
-        // TODO
-        warn!("axon query_incentivized_packet() not support");
+        let Some(contract) = self.fee_contract()? else {
+            return Ok(QueryIncentivizedPacketResponse {
+                incentivized_packet: None,
+            });
+        };
+        let packet_id = request
+            .packet_id
+            .ok_or_else(|| Error::query("missing packet id".to_owned()))?;
+        let packet_fees = self
+            .rt
+            .block_on(
+                contract
+                    .get_incentivized_packet(
+                        packet_id.port_id.clone(),
+                        packet_id.channel_id.clone(),
+                        packet_id.sequence,
+                    )
+                    .call(),
+            )
+            .map_err(convert_err)?;
         Ok(QueryIncentivizedPacketResponse {
-            incentivized_packet: None,
+            incentivized_packet: fee::into_identified_packet_fees(packet_id, packet_fees),
         })
     }
 
@@ -1066,55 +1181,86 @@ impl ChainEndpoint for AxonChain {
         settings: ClientSettings,
     ) -> Result<Self::ClientState, Error> {
         match settings {
-            ClientSettings::AxonCkb | ClientSettings::Other => Ok(AxonClientState {
-                chain_id: self.id(),
-                latest_height: height,
-            }),
+            ClientSettings::AxonCkb | ClientSettings::Other => {
+                Ok(AxonClientState::new(self.id(), height)
+                    .with_trusting_period(self.config.trusting_period))
+            }
             _ => Err(Error::build_client_state_failure()),
         }
     }
 
-    // TODO do we need to implement this?
     fn build_consensus_state(
         &self,
-        _light_block: Self::LightBlock,
+        light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
-        Ok(AxonConsensusState {
-            root: CommitmentRoot::from_bytes(&[]),
-            timestamp: Timestamp::default(),
-        })
+        Ok(light_block.into())
     }
 
-    // TODO do we need to implement this?
+    // the signed block lets the relayers check the update against their own node
     fn build_header(
         &mut self,
         _trusted_height: Height,
-        _target_height: Height,
+        target_height: Height,
         _client_state: &AnyClientState,
     ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
-        Ok((AxonHeader {}, vec![]))
+        let block_number = target_height.revision_height();
+        let ingredients = self
+            .rt
+            .block_on(self.get_proofs_ingredients(block_number.into()))?;
+        let header = AxonHeader {
+            signed_block: Some(ingredients.signed_block()),
+        };
+        Ok((header, vec![]))
     }
 
-    // TODO do we need to implement this?
     fn maybe_register_counterparty_payee(
         &mut self,
-        _channel_id: &ChannelId,
-        _port_id: &PortId,
-        _counterparty_payee: &Signer,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_payee: &Signer,
     ) -> Result<(), Error> {
-        // TODO
-        warn!("axon maybe_register_counterparty_payee() not support");
+        let Some(contract) = self.fee_contract()? else {
+            warn!(
+                "no fee contract configured on {}, skip registering counterparty payee",
+                self.id()
+            );
+            return Ok(());
+        };
+        let relayer = self.get_wallet(&self.config.key_name)?.address();
+        let current_payee = self
+            .rt
+            .block_on(
+                contract
+                    .get_counterparty_payee(channel_id.to_string(), relayer)
+                    .call(),
+            )
+            .map_err(convert_err)?;
+        if current_payee == counterparty_payee.as_ref() {
+            return Ok(());
+        }
+        let call = contract.register_counterparty_payee(
+            port_id.to_string(),
+            channel_id.to_string(),
+            counterparty_payee.to_string(),
+        );
+        self.rt.block_on(self.send_contract_call(call))?;
         Ok(())
     }
 
-    // TODO do we need to implement this?
     fn cross_chain_query(
         &self,
-        _requests: Vec<CrossChainQueryRequest>,
+        requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
-        // TODO
-        warn!("axon cross_chain_query() not support");
-        Ok(vec![])
+        let responses = requests
+            .into_iter()
+            .filter_map(|request| {
+                let query_id = request.query_id.clone();
+                self.cross_chain_query_storage(request)
+                    .map_err(|err| warn!("skip cross chain query {query_id}: {err}"))
+                    .ok()
+            })
+            .collect();
+        Ok(responses)
     }
 
     fn build_connection_proofs_and_client_state(
@@ -1175,6 +1321,22 @@ impl ChainEndpoint for AxonChain {
                 ),
             )
         })?;
+
+        // timeouts on close additionally prove that the channel end on Axon is closed
+        if matches!(
+            packet_type,
+            PacketMsgType::TimeoutOnCloseUnordered | PacketMsgType::TimeoutOnCloseOrdered
+        ) {
+            let channel_proofs = self.build_channel_proofs(&port_id, &channel_id, height)?;
+            return Proofs::new(
+                proofs.object_proof().clone(),
+                proofs.client_proof().clone(),
+                proofs.consensus_proof(),
+                Some(channel_proofs.object_proof().clone()),
+                proofs.height(),
+            )
+            .map_err(Error::malformed_proof);
+        }
         Ok(proofs)
     }
 }
@@ -1231,7 +1393,11 @@ impl AxonChain {
         let (event_monitor, monitor_tx) = AxonEventMonitor::new(
             self.config.id.clone(),
             self.config.websocket_addr.clone(),
+            self.config.rpc_addr.clone(),
+            self.config.event_source,
+            self.config.poll_interval,
             self.config.contract_address,
+            self.config.fee_contract_address,
             // header_receiver,
             self.rt.clone(),
         )
@@ -1242,84 +1408,149 @@ impl AxonChain {
     }
 
     fn get_proofs(&self, height: Height, commitment_path: &str) -> Result<Proofs, Error> {
+        let commitment_slot = commitment_slot(commitment_path.as_bytes());
+        let (_, object_proof) =
+            self.get_storage_proof(height, self.config.contract_address, commitment_slot.into())?;
+        let object_proof = object_proof.try_into().unwrap();
+
+        let useless_client_proof = vec![0u8].try_into().unwrap();
+        let useless_consensus_proof =
+            ConsensusProof::new(vec![0u8].try_into().unwrap(), Height::default()).unwrap();
+        let proofs = Proofs::new(
+            object_proof,
+            Some(useless_client_proof),
+            Some(useless_consensus_proof),
+            None,
+            height,
+        )
+        .unwrap();
+
+        Ok(proofs)
+    }
+
+    /// Prove the value of storage `slot` of the contract at `address` after the verified
+    /// Axon block at `height`, returning the value and the RLP encoded proof
+    fn get_storage_proof(
+        &self,
+        height: Height,
+        address: H160,
+        slot: U256,
+    ) -> Result<(U256, Vec<u8>), Error> {
         let block_number = height.revision_height();
-        let (block, previous_state_root, block_proof, mut validators) = self
+        let started = Instant::now();
+        let ingredients = self
             .rt
             .block_on(self.get_proofs_ingredients(block_number.into()))?;
 
-        let debug_content =
-            generate_debug_content(&block, &previous_state_root, &block_proof, &validators);
-
         // check the validation of Axon block
-        axon_tools::verify_proof(
-            block.clone(),
+        if let Err(err) = ingredients.verify() {
+            self.save_proof_bundle(&ingredients, &err);
+            let err_msg = format!("unverified axon block #{block_number}, err: {err}");
+            return Err(Error::rpc_response(err_msg));
+        }
+        let latency = started.elapsed();
+        debug!("verified proofs of axon block #{block_number} in {latency:?}");
+        crate::telemetry!(axon_proof_latency, &self.id(), latency);
+        self.light_client.observe(ingredients.signed_block());
+        let ProofIngredients {
+            block,
             previous_state_root,
-            &mut validators,
-            block_proof.clone(),
-        )
-        .map_err(|err| {
-            std::fs::write(
-                format!("./debug/axon_block_{block_number}.log"),
-                debug_content,
-            )
-            .unwrap();
-            let err_msg = format!("unverified axon block #{block_number}, err: {:?}", err);
-            Error::rpc_response(err_msg)
-        })?;
-
-        let commitment_slot = commitment_slot(commitment_path.as_bytes());
-
-        let mut commitment_proof = self
-            .rt
-            .block_on(self.rpc_client.eth_get_proof(
-                self.config.contract_address,
-                vec![commitment_slot.into()],
-                Some(block_number.into()),
-            ))
-            .unwrap();
-        assert!(!commitment_proof.storage_proof.is_empty());
+            block_proof,
+            ..
+        } = ingredients;
+
+        let mut proof = self.rt.block_on(self.rpc_client.eth_get_proof(
+            address,
+            vec![slot],
+            Some(block_number.into()),
+        ))?;
+        if proof.storage_proof.is_empty() {
+            return Err(Error::rpc_response(format!(
+                "no proof of storage slot {slot} of {address:?} in axon block #{block_number}"
+            )));
+        }
+        let storage_proof = proof.storage_proof.remove(0);
         let commitment_proof = AxonCommitmentProof {
             block,
             block_proof,
             previous_state_root,
-            account_proof: commitment_proof
+            account_proof: proof
                 .account_proof
                 .into_iter()
                 .map(|p| p.0.into())
                 .collect(),
-            storage_proof: commitment_proof
-                .storage_proof
-                .remove(0)
+            storage_proof: storage_proof
                 .proof
                 .into_iter()
                 .map(|p| p.0.into())
                 .collect(),
         };
-        let object_proof = rlp::encode(&commitment_proof)
-            .freeze()
-            .to_vec()
-            .try_into()
-            .unwrap();
-
-        let useless_client_proof = vec![0u8].try_into().unwrap();
-        let useless_consensus_proof =
-            ConsensusProof::new(vec![0u8].try_into().unwrap(), Height::default()).unwrap();
-        let proofs = Proofs::new(
-            object_proof,
-            Some(useless_client_proof),
-            Some(useless_consensus_proof),
-            None,
-            height,
-        )
-        .unwrap();
+        let encoded_proof = rlp::encode(&commitment_proof).freeze().to_vec();
+        Ok((storage_proof.value, encoded_proof))
+    }
 
-        Ok(proofs)
+    fn save_proof_bundle(&self, ingredients: &ProofIngredients, error: &str) {
+        let diagnostics = &self.config.diagnostics;
+        let Some(dir) = diagnostics.dir(&self.config.id) else {
+            return;
+        };
+        let bundle = ProofBundle {
+            ingredients: ingredients.clone(),
+            error: error.to_owned(),
+        };
+        match bundle.save(&dir, diagnostics.max_bundles) {
+            Ok(path) => warn!(
+                "saved proof ingredients of axon block #{} to {}",
+                ingredients.block_number(),
+                path.display()
+            ),
+            Err(err) => warn!(
+                "failed to save proof ingredients into {}: {err}",
+                dir.display()
+            ),
+        }
     }
 
-    async fn get_proofs_ingredients(
+    /// Answer an ICS31 storage query with the storage value and its proof
+    fn cross_chain_query_storage(
         &self,
-        block_number: U64,
-    ) -> Result<(AxonBlock, Hash256, AxonProof, Vec<ValidatorExtend>), Error> {
+        request: CrossChainQueryRequest,
+    ) -> Result<CrossChainQueryResponse, Error> {
+        if request.query_type != STORAGE_QUERY_TYPE {
+            return Err(Error::ics31(CrossChainQueryError::query()));
+        }
+        let (address, slot) = parse_storage_query(&request.request)?;
+        let block_number = match request.height.value() {
+            0 => self
+                .rt
+                .block_on(self.client.get_block_number())
+                .map_err(|e| Error::rpc_response(e.to_string()))?
+                .as_u64(),
+            number => number,
+        };
+        let height = Height::from_noncosmos_height(block_number);
+        let (value, proof) = self.get_storage_proof(height, address, slot)?;
+
+        let mut result = [0u8; 32];
+        value.to_big_endian(&mut result);
+        let proof = ProofOps {
+            ops: vec![ProofOp {
+                field_type: STORAGE_QUERY_TYPE.to_owned(),
+                key: hex::decode(request.request.trim_start_matches("0x"))
+                    .map_err(|_| Error::ics31(CrossChainQueryError::parse()))?,
+                data: proof,
+            }],
+        };
+        Ok(CrossChainQueryResponse::new(
+            request.chain_id.to_string(),
+            request.query_id,
+            result.to_vec(),
+            block_number as i64,
+            proof,
+        ))
+    }
+
+    async fn get_proofs_ingredients(&self, block_number: U64) -> Result<ProofIngredients, Error> {
         let previous_number = block_number
             .checked_sub(1u64.into())
             .expect("bad block_number");
@@ -1362,34 +1593,137 @@ impl AxonChain {
             })
             .collect::<Vec<_>>();
 
-        Ok((block, state_root, proof, validators))
+        Ok(ProofIngredients {
+            block,
+            previous_state_root: state_root,
+            block_proof: proof,
+            validators,
+        })
     }
 }
 
 macro_rules! convert {
     ($self:ident, $msg:ident, $eventy:ty, $method:ident) => {{
         let msg: $eventy = $msg.try_into()?;
-        $self.rt.block_on(async {
-            Ok($self
-                .contract()?
-                .$method(msg.clone())
-                .send()
-                .await
-                .map_err(decode_revert_error)?
-                .await?)
-        })
+        let call = $self.contract()?.$method(msg);
+        $self.rt.block_on($self.send_contract_call(call))
     }};
 }
 
+impl AxonChain {
+    /// Send a contract call with the configured gas settings and wait for it to be committed,
+    /// replacing it with bumped fees whenever it stays pending for too long.
+    async fn send_contract_call<D: Detokenize>(
+        &self,
+        call: ContractCall<ContractProvider, D>,
+    ) -> Result<TransactionReceipt, Error> {
+        let gas_config = &self.config.gas;
+        let call = if gas_config.eip1559 {
+            call
+        } else {
+            call.legacy()
+        };
+        let estimated_gas = call
+            .estimate_gas()
+            .await
+            .map_err(|e| convert_err(decode_revert_error(e)))?;
+        let provider = self.contract_provider()?;
+        let mut tx = call.tx;
+        tx.set_gas(gas::gas_limit(gas_config, &self.config.id, estimated_gas)?);
+        gas::fill_fees(provider.as_ref(), gas_config, &mut tx).await?;
+
+        let nonce = self
+            .nonce_manager
+            .next(provider.as_ref(), provider.address())
+            .await?;
+        tx.set_nonce(nonce);
+
+        let mut sent_hashes = vec![];
+        let mut replacements = 0;
+        loop {
+            match provider.send_transaction(tx.clone(), None).await {
+                Ok(pending_tx) => sent_hashes.push(pending_tx.tx_hash()),
+                // the replaced transaction might have been committed in the meantime
+                Err(err) if !sent_hashes.is_empty() => {
+                    warn!("failed to replace transaction with nonce {nonce}: {err}");
+                }
+                Err(err) => {
+                    self.nonce_manager.reset().await;
+                    return Err(convert_err(err));
+                }
+            }
+            if let Some(receipt) = self
+                .wait_tx_receipt(&sent_hashes, gas_config.tx_replacement_timeout)
+                .await?
+            {
+                if receipt.status == Some(0.into()) {
+                    return Err(Error::send_tx(format!(
+                        "transaction {} reverted",
+                        hex::encode(receipt.transaction_hash)
+                    )));
+                }
+                return Ok(receipt);
+            }
+            if replacements >= gas_config.max_tx_replacements
+                || !gas::bump_fees(gas_config, &mut tx)
+            {
+                break;
+            }
+            replacements += 1;
+            warn!(
+                "transaction with nonce {nonce} is pending for more than {:?}, replace it with bumped fees ({replacements}/{})",
+                gas_config.tx_replacement_timeout, gas_config.max_tx_replacements
+            );
+        }
+        self.nonce_manager.reset().await;
+        Err(Error::send_tx(format!(
+            "transaction with nonce {nonce} is still pending after {replacements} replacements"
+        )))
+    }
+
+    /// Wait until any of the transactions is committed, returns `None` on timeout.
+    async fn wait_tx_receipt(
+        &self,
+        tx_hashes: &[TxHash],
+        timeout: Duration,
+    ) -> Result<Option<TransactionReceipt>, Error> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            for tx_hash in tx_hashes {
+                let receipt = self
+                    .client
+                    .get_transaction_receipt(*tx_hash)
+                    .await
+                    .map_err(|e| Error::rpc_response(e.to_string()))?;
+                if let Some(receipt) = receipt.filter(|r| r.block_number.is_some()) {
+                    return Ok(Some(receipt));
+                }
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(TX_RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+}
+
 impl AxonChain {
     fn send_message(&mut self, message: Any) -> Result<IbcEventWithHeight, Error> {
         use contract::*;
         let msg = message.clone();
-        let tx_receipt: eyre::Result<_> = match msg.type_url.as_str() {
+        let tx_receipt = match msg.type_url.as_str() {
             // client
             create_client::TYPE_URL => {
                 convert!(self, msg, MsgCreateClient, create_client)
             }
+            update_client::TYPE_URL => {
+                convert!(self, msg, MsgUpdateClient, update_client)
+            }
+            misbehaviour::TYPE_URL => {
+                let msg = misbehaviour::MsgSubmitMisbehaviour::from_any(msg)
+                    .map_err(|e| Error::protobuf_decode(misbehaviour::TYPE_URL.into(), e))?;
+                convert!(self, msg, MsgUpdateClient, update_client)
+            }
             // connection
             conn_open_init::TYPE_URL => {
                 convert!(self, msg, MsgConnectionOpenInit, connection_open_init)
@@ -1440,14 +1774,15 @@ impl AxonChain {
                         signer: msg.signer,
                     }
                 };
-                self.rt.block_on(async {
-                    Ok(self
-                        .contract()?
-                        .recv_packet(msg.into())
-                        .send()
-                        .await?
-                        .await?)
-                })
+                let call = self.contract()?.recv_packet(msg.into());
+                self.rt.block_on(self.send_contract_call(call))
+            }
+            // the IBC contract of Axon has no entrypoint to time out packets on closed channels
+            timeout_on_close::TYPE_URL => {
+                return Err(Error::other_error(format!(
+                    "unsupported message type url on Axon: {}",
+                    timeout_on_close::TYPE_URL
+                )))
             }
             url => {
                 return Err(Error::other_error(format!(
@@ -1455,9 +1790,15 @@ impl AxonChain {
                 )))
             }
         };
-        let tx_receipt = tx_receipt
-            .map_err(convert_err)?
-            .ok_or(Error::send_tx(String::from("fail to send tx")))?;
+        let tx_receipt = tx_receipt?;
+        if let Some(gas_used) = tx_receipt.gas_used {
+            crate::telemetry!(
+                tx_gas_used,
+                &self.id(),
+                message.type_url.as_str(),
+                gas_used.as_u64()
+            );
+        }
         let event: IbcEvent = {
             use contract::OwnableIBCHandlerEvents::*;
 
@@ -1475,14 +1816,8 @@ impl AxonChain {
                 create_client::TYPE_URL => {
                     events.find(|event| matches!(event, Ok(CreateClientFilter(_))))
                 }
-                update_client::TYPE_URL => {
-                    let msg = update_client::MsgUpdateClient::from_any(message).map_err(|e| {
-                        Error::send_tx(format!("fail to decode MsgUpdateClient {}", e))
-                    })?;
-                    Some(Ok(UpdateClientFilter(contract::UpdateClientFilter {
-                        client_id: msg.client_id.to_string(),
-                        client_message: "update client".parse().unwrap(), // FIXME
-                    })))
+                update_client::TYPE_URL | misbehaviour::TYPE_URL => {
+                    events.find(|event| matches!(event, Ok(UpdateClientFilter(_))))
                 }
                 conn_open_init::TYPE_URL => {
                     events.find(|event| matches!(event, Ok(OpenInitConnectionFilter(_))))
@@ -1533,6 +1868,13 @@ impl AxonChain {
         })?
         .unwrap()
         .into();
+        // the frozen client is reported the way Cosmos chains report it
+        let event = match event {
+            IbcEvent::UpdateClient(update) if message.type_url == misbehaviour::TYPE_URL => {
+                IbcEvent::ClientMisbehaviour(ClientMisbehaviour(update.common))
+            }
+            event => event,
+        };
         let tx_hash = tx_receipt.transaction_hash.0;
         let height = {
             let block_height = tx_receipt.block_number.ok_or_else(|| {
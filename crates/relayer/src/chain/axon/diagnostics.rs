@@ -0,0 +1,143 @@
+//! Bundles of the ingredients of Axon block proofs that failed to be verified.
+//!
+//! A bundle holds everything `axon_tools::verify_proof` needs, so that a verification
+//! failure can be reproduced offline with `forcerelay debug axon-proof`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
+use eth2_types::Hash256;
+use ibc_relayer_types::clients::ics07_axon::misbehaviour::AxonSignedBlock;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofIngredients {
+    pub block: AxonBlock,
+    pub previous_state_root: Hash256,
+    pub block_proof: AxonProof,
+    pub validators: Vec<ValidatorExtend>,
+}
+
+impl ProofIngredients {
+    pub fn block_number(&self) -> u64 {
+        self.block.header.number
+    }
+
+    pub fn signed_block(&self) -> AxonSignedBlock {
+        AxonSignedBlock {
+            block: self.block.clone(),
+            previous_state_root: self.previous_state_root,
+            proof: self.block_proof.clone(),
+        }
+    }
+
+    /// Check the validity of the Axon block against its proof and validators
+    pub fn verify(&self) -> Result<(), String> {
+        let mut validators = self.validators.clone();
+        axon_tools::verify_proof(
+            self.block.clone(),
+            self.previous_state_root,
+            &mut validators,
+            self.block_proof.clone(),
+        )
+        .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub ingredients: ProofIngredients,
+    /// The verification error reported when the bundle was saved
+    pub error: String,
+}
+
+impl ProofBundle {
+    pub fn path(dir: &Path, block_number: u64) -> PathBuf {
+        dir.join(format!("axon_block_{block_number}.json"))
+    }
+
+    /// Save the bundle into `dir`, then remove the oldest bundles so that at most
+    /// `max_bundles` are retained, all of them are if it's 0.
+    pub fn save(&self, dir: &Path, max_bundles: usize) -> Result<PathBuf, Error> {
+        fs::create_dir_all(dir).map_err(Error::io)?;
+        let path = Self::path(dir, self.ingredients.block_number());
+        let content = serde_json::to_string_pretty(self).map_err(Error::other)?;
+        fs::write(&path, content).map_err(Error::io)?;
+        prune_bundles(dir, max_bundles)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(Error::io)?;
+        serde_json::from_str(&content).map_err(Error::other)
+    }
+}
+
+fn prune_bundles(dir: &Path, max_bundles: usize) -> Result<(), Error> {
+    if max_bundles == 0 {
+        return Ok(());
+    }
+    let mut bundles = fs::read_dir(dir)
+        .map_err(Error::io)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            if !(name.starts_with("axon_block_") && name.ends_with(".json")) {
+                return None;
+            }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    if bundles.len() <= max_bundles {
+        return Ok(());
+    }
+    bundles.sort();
+    let outdated = bundles.len() - max_bundles;
+    for (_, path) in bundles.into_iter().take(outdated) {
+        fs::remove_file(path).map_err(Error::io)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        for number in 0..5 {
+            fs::write(ProofBundle::path(dir.path(), number), "{}").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        fs::write(dir.path().join("other.log"), "").unwrap();
+
+        prune_bundles(dir.path(), 2).unwrap();
+
+        let mut remains = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        remains.sort();
+        assert_eq!(
+            remains,
+            ["axon_block_3.json", "axon_block_4.json", "other.log"]
+        );
+    }
+
+    #[test]
+    fn test_keep_all_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        for number in 0..3 {
+            fs::write(ProofBundle::path(dir.path(), number), "{}").unwrap();
+        }
+
+        prune_bundles(dir.path(), 0).unwrap();
+
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+}
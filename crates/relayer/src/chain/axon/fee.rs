@@ -0,0 +1,100 @@
+//! ICS29 fee middleware of Axon, implemented by a contract beside the IBC handler.
+
+use std::str::FromStr;
+
+use ethers::contract::abigen;
+use ibc_proto::{
+    cosmos::base::v1beta1::Coin as ProtoCoin,
+    ibc::{
+        apps::fee::v1::{Fee as ProtoFee, IdentifiedPacketFees, PacketFee as ProtoPacketFee},
+        core::channel::v1::PacketId,
+    },
+};
+use ibc_relayer_types::{
+    applications::{
+        ics29_fee::events::IncentivizedPacket,
+        transfer::{amount::Amount, coin::RawCoin},
+    },
+    core::{
+        ics04_channel::packet::Sequence,
+        ics24_host::identifier::{ChannelId, PortId},
+    },
+};
+
+use super::utils::convert_err;
+use crate::error::Error;
+
+abigen!(
+    ICS29Fee,
+    r"[
+        struct FeeCoin { string denom; uint256 amount; }
+        struct PacketFee { FeeCoin[] recvFee; FeeCoin[] ackFee; FeeCoin[] timeoutFee; address refundAddress; }
+        function registerCounterpartyPayee(string portId, string channelId, string counterpartyPayee) external
+        function getCounterpartyPayee(string channelId, address relayer) external view returns (string)
+        function getIncentivizedPacket(string portId, string channelId, uint64 sequence) external view returns (PacketFee[])
+        event IncentivizedPacket(string portId, string channelId, uint64 sequence, FeeCoin[] totalRecvFee, FeeCoin[] totalAckFee, FeeCoin[] totalTimeoutFee)
+    ]"
+);
+
+fn into_raw_coins(coins: Vec<FeeCoin>) -> Result<Vec<RawCoin>, Error> {
+    coins
+        .into_iter()
+        .map(|coin| {
+            Ok(RawCoin {
+                denom: coin.denom,
+                amount: Amount::from_str(&coin.amount.to_string()).map_err(convert_err)?,
+            })
+        })
+        .collect()
+}
+
+fn into_proto_coins(coins: Vec<FeeCoin>) -> Vec<ProtoCoin> {
+    coins
+        .into_iter()
+        .map(|coin| ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        })
+        .collect()
+}
+
+impl TryFrom<IncentivizedPacketFilter> for IncentivizedPacket {
+    type Error = Error;
+
+    fn try_from(value: IncentivizedPacketFilter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            port_id: PortId::from_str(&value.port_id).map_err(convert_err)?,
+            channel_id: ChannelId::from_str(&value.channel_id).map_err(convert_err)?,
+            sequence: Sequence::from(value.sequence),
+            total_recv_fee: into_raw_coins(value.total_recv_fee)?,
+            total_ack_fee: into_raw_coins(value.total_ack_fee)?,
+            total_timeout_fee: into_raw_coins(value.total_timeout_fee)?,
+        })
+    }
+}
+
+/// The fees escrowed for `packet_id`, `None` if the packet isn't incentivized
+pub fn into_identified_packet_fees(
+    packet_id: PacketId,
+    packet_fees: Vec<PacketFee>,
+) -> Option<IdentifiedPacketFees> {
+    if packet_fees.is_empty() {
+        return None;
+    }
+    let packet_fees = packet_fees
+        .into_iter()
+        .map(|packet_fee| ProtoPacketFee {
+            fee: Some(ProtoFee {
+                recv_fee: into_proto_coins(packet_fee.recv_fee),
+                ack_fee: into_proto_coins(packet_fee.ack_fee),
+                timeout_fee: into_proto_coins(packet_fee.timeout_fee),
+            }),
+            refund_address: format!("{:?}", packet_fee.refund_address),
+            relayers: vec![],
+        })
+        .collect();
+    Some(IdentifiedPacketFees {
+        packet_id: Some(packet_id),
+        packet_fees,
+    })
+}
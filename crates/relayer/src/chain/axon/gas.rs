@@ -0,0 +1,176 @@
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::U256;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tracing::warn;
+
+use crate::config::axon::GasConfig;
+use crate::error::Error;
+
+/// Apply the configured multiplier to the estimated gas and check it against the configured limit.
+pub fn gas_limit(config: &GasConfig, chain_id: &ChainId, estimated: U256) -> Result<U256, Error> {
+    let max_gas = config.max_gas_limit.unwrap_or(u64::MAX);
+    let exceeded = |gas| Error::tx_simulate_gas_estimate_exceeded(chain_id.clone(), gas, max_gas);
+    // the gas beyond a u64 exceeds any block gas limit
+    let estimated = u64::try_from(estimated).map_err(|_| exceeded(u64::MAX))?;
+    let gas = (estimated as f64 * config.gas_multiplier).ceil();
+    if gas >= u64::MAX as f64 {
+        return Err(exceeded(u64::MAX));
+    }
+    let gas = (gas as u64).max(estimated);
+    if gas > max_gas {
+        return Err(exceeded(gas));
+    }
+    Ok(gas.into())
+}
+
+/// Fill the fee fields of `tx` with the current network prices, capped by the configuration.
+pub async fn fill_fees<M: Middleware>(
+    provider: &M,
+    config: &GasConfig,
+    tx: &mut TypedTransaction,
+) -> Result<(), Error> {
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            let (max_fee, priority_fee) = provider
+                .estimate_eip1559_fees(None)
+                .await
+                .map_err(|e| Error::rpc_response(e.to_string()))?;
+            let max_fee = cap(max_fee, config.max_gas_price);
+            let priority_fee = cap(priority_fee, config.max_priority_fee_per_gas).min(max_fee);
+            inner.max_fee_per_gas = Some(max_fee);
+            inner.max_priority_fee_per_gas = Some(priority_fee);
+        }
+        _ => {
+            let gas_price = provider
+                .get_gas_price()
+                .await
+                .map_err(|e| Error::rpc_response(e.to_string()))?;
+            tx.set_gas_price(cap(gas_price, config.max_gas_price));
+        }
+    }
+    Ok(())
+}
+
+/// Increase the fees of `tx` by `fee_bump_percent` so that it can replace a pending
+/// transaction with the same nonce.
+///
+/// Returns false if the fees cannot be increased any more because of the configured caps.
+pub fn bump_fees(config: &GasConfig, tx: &mut TypedTransaction) -> bool {
+    let percent = config.fee_bump_percent;
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            let (Some(max_fee), Some(priority_fee)) =
+                (inner.max_fee_per_gas, inner.max_priority_fee_per_gas)
+            else {
+                return false;
+            };
+            let new_max_fee = bump(max_fee, percent, config.max_gas_price);
+            let new_priority_fee =
+                bump(priority_fee, percent, config.max_priority_fee_per_gas).min(new_max_fee);
+            if new_max_fee <= max_fee || new_priority_fee <= priority_fee {
+                return false;
+            }
+            inner.max_fee_per_gas = Some(new_max_fee);
+            inner.max_priority_fee_per_gas = Some(new_priority_fee);
+            true
+        }
+        _ => {
+            let Some(gas_price) = tx.gas_price() else {
+                return false;
+            };
+            let new_gas_price = bump(gas_price, percent, config.max_gas_price);
+            if new_gas_price <= gas_price {
+                return false;
+            }
+            tx.set_gas_price(new_gas_price);
+            true
+        }
+    }
+}
+
+fn cap(value: U256, max: Option<u64>) -> U256 {
+    match max {
+        Some(max) if value > U256::from(max) => {
+            warn!("fee {value} exceeds the configured cap, use {max} instead");
+            U256::from(max)
+        }
+        _ => value,
+    }
+}
+
+fn bump(value: U256, percent: u64, max: Option<u64>) -> U256 {
+    let bumped = value * (100 + percent) / 100;
+    // make sure tiny values still increase
+    let bumped = bumped.max(value + 1);
+    match max {
+        Some(max) => bumped.min(U256::from(max)),
+        None => bumped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Eip1559TransactionRequest, TransactionRequest};
+
+    #[test]
+    fn test_gas_limit() {
+        let chain_id = ChainId::from_string("axon-0");
+        let config = GasConfig {
+            gas_multiplier: 1.5,
+            max_gas_limit: Some(200_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            gas_limit(&config, &chain_id, 100_000.into()).unwrap(),
+            150_000.into()
+        );
+        assert!(gas_limit(&config, &chain_id, 150_000.into()).is_err());
+
+        // the overflowing estimations are rejected without a configured limit
+        let config = GasConfig {
+            gas_multiplier: 1.5,
+            max_gas_limit: None,
+            ..Default::default()
+        };
+        assert!(gas_limit(&config, &chain_id, U256::MAX).is_err());
+        assert!(gas_limit(&config, &chain_id, u64::MAX.into()).is_err());
+    }
+
+    #[test]
+    fn test_bump_legacy_fees() {
+        let config = GasConfig {
+            fee_bump_percent: 20,
+            max_gas_price: Some(130),
+            ..Default::default()
+        };
+        let mut tx: TypedTransaction = TransactionRequest::new().gas_price(100).into();
+        assert!(bump_fees(&config, &mut tx));
+        assert_eq!(tx.gas_price(), Some(120.into()));
+        assert!(bump_fees(&config, &mut tx));
+        assert_eq!(tx.gas_price(), Some(130.into()));
+        assert!(!bump_fees(&config, &mut tx));
+    }
+
+    #[test]
+    fn test_bump_eip1559_fees() {
+        let config = GasConfig {
+            fee_bump_percent: 10,
+            max_priority_fee_per_gas: Some(2),
+            ..Default::default()
+        };
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(100)
+            .max_priority_fee_per_gas(1)
+            .into();
+        assert!(bump_fees(&config, &mut tx));
+        let TypedTransaction::Eip1559(inner) = &tx else {
+            unreachable!()
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(110.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(2.into()));
+        // the priority fee reaches its cap, so the replacement would be rejected
+        assert!(!bump_fees(&config, &mut tx));
+    }
+}
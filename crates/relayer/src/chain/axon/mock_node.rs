@@ -0,0 +1,723 @@
+//! A stand-in for an Axon node in tests.
+//!
+//! The node serves the Ethereum and Axon JSON-RPC methods used by [`AxonChain`](super::AxonChain)
+//! over HTTP, and the `eth_subscribe` notifications used by the event monitor over WebSocket.
+//! Every raw transaction is committed right away in a block of its own, the logs of its
+//! receipt are the ones registered with [`MockAxonNode::on_transaction`] for its method.
+//!
+//! Axon blocks and proofs are canned: unless set explicitly they are default values
+//! carrying the number of the block, so they don't pass any signature verification.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::net::TcpListener as StdTcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use async_trait::async_trait;
+use axon_tools::types::{Block as AxonBlock, CkbRelatedInfo, Metadata, Proof};
+use ethers::abi::{self, RawLog, Token, Tokenizable, Tokenize};
+use ethers::contract::{EthCall, EthEvent};
+use ethers::types::{
+    Address, Block, BlockId, BlockNumber, Bytes, FeeHistory, Log, Transaction, TransactionReceipt,
+    H160, H256, U256, U64,
+};
+use ethers::utils::{keccak256, rlp};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tendermint_rpc::{Url, WebSocketClientUrl};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::rpc::{AxonRpc, EIP1186ProofResponse, Response, StorageProof};
+use crate::error::Error;
+
+pub const MOCK_CHAIN_ID: u64 = 0x41786f6e;
+
+const GENESIS_TIMESTAMP: u64 = 1_600_000_000;
+const BLOCK_INTERVAL: u64 = 3;
+const GAS_LIMIT: u64 = 30_000_000;
+const GAS_ESTIMATE: u64 = 100_000;
+const BASE_FEE: u64 = 1_000_000_000;
+const PRIORITY_FEE: u64 = 1_000_000_000;
+
+#[derive(Clone)]
+enum SubscriptionKind {
+    NewHeads,
+    Logs(Vec<Address>),
+}
+
+struct Subscriber {
+    id: U256,
+    kind: SubscriptionKind,
+    sender: mpsc::UnboundedSender<String>,
+}
+
+struct NodeState {
+    contract_address: Address,
+    blocks: Vec<Block<H256>>,
+    axon_blocks: HashMap<u64, AxonBlock>,
+    proofs: HashMap<u64, Proof>,
+    metadata: Option<Metadata>,
+    ckb_related_info: Option<CkbRelatedInfo>,
+    transactions: HashMap<H256, Transaction>,
+    receipts: HashMap<H256, TransactionReceipt>,
+    logs: Vec<Log>,
+    nonces: HashMap<Address, U256>,
+    // canned logs of the transactions and results of the calls, by method selector
+    transaction_logs: HashMap<[u8; 4], Vec<RawLog>>,
+    call_results: HashMap<[u8; 4], Bytes>,
+    next_subscription_id: u64,
+    subscribers: Vec<Subscriber>,
+}
+
+fn block_hash(number: u64) -> H256 {
+    keccak256(number.to_be_bytes()).into()
+}
+
+fn rpc_error(message: impl ToString) -> Value {
+    json!({ "code": -32000, "message": message.to_string() })
+}
+
+fn param<T: serde::de::DeserializeOwned>(params: &[Value], index: usize) -> Result<T, Value> {
+    let value = params.get(index).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value).map_err(|e| rpc_error(format!("invalid param #{index}: {e}")))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, Value> {
+    serde_json::to_value(value).map_err(rpc_error)
+}
+
+fn selector(input: &[u8]) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    if input.len() >= 4 {
+        selector.copy_from_slice(&input[..4]);
+    }
+    selector
+}
+
+impl NodeState {
+    fn new(contract_address: Address) -> Self {
+        let mut state = Self {
+            contract_address,
+            blocks: vec![],
+            axon_blocks: HashMap::new(),
+            proofs: HashMap::new(),
+            metadata: None,
+            ckb_related_info: None,
+            transactions: HashMap::new(),
+            receipts: HashMap::new(),
+            logs: vec![],
+            nonces: HashMap::new(),
+            transaction_logs: HashMap::new(),
+            call_results: HashMap::new(),
+            next_subscription_id: 1,
+            subscribers: vec![],
+        };
+        state.produce_block(vec![], vec![]);
+        state
+    }
+
+    fn tip(&self) -> u64 {
+        self.blocks.len() as u64 - 1
+    }
+
+    fn produce_block(&mut self, transactions: Vec<H256>, logs: Vec<Log>) -> u64 {
+        let number = self.blocks.len() as u64;
+        let block = Block {
+            hash: Some(block_hash(number)),
+            parent_hash: number.checked_sub(1).map(block_hash).unwrap_or_default(),
+            number: Some(number.into()),
+            timestamp: (GENESIS_TIMESTAMP + number * BLOCK_INTERVAL).into(),
+            gas_limit: GAS_LIMIT.into(),
+            base_fee_per_gas: Some(BASE_FEE.into()),
+            transactions,
+            ..Default::default()
+        };
+        self.blocks.push(block.clone());
+        self.notify(SubscriptionKind::NewHeads, &block);
+        for log in logs {
+            let address = log.address;
+            self.notify(SubscriptionKind::Logs(vec![address]), &log);
+            self.logs.push(log);
+        }
+        number
+    }
+
+    /// Push a notification to the subscribers interested in it, closed subscriptions are dropped
+    fn notify(&mut self, kind: SubscriptionKind, result: &impl serde::Serialize) {
+        self.subscribers.retain(|subscriber| {
+            let interested = match (&subscriber.kind, &kind) {
+                (SubscriptionKind::NewHeads, SubscriptionKind::NewHeads) => true,
+                (SubscriptionKind::Logs(filter), SubscriptionKind::Logs(address)) => {
+                    filter.is_empty() || filter.contains(&address[0])
+                }
+                _ => false,
+            };
+            if !interested {
+                return true;
+            }
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": { "subscription": subscriber.id, "result": result },
+            });
+            subscriber.sender.send(notification.to_string()).is_ok()
+        });
+    }
+
+    fn make_log(&self, raw: RawLog, number: u64, tx_hash: H256, log_index: usize) -> Log {
+        Log {
+            address: self.contract_address,
+            topics: raw.topics,
+            data: raw.data.into(),
+            block_hash: Some(block_hash(number)),
+            block_number: Some(number.into()),
+            transaction_hash: Some(tx_hash),
+            transaction_index: Some(U64::zero()),
+            log_index: Some(log_index.into()),
+            removed: Some(false),
+            ..Default::default()
+        }
+    }
+
+    fn send_raw_transaction(&mut self, raw: Bytes) -> Result<H256, String> {
+        let mut tx: Transaction = rlp::decode(&raw).map_err(|e| e.to_string())?;
+        let from = tx.recover_from_mut().map_err(|e| e.to_string())?;
+        let nonce = self.nonces.entry(from).or_default();
+        if tx.nonce != *nonce {
+            return Err(format!("invalid nonce {}, expected {}", tx.nonce, nonce));
+        }
+        *nonce += U256::one();
+
+        let number = self.blocks.len() as u64;
+        let logs = self
+            .transaction_logs
+            .get(&selector(&tx.input))
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(index, raw)| self.make_log(raw, number, tx.hash, index))
+            .collect::<Vec<_>>();
+        tx.block_hash = Some(block_hash(number));
+        tx.block_number = Some(number.into());
+        tx.transaction_index = Some(U64::zero());
+        let receipt = TransactionReceipt {
+            transaction_hash: tx.hash,
+            transaction_index: U64::zero(),
+            block_hash: tx.block_hash,
+            block_number: tx.block_number,
+            from,
+            to: tx.to,
+            cumulative_gas_used: GAS_ESTIMATE.into(),
+            gas_used: Some(GAS_ESTIMATE.into()),
+            effective_gas_price: Some((BASE_FEE + PRIORITY_FEE).into()),
+            logs: logs.clone(),
+            status: Some(U64::one()),
+            transaction_type: tx.transaction_type,
+            ..Default::default()
+        };
+        let hash = tx.hash;
+        self.transactions.insert(hash, tx);
+        self.receipts.insert(hash, receipt);
+        self.produce_block(vec![hash], logs);
+        Ok(hash)
+    }
+
+    /// Resolve a block tag, number or hash into a block number
+    fn block_number(&self, value: Option<&Value>) -> Result<Option<u64>, Value> {
+        let tag = match value {
+            None | Some(Value::Null) => return Ok(Some(self.tip())),
+            Some(Value::String(tag)) => tag.as_str(),
+            Some(Value::Object(object)) => {
+                let inner = object
+                    .get("blockHash")
+                    .or_else(|| object.get("blockNumber"));
+                return self.block_number(inner);
+            }
+            Some(value) => return Err(rpc_error(format!("invalid block id {value}"))),
+        };
+        match tag {
+            "latest" | "pending" | "safe" | "finalized" => Ok(Some(self.tip())),
+            "earliest" => Ok(Some(0)),
+            hash if hash.len() == 66 => {
+                let hash: H256 = hash.parse().map_err(rpc_error)?;
+                Ok(self
+                    .blocks
+                    .iter()
+                    .position(|block| block.hash == Some(hash))
+                    .map(|number| number as u64))
+            }
+            number => {
+                let number =
+                    u64::from_str_radix(number.trim_start_matches("0x"), 16).map_err(rpc_error)?;
+                Ok((number <= self.tip()).then_some(number))
+            }
+        }
+    }
+
+    fn block_id_number(&self, block_id: BlockId) -> Option<u64> {
+        let value = match block_id {
+            BlockId::Hash(hash) => json!(hash),
+            BlockId::Number(number) => json!(number),
+        };
+        self.block_number(Some(&value)).ok().flatten()
+    }
+
+    fn axon_block(&self, number: Option<u64>) -> Option<AxonBlock> {
+        let number = number?;
+        let block = self.axon_blocks.get(&number).cloned().unwrap_or_else(|| {
+            let mut block = AxonBlock::default();
+            block.header.number = number;
+            block
+        });
+        Some(block)
+    }
+
+    fn proof(&self, number: Option<u64>) -> Option<Proof> {
+        let number = number?;
+        let proof = self.proofs.get(&number).cloned().unwrap_or_else(|| {
+            let mut proof = Proof::default();
+            proof.number = number;
+            proof.block_hash = block_hash(number);
+            proof
+        });
+        Some(proof)
+    }
+
+    fn metadata(&self) -> Result<Metadata, String> {
+        self.metadata
+            .clone()
+            .ok_or_else(|| "metadata is not set".to_owned())
+    }
+
+    fn ckb_related_info(&self) -> Result<CkbRelatedInfo, String> {
+        self.ckb_related_info
+            .clone()
+            .ok_or_else(|| "CKB related info is not set".to_owned())
+    }
+
+    /// Empty proofs of the storage slots, which are all zero
+    fn storage_proofs(&self, positions: &[U256]) -> Vec<Value> {
+        positions
+            .iter()
+            .map(|key| json!({ "key": key, "value": U256::zero(), "proof": [] }))
+            .collect()
+    }
+
+    fn logs(&self, filter: &Value) -> Result<Vec<Log>, Value> {
+        let from_block = self.block_number(filter.get("fromBlock"))?.unwrap_or(0);
+        let to_block = self
+            .block_number(filter.get("toBlock"))?
+            .unwrap_or_else(|| self.tip());
+        let addresses: Vec<Address> = match filter.get("address") {
+            None | Some(Value::Null) => vec![],
+            Some(Value::Array(_)) => param(&[filter["address"].clone()], 0)?,
+            Some(address) => vec![param(&[address.clone()], 0)?],
+        };
+        let logs = self
+            .logs
+            .iter()
+            .filter(|log| {
+                let number = log.block_number.unwrap_or_default().as_u64();
+                from_block <= number
+                    && number <= to_block
+                    && (addresses.is_empty() || addresses.contains(&log.address))
+            })
+            .cloned()
+            .collect();
+        Ok(logs)
+    }
+
+    fn handle(&mut self, method: &str, params: &[Value]) -> Result<Value, Value> {
+        match method {
+            "eth_chainId" => to_value(U64::from(MOCK_CHAIN_ID)),
+            "net_version" => to_value(MOCK_CHAIN_ID.to_string()),
+            "eth_blockNumber" => to_value(U64::from(self.tip())),
+            "eth_getBlockByNumber" | "eth_getBlockByHash" => {
+                let full: bool = param(params, 1).unwrap_or_default();
+                let Some(number) = self.block_number(params.first())? else {
+                    return Ok(Value::Null);
+                };
+                let block = &self.blocks[number as usize];
+                let mut value = to_value(block)?;
+                if full {
+                    let transactions = block
+                        .transactions
+                        .iter()
+                        .flat_map(|hash| self.transactions.get(hash))
+                        .collect::<Vec<_>>();
+                    value["transactions"] = to_value(transactions)?;
+                }
+                Ok(value)
+            }
+            "eth_getTransactionCount" => {
+                let address: Address = param(params, 0)?;
+                to_value(self.nonces.get(&address).copied().unwrap_or_default())
+            }
+            "eth_gasPrice" => to_value(U256::from(BASE_FEE + PRIORITY_FEE)),
+            "eth_maxPriorityFeePerGas" => to_value(U256::from(PRIORITY_FEE)),
+            "eth_feeHistory" => {
+                let count: U256 = param(params, 0)?;
+                let count = count.as_u64().clamp(1, self.tip() + 1);
+                to_value(FeeHistory {
+                    base_fee_per_gas: vec![BASE_FEE.into(); count as usize + 1],
+                    gas_used_ratio: vec![0.5; count as usize],
+                    oldest_block: (self.tip() + 1 - count).into(),
+                    reward: vec![vec![PRIORITY_FEE.into()]; count as usize],
+                })
+            }
+            "eth_estimateGas" => to_value(U256::from(GAS_ESTIMATE)),
+            "eth_call" => {
+                let call = params.first().cloned().unwrap_or(Value::Null);
+                let input = call.get("input").or_else(|| call.get("data")).cloned();
+                let input: Bytes = param(&[input.unwrap_or(Value::Null)], 0)?;
+                match self.call_results.get(&selector(&input)) {
+                    Some(output) => to_value(output),
+                    None => Err(rpc_error("execution reverted")),
+                }
+            }
+            "eth_sendRawTransaction" => {
+                let raw: Bytes = param(params, 0)?;
+                self.send_raw_transaction(raw)
+                    .map_err(rpc_error)
+                    .and_then(to_value)
+            }
+            "eth_getTransactionByHash" => {
+                let hash: H256 = param(params, 0)?;
+                to_value(self.transactions.get(&hash))
+            }
+            "eth_getTransactionReceipt" => {
+                let hash: H256 = param(params, 0)?;
+                to_value(self.receipts.get(&hash))
+            }
+            "eth_getLogs" => to_value(self.logs(params.first().unwrap_or(&Value::Null))?),
+            "eth_getProof" => {
+                let address: H160 = param(params, 0)?;
+                let positions: Vec<U256> = param(params, 1)?;
+                Ok(json!({
+                    "address": address,
+                    "accountProof": [],
+                    "balance": U256::zero(),
+                    "codeHash": H256::zero(),
+                    "nonce": U64::zero(),
+                    "storageHash": H256::zero(),
+                    "storageProof": self.storage_proofs(&positions),
+                }))
+            }
+            "axon_getBlockById" => {
+                let number = self.block_number(params.first())?;
+                to_value(self.axon_block(number))
+            }
+            "axon_getProofById" => {
+                let number = self.block_number(params.first())?;
+                to_value(self.proof(number))
+            }
+            "axon_getMetadataByNumber" | "axon_getCurrentMetadata" => {
+                self.metadata().map_err(rpc_error).and_then(to_value)
+            }
+            "axon_getCkbRelatedInfo" => self
+                .ckb_related_info()
+                .map_err(rpc_error)
+                .and_then(to_value),
+            method => Err(json!({
+                "code": -32601,
+                "message": format!("method {method} not found"),
+            })),
+        }
+    }
+}
+
+/// Handle a JSON-RPC request, `subscriber` is the channel of the WebSocket connection
+/// which is required by `eth_subscribe`
+fn handle_request(
+    state: &Mutex<NodeState>,
+    request: &str,
+    subscriber: Option<&mpsc::UnboundedSender<String>>,
+) -> String {
+    let request: Value = match serde_json::from_str(request) {
+        Ok(request) => request,
+        Err(e) => {
+            return json!({ "jsonrpc": "2.0", "id": null, "error": rpc_error(e) }).to_string()
+        }
+    };
+    let id = request["id"].clone();
+    let method = request["method"].as_str().unwrap_or_default();
+    let params = request["params"].as_array().cloned().unwrap_or_default();
+
+    let mut state = state.lock().unwrap();
+    let result = match (method, subscriber) {
+        ("eth_subscribe", Some(sender)) => {
+            let kind = match params.first().and_then(Value::as_str) {
+                Some("newHeads") => Ok(SubscriptionKind::NewHeads),
+                Some("logs") => {
+                    let filter = params.get(1).cloned().unwrap_or(Value::Null);
+                    let addresses = match filter.get("address") {
+                        None | Some(Value::Null) => Ok(vec![]),
+                        Some(Value::Array(_)) => param(&[filter["address"].clone()], 0),
+                        Some(address) => param(&[address.clone()], 0).map(|a| vec![a]),
+                    };
+                    addresses.map(SubscriptionKind::Logs)
+                }
+                kind => Err(rpc_error(format!("unsupported subscription {kind:?}"))),
+            };
+            kind.map(|kind| {
+                let id = U256::from(state.next_subscription_id);
+                state.next_subscription_id += 1;
+                state.subscribers.push(Subscriber {
+                    id,
+                    kind,
+                    sender: sender.clone(),
+                });
+                json!(id)
+            })
+        }
+        ("eth_unsubscribe", Some(_)) => {
+            let id: U256 = param(&params, 0).unwrap_or_default();
+            let count = state.subscribers.len();
+            state.subscribers.retain(|subscriber| subscriber.id != id);
+            Ok(json!(count != state.subscribers.len()))
+        }
+        (method, _) => state.handle(method, &params),
+    };
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+    .to_string()
+}
+
+async fn serve_http_connection(state: Arc<Mutex<NodeState>>, stream: TcpStream) {
+    let mut reader = BufReader::new(stream);
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or_default();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).await.is_err() {
+        return;
+    }
+    let response = handle_request(&state, &String::from_utf8_lossy(&body), None);
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.len(),
+        response
+    );
+    let _ = reader.into_inner().write_all(response.as_bytes()).await;
+}
+
+async fn serve_ws_connection(state: Arc<Mutex<NodeState>>, stream: TcpStream) {
+    let Ok(websocket) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (mut sink, mut stream) = websocket.split();
+    let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if sink.send(Message::Text(message)).await.is_err() {
+                break;
+            }
+        }
+    });
+    while let Some(Ok(message)) = stream.next().await {
+        if let Message::Text(request) = message {
+            let response = handle_request(&state, &request, Some(&sender));
+            if sender.send(response).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+async fn serve(state: Arc<Mutex<NodeState>>, http: StdTcpListener, ws: StdTcpListener) {
+    let http = TcpListener::from_std(http).expect("HTTP listener");
+    let ws = TcpListener::from_std(ws).expect("WebSocket listener");
+    loop {
+        tokio::select! {
+            Ok((stream, _)) = http.accept() => {
+                tokio::spawn(serve_http_connection(state.clone(), stream));
+            }
+            Ok((stream, _)) = ws.accept() => {
+                tokio::spawn(serve_ws_connection(state.clone(), stream));
+            }
+            else => continue,
+        }
+    }
+}
+
+/// The log emitted by the IBC contract for `event`, none of whose fields is indexed
+pub fn encode_event<E: EthEvent + Tokenizable>(event: E) -> RawLog {
+    let data = match event.into_token() {
+        Token::Tuple(tokens) => abi::encode(&tokens),
+        token => abi::encode(&[token]),
+    };
+    RawLog {
+        topics: vec![E::signature()],
+        data,
+    }
+}
+
+#[derive(Clone)]
+pub struct MockAxonNode {
+    state: Arc<Mutex<NodeState>>,
+    pub rpc_addr: Url,
+    pub websocket_addr: WebSocketClientUrl,
+}
+
+impl MockAxonNode {
+    /// Start a node on local ports, the IBC contract is deployed at `contract_address`
+    pub fn start(contract_address: Address) -> Self {
+        let http = StdTcpListener::bind("127.0.0.1:0").expect("bind HTTP port");
+        let ws = StdTcpListener::bind("127.0.0.1:0").expect("bind WebSocket port");
+        http.set_nonblocking(true).unwrap();
+        ws.set_nonblocking(true).unwrap();
+        let rpc_addr = format!("http://{}", http.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let websocket_addr = format!("ws://{}", ws.local_addr().unwrap())
+            .parse()
+            .unwrap();
+
+        let state = Arc::new(Mutex::new(NodeState::new(contract_address)));
+        let server_state = state.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("mock node runtime");
+            rt.block_on(serve(server_state, http, ws));
+        });
+
+        Self {
+            state,
+            rpc_addr,
+            websocket_addr,
+        }
+    }
+
+    pub fn tip_block_number(&self) -> u64 {
+        self.state.lock().unwrap().tip()
+    }
+
+    pub fn produce_empty_blocks(&self, count: u64) {
+        let mut state = self.state.lock().unwrap();
+        for _ in 0..count {
+            state.produce_block(vec![], vec![]);
+        }
+    }
+
+    /// Emit the logs of the IBC contract in a new block, as if a transaction of
+    /// another relayer were committed
+    pub fn emit(&self, logs: Vec<RawLog>) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let number = state.blocks.len() as u64;
+        let tx_hash = H256::from(keccak256(format!("emitted in #{number}")));
+        let logs = logs
+            .into_iter()
+            .enumerate()
+            .map(|(index, raw)| state.make_log(raw, number, tx_hash, index))
+            .collect();
+        state.produce_block(vec![], logs)
+    }
+
+    /// Logs of the receipts of the transactions calling the method `C`
+    pub fn on_transaction<C: EthCall>(&self, logs: Vec<RawLog>) {
+        self.state
+            .lock()
+            .unwrap()
+            .transaction_logs
+            .insert(C::selector(), logs);
+    }
+
+    /// Output of the `eth_call`s of the view method `C`
+    pub fn on_call<C: EthCall, R: Tokenize>(&self, output: R) {
+        let output = abi::encode(&output.into_tokens());
+        self.state
+            .lock()
+            .unwrap()
+            .call_results
+            .insert(C::selector(), output.into());
+    }
+
+    pub fn set_axon_block(&self, block: AxonBlock, proof: Proof) {
+        let mut state = self.state.lock().unwrap();
+        let number = block.header.number;
+        state.axon_blocks.insert(number, block);
+        state.proofs.insert(number, proof);
+    }
+
+    pub fn set_metadata(&self, metadata: Metadata) {
+        self.state.lock().unwrap().metadata = Some(metadata);
+    }
+
+    pub fn set_ckb_related_info(&self, info: CkbRelatedInfo) {
+        self.state.lock().unwrap().ckb_related_info = Some(info);
+    }
+
+    pub fn get_transaction(&self, hash: H256) -> Option<Transaction> {
+        self.state.lock().unwrap().transactions.get(&hash).cloned()
+    }
+}
+
+#[async_trait]
+impl AxonRpc for MockAxonNode {
+    async fn get_block_by_id(&self, block_id: BlockId) -> Response<Option<AxonBlock>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.axon_block(state.block_id_number(block_id)))
+    }
+
+    async fn get_proof_by_id(&self, block_id: BlockId) -> Response<Option<Proof>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.proof(state.block_id_number(block_id)))
+    }
+
+    async fn get_metadata_by_number(&self, _block_number: BlockNumber) -> Response<Metadata> {
+        self.get_current_metadata().await
+    }
+
+    async fn get_current_metadata(&self) -> Response<Metadata> {
+        let state = self.state.lock().unwrap();
+        state.metadata().map_err(Error::rpc_response)
+    }
+
+    async fn get_ckb_related_info(&self) -> Response<CkbRelatedInfo> {
+        let state = self.state.lock().unwrap();
+        state.ckb_related_info().map_err(Error::rpc_response)
+    }
+
+    async fn eth_get_proof(
+        &self,
+        _address: H160,
+        positions: Vec<U256>,
+        _block_id: Option<BlockId>,
+    ) -> Response<EIP1186ProofResponse> {
+        let storage_proof = positions
+            .into_iter()
+            .map(|key| StorageProof {
+                key,
+                value: U256::zero(),
+                proof: vec![],
+            })
+            .collect();
+        Ok(EIP1186ProofResponse {
+            account_proof: vec![],
+            storage_proof,
+        })
+    }
+}
@@ -2,40 +2,63 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use super::contract::*;
+use super::fee::IncentivizedPacketFilter;
+use crate::config::axon::EventSource;
 use crate::event::bus::EventBus;
 use crate::event::IbcEventWithHeight;
+use crate::telemetry;
 use crossbeam_channel as channel;
 use ethers::contract::LogMeta;
 use ethers::prelude::*;
 use ethers::providers::Middleware;
 use ethers::types::Address;
+use futures::StreamExt;
+use ibc_relayer_types::applications::ics29_fee::events::IncentivizedPacket;
+use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height;
-use OwnableIBCHandler as Contract;
 use OwnableIBCHandlerEvents as ContractEvents;
 
 use crate::chain::tracking::TrackingId;
 use crate::event::monitor::{Error, EventBatch, MonitorCmd, Next, Result, TxMonitorCmd};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use tendermint_rpc::WebSocketClientUrl;
+use tendermint_rpc::{Url, WebSocketClientUrl};
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::{debug, error, info, instrument, warn};
 
-type Client = Provider<Ws>;
+/// Maximum number of blocks queried by a single `eth_getLogs` request
+const MAX_LOGS_BLOCK_RANGE: u64 = 1000;
+
+/// Interval between two checks of the monitor commands while waiting for new logs
+const COMMAND_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum idle time of the subscription before checking the WebSocket connection
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
 
-// #[derive(Clone, Debug)]
 pub struct AxonEventMonitor {
     websocket_addr: WebSocketClientUrl,
-    client: Arc<Client>,
+    rpc_addr: Url,
+    event_source: EventSource,
+    poll_interval: Duration,
     rt: Arc<TokioRuntime>,
     chain_id: ChainId,
     contract_address: Address,
+    /// The ICS29 fee contract, whose `IncentivizedPacket` events are monitored as well
+    fee_contract_address: Option<Address>,
+    /// The first block whose logs have not been completely processed
     start_block_number: u64,
+    /// Position of the last processed log, used to skip logs delivered twice
+    /// by the subscription and the gap filling
+    last_log_position: Option<(u64, U256)>,
+    reconnect_attempts: u32,
     rx_cmd: channel::Receiver<MonitorCmd>,
     event_bus: EventBus<Arc<Result<EventBatch>>>,
 }
 
 impl AxonEventMonitor {
-    /// Create an event monitor, and connect to a node
+    /// Create an event monitor, the connection to the node is established in [`Self::run`]
     #[instrument(
         name = "axon_event_monitor.create",
         level = "error",
@@ -45,15 +68,17 @@ impl AxonEventMonitor {
     pub fn new(
         chain_id: ChainId,
         websocket_addr: WebSocketClientUrl,
+        rpc_addr: Url,
+        event_source: EventSource,
+        poll_interval: Duration,
         contract_address: Address,
+        fee_contract_address: Option<Address>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, TxMonitorCmd)> {
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let client = rt
-            .block_on(Provider::<Ws>::connect(websocket_addr.to_string()))
-            .map_err(|_| Error::client_creation_failed(chain_id.clone(), websocket_addr.clone()))?;
-
+        let client = Provider::<Http>::try_from(rpc_addr.to_string())
+            .map_err(|e| Error::others(e.to_string()))?;
         let start_block_number = rt
             .block_on(client.get_block_number())
             .map_err(|e| Error::others(e.to_string()))?
@@ -62,32 +87,22 @@ impl AxonEventMonitor {
         let event_bus = EventBus::new();
         let monitor = Self {
             websocket_addr,
-            client: Arc::new(client),
+            rpc_addr,
+            event_source,
+            poll_interval,
             rt,
             chain_id,
             contract_address,
+            fee_contract_address,
             start_block_number,
+            last_log_position: None,
+            reconnect_attempts: 0,
             rx_cmd,
             event_bus,
         };
         Ok((monitor, TxMonitorCmd::new(tx_cmd)))
     }
 
-    // XXX: we met a connection error that ethers-rs doesn't reconnect WebSocket if it meets error,
-    //      we just choose to recreate provider mannully to solve connection problem
-    //
-    //      see: https://github.com/gakonst/ethers-rs/issues/2323
-    fn new_ws_provider(&mut self) -> Result<Client> {
-        let client = self
-            .rt
-            .block_on(Provider::<Ws>::connect(self.websocket_addr.to_string()))
-            .map_err(|_| {
-                Error::client_creation_failed(self.chain_id.clone(), self.websocket_addr.clone())
-            })?;
-        Ok(client)
-    }
-
-    #[allow(clippy::while_let_loop)]
     #[instrument(
         name = "axon_event_monitor",
         level = "error",
@@ -95,36 +110,163 @@ impl AxonEventMonitor {
         fields(chain = %self.chain_id)
     )]
     pub fn run(mut self) {
-        if let Next::Continue = self.update_subscribe(false) {
-            info!("start Axon event monitor for {}", self.chain_id,);
-            let mut contract = Contract::new(self.contract_address, Arc::clone(&self.client));
-            info!(
-                "start to fetch IBC events from block {}",
-                self.start_block_number
+        if let Next::Abort = self.update_subscribe(false) {
+            return;
+        }
+        info!(
+            "start Axon event monitor in {:?} mode, fetch IBC events from block {}",
+            self.event_source, self.start_block_number
+        );
+        loop {
+            let next = match self.event_source {
+                EventSource::WebSocket => self.run_websocket(),
+                EventSource::Polling => self.run_polling(),
+            };
+            if let Next::Abort = next {
+                break;
+            }
+
+            // the connection is lost, wait before the next attempt
+            let delay = reconnect_delay(self.reconnect_attempts);
+            self.reconnect_attempts = self.reconnect_attempts.saturating_add(1);
+            warn!(
+                "reconnect to Axon in {delay:?} (attempt {}), fetch IBC events from block {}",
+                self.reconnect_attempts, self.start_block_number
             );
-            loop {
-                std::thread::sleep(Duration::from_secs(1));
-                match self.run_once(&contract) {
-                    (Next::Abort, _) => break,
-                    (Next::Continue, false) => match self.new_ws_provider() {
-                        Ok(client) => {
-                            // recreate contract when WS connection meets error
-                            self.client = Arc::new(client);
-                            contract =
-                                Contract::new(self.contract_address, Arc::clone(&self.client));
-                            info!(
-                                "restart to fetch IBC events from block {}",
-                                self.start_block_number
-                            );
-                        }
-                        Err(err) => {
-                            error!("restart provider failed: {err}");
+            if let Next::Abort = self.wait(delay) {
+                break;
+            }
+            if self.event_source == EventSource::WebSocket {
+                telemetry!(ws_reconnect, &self.chain_id);
+            }
+        }
+        debug!("event monitor is shutting down");
+    }
+
+    /// Subscribe to the logs of the IBC contract, returns `Next::Continue` once the
+    /// subscription is broken.
+    //
+    // XXX: ethers-rs doesn't reconnect WebSocket if it meets error, so the provider is
+    //      recreated manually on each reconnection
+    //
+    //      see: https://github.com/gakonst/ethers-rs/issues/2323
+    fn run_websocket(&mut self) -> Next {
+        let rt = self.rt.clone();
+        let client = match rt.block_on(Provider::<Ws>::connect(self.websocket_addr.to_string())) {
+            Ok(client) => client,
+            Err(err) => {
+                error!("failed to connect to {}: {err}", self.websocket_addr);
+                return Next::Continue;
+            }
+        };
+
+        // subscribe before filling the gap, so that no log is missed in between
+        let filter = Filter::new().address(self.contract_addresses());
+        let mut stream = match rt.block_on(client.subscribe_logs(&filter)) {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!("failed to subscribe to IBC contract logs: {err}");
+                return Next::Continue;
+            }
+        };
+        if let Err(err) = self.fetch_to_tip(&client) {
+            error!("failed to fill the gap of IBC events: {err}");
+            return Next::Continue;
+        }
+        self.reconnect_attempts = 0;
+
+        let mut idle = Duration::ZERO;
+        loop {
+            if let Next::Abort = self.update_subscribe(true) {
+                return Next::Abort;
+            }
+            let next_log = rt.block_on(async {
+                tokio::time::timeout(COMMAND_CHECK_INTERVAL, stream.next()).await
+            });
+            match next_log {
+                Ok(Some(log)) => {
+                    idle = Duration::ZERO;
+                    self.process_log(log);
+                }
+                Ok(None) => {
+                    error!("subscription of IBC contract logs is closed");
+                    return Next::Continue;
+                }
+                // no new log yet, make sure the connection is still alive since a broken
+                // WebSocket doesn't always terminate the subscription
+                Err(_) => {
+                    idle += COMMAND_CHECK_INTERVAL;
+                    if idle >= HEARTBEAT_INTERVAL {
+                        idle = Duration::ZERO;
+                        let heartbeat = rt.block_on(async {
+                            tokio::time::timeout(HEARTBEAT_INTERVAL, client.get_block_number())
+                                .await
+                        });
+                        if !matches!(heartbeat, Ok(Ok(_))) {
+                            error!("WebSocket connection to {} is lost", self.websocket_addr);
+                            return Next::Continue;
                         }
-                    },
-                    (Next::Continue, true) => {}
+                    }
                 }
             }
-            debug!("event monitor is shutting down");
+        }
+    }
+
+    /// Query the logs of the IBC contract every `poll_interval`, returns `Next::Continue`
+    /// once a query fails.
+    fn run_polling(&mut self) -> Next {
+        let client = match Provider::<Http>::try_from(self.rpc_addr.to_string()) {
+            Ok(client) => client,
+            Err(err) => {
+                error!(
+                    "failed to create HTTP provider for {}: {err}",
+                    self.rpc_addr
+                );
+                return Next::Continue;
+            }
+        };
+        loop {
+            if let Err(err) = self.fetch_to_tip(&client) {
+                error!("failed to poll IBC events: {err}");
+                return Next::Continue;
+            }
+            self.reconnect_attempts = 0;
+            if let Next::Abort = self.wait(self.poll_interval) {
+                return Next::Abort;
+            }
+        }
+    }
+
+    /// Fetch the logs from `start_block_number` up to the current tip block
+    fn fetch_to_tip<M: Middleware>(&mut self, client: &M) -> core::result::Result<(), String> {
+        let tip_block_number = self
+            .rt
+            .block_on(client.get_block_number())
+            .map_err(|e| format!("failed to fetch Axon latest block number: {e}"))?
+            .as_u64();
+
+        while self.start_block_number <= tip_block_number {
+            let from_block = self.start_block_number;
+            let to_block = tip_block_number.min(from_block + MAX_LOGS_BLOCK_RANGE - 1);
+            let filter = Filter::new()
+                .address(self.contract_addresses())
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = self.rt.block_on(client.get_logs(&filter)).map_err(|e| {
+                format!("failed to fetch events from block {from_block} to block {to_block}: {e}")
+            })?;
+            logs.into_iter().for_each(|log| self.process_log(log));
+            self.start_block_number = to_block + 1;
+        }
+        Ok(())
+    }
+
+    /// Wait for `duration` while still handling the monitor commands
+    fn wait(&mut self, duration: Duration) -> Next {
+        match self.rx_cmd.recv_timeout(duration) {
+            Ok(cmd) => self.handle_cmd(cmd),
+            Err(channel::RecvTimeoutError::Timeout) => Next::Continue,
+            Err(channel::RecvTimeoutError::Disconnected) => Next::Abort,
         }
     }
 
@@ -143,6 +285,10 @@ impl AxonEventMonitor {
                 Err(_) => return Next::Abort,
             }
         };
+        self.handle_cmd(cmd)
+    }
+
+    fn handle_cmd(&mut self, cmd: MonitorCmd) -> Next {
         match cmd {
             MonitorCmd::Shutdown => return Next::Abort,
             MonitorCmd::Subscribe(tx) => {
@@ -154,53 +300,47 @@ impl AxonEventMonitor {
         Next::Continue
     }
 
-    fn run_once(&mut self, contract: &OwnableIBCHandler<Client>) -> (Next, bool) {
-        if let Next::Abort = self.update_subscribe(true) {
-            return (Next::Abort, true);
+    fn process_log(&mut self, log: Log) {
+        if log.removed == Some(true) {
+            return;
         }
-
-        let tip_block_number = match self.rt.block_on(contract.client().get_block_number()) {
-            Ok(tip) => tip.as_u64(),
-            Err(err) => {
-                error!("failed to fetch Axon latest block number: {err}");
-                return (Next::Continue, false);
-            }
-        };
-
-        if self.start_block_number >= tip_block_number {
-            return (Next::Continue, true);
+        let meta = LogMeta::from(&log);
+        let position = (meta.block_number.as_u64(), meta.log_index);
+        if matches!(self.last_log_position, Some(last) if position <= last) {
+            return;
         }
+        self.last_log_position = Some(position);
+        self.start_block_number = self.start_block_number.max(position.0);
 
-        let query = contract
-            .events()
-            .from_block(self.start_block_number)
-            .to_block(tip_block_number);
-        let events = match self.rt.block_on(query.query_with_meta()) {
-            Ok(events) => events,
-            Err(err) => {
-                error!(
-                    "failed to fetch events from block {} to block {tip_block_number}: {err}",
-                    self.start_block_number
-                );
-                return (Next::Continue, false);
+        if Some(log.address) == self.fee_contract_address {
+            let event = <IncentivizedPacketFilter as EthEvent>::decode_log(&log.into())
+                .map_err(|err| err.to_string())
+                .and_then(|event| {
+                    IncentivizedPacket::try_from(event).map_err(|err| err.to_string())
+                });
+            match event {
+                Ok(event) => self.process_event(IbcEvent::IncentivizedPacket(event), meta),
+                Err(err) => warn!("skip undecodable log of fee contract: {err}"),
             }
-        };
-
-        events
-            .into_iter()
-            .for_each(|(event, meta)| self.process_event(event, meta));
+            return;
+        }
+        match ContractEvents::decode_log(&log.into()) {
+            Ok(event) => self.process_event(event.into(), meta),
+            Err(err) => warn!("skip undecodable log of IBC contract: {err}"),
+        }
+    }
 
-        self.start_block_number = tip_block_number + 1;
-        (Next::Continue, true)
+    fn contract_addresses(&self) -> Vec<Address> {
+        let mut addresses = vec![self.contract_address];
+        addresses.extend(self.fee_contract_address);
+        addresses
     }
 
-    fn process_event(&mut self, event: ContractEvents, meta: LogMeta) {
-        println!("\n{}\n[event] = {:?}", self.chain_id, event);
-        println!("[event_meta] = {:?}\n", meta);
+    fn process_event(&mut self, event: IbcEvent, meta: LogMeta) {
+        debug!("{} received {event:?} with {meta:?}", self.chain_id);
 
-        self.start_block_number = meta.block_number.as_u64();
         let event = IbcEventWithHeight::new_with_tx_hash(
-            event.into(),
+            event,
             Height::from_noncosmos_height(meta.block_number.as_u64()),
             meta.transaction_hash.into(),
         );
@@ -214,6 +354,33 @@ impl AxonEventMonitor {
     }
 
     fn process_batch(&mut self, batch: EventBatch) {
+        telemetry!(ws_events, &batch.chain_id, batch.events.len() as u64);
+
         self.event_bus.broadcast(Arc::new(Ok(batch)));
     }
 }
+
+/// Exponential backoff between two reconnections, capped at `MAX_RECONNECT_DELAY`
+fn reconnect_delay(attempts: u32) -> Duration {
+    INITIAL_RECONNECT_DELAY
+        .checked_mul(2u32.saturating_pow(attempts))
+        .unwrap_or(MAX_RECONNECT_DELAY)
+        .min(MAX_RECONNECT_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay() {
+        let delays = (0..8).map(reconnect_delay).collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            [1, 2, 4, 8, 16, 32, 60, 60]
+                .map(Duration::from_secs)
+                .to_vec()
+        );
+        assert_eq!(reconnect_delay(u32::MAX), MAX_RECONNECT_DELAY);
+    }
+}
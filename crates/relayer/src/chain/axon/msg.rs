@@ -1,13 +1,29 @@
 use ethers::types::Bytes;
-use ibc_proto::google::protobuf::Any;
+use ibc_proto::{
+    google::protobuf::Any, ibc::lightclients::tendermint::v1::Header as RawTmHeader,
+    protobuf::Protobuf,
+};
 use ibc_relayer_types::{
     clients::{
-        ics07_axon::client_state::AXON_CLIENT_STATE_TYPE_URL,
+        ics07_axon::{
+            client_state::AXON_CLIENT_STATE_TYPE_URL, misbehaviour::AXON_MISBEHAVIOUR_TYPE_URL,
+        },
         ics07_ckb::client_state::CKB_CLIENT_STATE_TYPE_URL,
+        ics07_tendermint::{
+            client_state::TENDERMINT_CLIENT_STATE_TYPE_URL,
+            header::{Header as TmHeader, TENDERMINT_HEADER_TYPE_URL},
+            misbehaviour::TENDERMINT_MISBEHAVIOR_TYPE_URL,
+        },
     },
     core::{
         ics02_client::{
-            client_type::ClientType, events as client_events, msgs::create_client::MsgCreateClient,
+            client_type::ClientType,
+            events as client_events,
+            header::Header,
+            msgs::{
+                create_client::MsgCreateClient, misbehaviour::MsgSubmitMisbehaviour,
+                update_client::MsgUpdateClient,
+            },
         },
         ics03_connection::{
             self,
@@ -322,6 +338,7 @@ impl TryFrom<MsgCreateClient> for contract::MsgCreateClient {
         let client_type = match value.client_state.type_url.as_str() {
             AXON_CLIENT_STATE_TYPE_URL => ClientType::Axon.as_str(),
             CKB_CLIENT_STATE_TYPE_URL => ClientType::Ckb4Ibc.as_str(),
+            TENDERMINT_CLIENT_STATE_TYPE_URL => ClientType::Tendermint.as_str(),
             type_url => {
                 return Err(Error::other_error(format!(
                     "unsupported client state type_url: {type_url}"
@@ -347,6 +364,55 @@ impl TryFrom<Any> for contract::MsgCreateClient {
     }
 }
 
+// the light client of the IBC handler receives the raw header, the same way
+// the client and consensus states are passed on creation
+impl TryFrom<MsgUpdateClient> for contract::MsgUpdateClient {
+    type Error = Error;
+
+    fn try_from(value: MsgUpdateClient) -> Result<Self, Self::Error> {
+        if value.header.type_url != TENDERMINT_HEADER_TYPE_URL {
+            return Err(Error::other_error(format!(
+                "unsupported header type_url: {}",
+                value.header.type_url
+            )));
+        }
+        Ok(Self {
+            client_id: value.client_id.as_str().into(),
+            client_message: value.header.value.into(),
+        })
+    }
+}
+
+impl TryFrom<Any> for contract::MsgUpdateClient {
+    type Error = Error;
+
+    fn try_from(value: Any) -> Result<Self, Self::Error> {
+        let type_url = value.type_url.clone();
+        MsgUpdateClient::from_any(value)
+            .map_err(|e| Error::protobuf_decode(type_url, e))?
+            .try_into()
+    }
+}
+
+// the light client also receives misbehaviour through `updateClient`, and freezes
+// itself once the evidence is verified
+impl TryFrom<MsgSubmitMisbehaviour> for contract::MsgUpdateClient {
+    type Error = Error;
+
+    fn try_from(value: MsgSubmitMisbehaviour) -> Result<Self, Self::Error> {
+        let type_url = value.misbehaviour.type_url.as_str();
+        if type_url != TENDERMINT_MISBEHAVIOR_TYPE_URL && type_url != AXON_MISBEHAVIOUR_TYPE_URL {
+            return Err(Error::other_error(format!(
+                "unsupported misbehaviour type_url: {type_url}"
+            )));
+        }
+        Ok(Self {
+            client_id: value.client_id.as_str().into(),
+            client_message: value.misbehaviour.value.into(),
+        })
+    }
+}
+
 impl From<MsgConnectionOpenInit> for contract::MsgConnectionOpenInit {
     fn from(value: MsgConnectionOpenInit) -> Self {
         Self {
@@ -813,13 +879,24 @@ impl From<contract::OwnableIBCHandlerEvents> for IbcEvent {
                 let client_id: ClientId = event.client_id.parse().unwrap();
                 let client_type = client_id.clone().into();
                 assert!(client_type != ClientType::Mock);
+                // keep the header around for misbehaviour detection
+                let header = match client_type {
+                    ClientType::Tendermint => {
+                        <TmHeader as Protobuf<RawTmHeader>>::decode_vec(&event.client_message).ok()
+                    }
+                    _ => None,
+                };
+                let consensus_height = header
+                    .as_ref()
+                    .map(|header| header.height())
+                    .unwrap_or_default();
                 let event = client_events::UpdateClient {
                     common: client_events::Attributes {
                         client_id,
                         client_type,
-                        consensus_height: Height::default(),
+                        consensus_height,
                     },
-                    header: None,
+                    header: header.map(|header| Box::new(header) as Box<dyn Header>),
                 };
                 IbcEvent::UpdateClient(event)
             }
@@ -0,0 +1,35 @@
+use ethers::providers::Middleware;
+use ethers::types::{Address, BlockId, BlockNumber, U256};
+use tokio::sync::Mutex;
+
+use crate::error::Error;
+
+/// Local nonce counter of the relayer account.
+///
+/// The nonce is fetched from the pending pool once and then incremented locally, so
+/// transactions sent before the previous ones are committed don't reuse the same nonce.
+/// Call [`NonceManager::reset`] whenever a transaction fails to be broadcast, so that the
+/// next one re-synchronizes with the node.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    next: Mutex<Option<U256>>,
+}
+
+impl NonceManager {
+    pub async fn next<M: Middleware>(&self, provider: &M, address: Address) -> Result<U256, Error> {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => provider
+                .get_transaction_count(address, Some(BlockId::Number(BlockNumber::Pending)))
+                .await
+                .map_err(|e| Error::rpc_response(e.to_string()))?,
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    pub async fn reset(&self) {
+        *self.next.lock().await = None;
+    }
+}
@@ -0,0 +1,553 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use ethers::types::{Address, BlockId, Bytes};
+use ibc_proto::{
+    google::protobuf::Any,
+    ibc::{
+        apps::fee::v1::QueryIncentivizedPacketRequest, core::channel::v1::PacketId,
+        lightclients::tendermint::v1::Header as RawTmHeader,
+    },
+    protobuf::Protobuf,
+};
+use ibc_relayer_types::{
+    clients::ics07_tendermint::{
+        client_state::test_util::get_dummy_tendermint_client_state,
+        consensus_state::ConsensusState as TmConsensusState,
+        header::{test_util::get_dummy_ics07_header, Header as TmHeader},
+        misbehaviour::Misbehaviour as TmMisbehaviour,
+    },
+    core::{
+        ics02_client::{
+            header::{downcast_header, Header},
+            msgs::{
+                create_client::MsgCreateClient, misbehaviour::MsgSubmitMisbehaviour,
+                update_client::MsgUpdateClient,
+            },
+        },
+        ics03_connection::{
+            connection::Counterparty as ConnectionCounterparty,
+            msgs::conn_open_init::MsgConnectionOpenInit,
+        },
+        ics04_channel::{
+            channel::{ChannelEnd, Counterparty as ChannelCounterparty, Order, State},
+            msgs::{
+                chan_close_init::MsgChannelCloseInit, chan_open_init::MsgChannelOpenInit,
+                recv_packet::MsgRecvPacket, timeout_on_close::MsgTimeoutOnClose,
+            },
+            packet::{Packet, Sequence},
+            timeout::TimeoutHeight,
+            version::Version,
+        },
+        ics23_commitment::commitment::CommitmentPrefix,
+        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+    },
+    events::IbcEvent,
+    proofs::Proofs,
+    signer::Signer,
+    timestamp::Timestamp,
+    tx_msg::Msg,
+    Height,
+};
+use tokio::runtime::Runtime as TokioRuntime;
+
+use super::{
+    contract::{
+        self, ChannelCloseInitCall, ChannelOpenInitCall, CloseInitChannelFilter,
+        ConnectionOpenInitCall, CreateClientCall, CreateClientFilter, GetClientStateCall,
+        GetConsensusStateCall, OpenInitChannelFilter, OpenInitConnectionFilter,
+        ReceivePacketFilter, RecvPacketCall, UpdateClientCall, UpdateClientFilter,
+    },
+    fee::{FeeCoin, GetIncentivizedPacketCall, PacketFee},
+    mock_node::{encode_event, MockAxonNode},
+    rpc::AxonRpcClient,
+    utils::{parse_storage_query, STORAGE_QUERY_TYPE},
+    AxonChain, AxonRpc,
+};
+use crate::{
+    chain::{
+        endpoint::ChainEndpoint,
+        requests::{
+            CrossChainQueryRequest, IncludeProof, QueryClientStateRequest,
+            QueryConsensusStateRequest, QueryHeight,
+        },
+        tracking::TrackedMsgs,
+    },
+    client_state::AnyClientState,
+    config::{axon::AxonChainConfig, AddressType, ChainConfig},
+    consensus_state::AnyConsensusState,
+    keyring::{Secp256k1KeyPair, SigningKeyPair},
+    misbehaviour::AnyMisbehaviour,
+};
+
+const KEY_NAME: &str = "relayer";
+const SECRET_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+fn contract_address() -> Address {
+    Address::repeat_byte(0x11)
+}
+
+pub(crate) fn setup() -> (MockAxonNode, AxonChain) {
+    let node = MockAxonNode::start(contract_address());
+    let config: AxonChainConfig = toml::from_str(&format!(
+        r#"
+        id = 'axon-0'
+        websocket_addr = '{}'
+        rpc_addr = '{}'
+        contract_address = '{:?}'
+        transfer_contract_address = '{:?}'
+        restore_block_count = 10000
+        key_name = '{KEY_NAME}'
+        key_store_type = 'Memory'
+        store_prefix = 'forcerelay'
+        "#,
+        node.websocket_addr,
+        node.rpc_addr,
+        contract_address(),
+        Address::repeat_byte(0x22),
+    ))
+    .unwrap();
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+    let mut chain = AxonChain::bootstrap(ChainConfig::Axon(config), rt).unwrap();
+    let key = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Axon).unwrap();
+    chain.keybase_mut().add_key(KEY_NAME, key).unwrap();
+    (node, chain)
+}
+
+fn signer() -> Signer {
+    Signer::from_str("relayer").unwrap()
+}
+
+fn connection_open_init_event() -> OpenInitConnectionFilter {
+    OpenInitConnectionFilter {
+        connection_id: "connection-0".to_owned(),
+        client_id: "07-axon-0".to_owned(),
+        counterparty_connection_id: "".to_owned(),
+        counterparty_client_id: "07-tendermint-0".to_owned(),
+    }
+}
+
+fn send(chain: &mut AxonChain, msg: impl Msg) -> Result<IbcEvent, crate::error::Error> {
+    let tracked_msgs = TrackedMsgs::new_static(vec![msg.to_any()], "test");
+    let mut events = chain.send_messages_and_wait_commit(tracked_msgs)?;
+    assert_eq!(events.len(), 1);
+    Ok(events.remove(0).event)
+}
+
+#[test]
+fn test_axon_rpc_of_mock_node() {
+    let rt = TokioRuntime::new().unwrap();
+    let node = MockAxonNode::start(contract_address());
+    node.produce_empty_blocks(2);
+    let client = AxonRpcClient::new(&node.rpc_addr);
+
+    let block_id = BlockId::Number(2u64.into());
+    let block = rt.block_on(client.get_block_by_id(block_id)).unwrap();
+    assert_eq!(block.unwrap().header.number, 2);
+    let proof = rt.block_on(client.get_proof_by_id(block_id)).unwrap();
+    let expected = rt.block_on(node.get_proof_by_id(block_id)).unwrap();
+    assert_eq!(
+        serde_json::to_value(proof).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+
+    // blocks and proofs beyond the tip are not available yet
+    let block_id = BlockId::Number(3u64.into());
+    assert!(rt
+        .block_on(client.get_proof_by_id(block_id))
+        .unwrap()
+        .is_none());
+    assert!(rt.block_on(client.get_current_metadata()).is_err());
+}
+
+#[test]
+fn test_send_connection_open_init() {
+    let (node, mut chain) = setup();
+    node.on_transaction::<ConnectionOpenInitCall>(vec![encode_event(connection_open_init_event())]);
+
+    let client_id = ClientId::from_str("07-axon-0").unwrap();
+    let msg = MsgConnectionOpenInit {
+        client_id: client_id.clone(),
+        counterparty: ConnectionCounterparty::new(
+            ClientId::from_str("07-tendermint-0").unwrap(),
+            None,
+            CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap(),
+        ),
+        version: None,
+        delay_period: Duration::ZERO,
+        signer: signer(),
+    };
+    let event = send(&mut chain, msg).unwrap();
+    match event {
+        IbcEvent::OpenInitConnection(event) => {
+            assert_eq!(event.0.client_id, client_id);
+            assert_eq!(
+                event.0.connection_id,
+                Some(ConnectionId::from_str("connection-0").unwrap())
+            );
+        }
+        event => panic!("unexpected event {event}"),
+    }
+    assert_eq!(node.tip_block_number(), 1);
+}
+
+#[test]
+fn test_send_channel_open_init() {
+    let (node, mut chain) = setup();
+    node.on_transaction::<ChannelOpenInitCall>(vec![encode_event(OpenInitChannelFilter {
+        port_id: "transfer".to_owned(),
+        channel_id: "channel-0".to_owned(),
+        connection_id: "connection-0".to_owned(),
+        counterparty_port_id: "transfer".to_owned(),
+        counterparty_channel_id: "".to_owned(),
+    })]);
+
+    let port_id = PortId::transfer();
+    let msg = MsgChannelOpenInit {
+        port_id: port_id.clone(),
+        channel: ChannelEnd::new(
+            State::Init,
+            Order::Unordered,
+            ChannelCounterparty::new(port_id, None),
+            vec![ConnectionId::from_str("connection-0").unwrap()],
+            Version::ics20(),
+        ),
+        signer: signer(),
+    };
+    let event = send(&mut chain, msg).unwrap();
+    match event {
+        IbcEvent::OpenInitChannel(event) => {
+            assert_eq!(event.channel_id, Some(ChannelId::new(0)));
+        }
+        event => panic!("unexpected event {event}"),
+    }
+}
+
+#[test]
+fn test_send_channel_close_init() {
+    let (node, mut chain) = setup();
+    node.on_transaction::<ChannelCloseInitCall>(vec![encode_event(CloseInitChannelFilter {
+        port_id: "transfer".to_owned(),
+        channel_id: "channel-0".to_owned(),
+        connection_id: "connection-0".to_owned(),
+        counterparty_port_id: "transfer".to_owned(),
+        counterparty_channel_id: "channel-1".to_owned(),
+    })]);
+
+    let msg = MsgChannelCloseInit {
+        port_id: PortId::transfer(),
+        channel_id: ChannelId::new(0),
+        signer: signer(),
+    };
+    let event = send(&mut chain, msg).unwrap();
+    match event {
+        IbcEvent::CloseInitChannel(event) => {
+            assert_eq!(event.channel_id, ChannelId::new(0));
+            assert_eq!(event.counterparty_channel_id, Some(ChannelId::new(1)));
+        }
+        event => panic!("unexpected event {event}"),
+    }
+}
+
+#[test]
+fn test_send_recv_packet() {
+    let (node, mut chain) = setup();
+    let packet = Packet {
+        sequence: Sequence::from(1),
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(0),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(1),
+        data: b"packet".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    node.on_transaction::<RecvPacketCall>(vec![encode_event(ReceivePacketFilter {
+        packet: packet.clone().into(),
+    })]);
+
+    let proof_height = Height::from_noncosmos_height(1);
+    let msg = MsgRecvPacket {
+        packet: packet.clone(),
+        proofs: Proofs::new(
+            vec![0u8].try_into().unwrap(),
+            None,
+            None,
+            None,
+            proof_height,
+        )
+        .unwrap(),
+        signer: signer(),
+    };
+    let event = send(&mut chain, msg).unwrap();
+    match event {
+        IbcEvent::ReceivePacket(event) => assert_eq!(event.packet, packet),
+        event => panic!("unexpected event {event}"),
+    }
+}
+
+#[test]
+fn test_send_timeout_on_close_is_unsupported() {
+    let (_node, mut chain) = setup();
+    let packet = Packet {
+        sequence: Sequence::from(1),
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(0),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(1),
+        data: b"packet".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    let msg = MsgTimeoutOnClose {
+        packet,
+        next_sequence_recv: Sequence::from(1),
+        proofs: Proofs::new(
+            vec![0u8].try_into().unwrap(),
+            None,
+            None,
+            Some(vec![1u8].try_into().unwrap()),
+            Height::from_noncosmos_height(1),
+        )
+        .unwrap(),
+        signer: signer(),
+    };
+    let err = send(&mut chain, msg).unwrap_err();
+    assert!(err.to_string().contains("unsupported message type url"));
+}
+
+#[test]
+fn test_send_update_tendermint_client() {
+    let (node, mut chain) = setup();
+    let header = get_dummy_ics07_header();
+    let client_message = Protobuf::<RawTmHeader>::encode_vec(&header).unwrap();
+    node.on_transaction::<UpdateClientCall>(vec![encode_event(UpdateClientFilter {
+        client_id: "07-tendermint-0".to_owned(),
+        client_message: client_message.into(),
+    })]);
+
+    let client_id = ClientId::from_str("07-tendermint-0").unwrap();
+    let msg = MsgUpdateClient::new(client_id.clone(), header.clone().into(), signer());
+    let event = send(&mut chain, msg).unwrap();
+    match event {
+        IbcEvent::UpdateClient(event) => {
+            assert_eq!(event.client_id(), &client_id);
+            assert_eq!(event.consensus_height(), header.height());
+            let decoded = event.header.expect("update client header");
+            assert_eq!(downcast_header::<TmHeader>(decoded.as_ref()), Some(&header));
+        }
+        event => panic!("unexpected event {event}"),
+    }
+}
+
+#[test]
+fn test_create_query_and_update_tendermint_client() {
+    let (node, mut chain) = setup();
+    let header = get_dummy_ics07_header();
+    let client_state = get_dummy_tendermint_client_state(header.signed_header.header.clone());
+    let consensus_state = TmConsensusState::from(header.clone());
+    let client_id = ClientId::from_str("07-tendermint-0").unwrap();
+
+    node.on_transaction::<CreateClientCall>(vec![encode_event(CreateClientFilter {
+        client_id: client_id.to_string(),
+        client_type: "07-tendermint".to_owned(),
+    })]);
+    let msg = MsgCreateClient::new(
+        client_state.clone().into(),
+        consensus_state.clone().into(),
+        signer(),
+    )
+    .unwrap();
+    let created = contract::MsgCreateClient::try_from(msg.clone()).unwrap();
+    match send(&mut chain, msg).unwrap() {
+        IbcEvent::CreateClient(event) => assert_eq!(event.client_id(), &client_id),
+        event => panic!("unexpected event {event}"),
+    }
+
+    // the IBC handler returns the states in the same encoding they were created with
+    let stored = |state: Bytes| [format!("{client_id}|").as_bytes(), state.as_ref()].concat();
+    node.on_call::<GetClientStateCall, _>((Bytes::from(stored(created.client_state)), true));
+    node.on_call::<GetConsensusStateCall, _>((Bytes::from(stored(created.consensus_state)), true));
+    let (queried, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .unwrap();
+    assert_eq!(queried, AnyClientState::from(client_state));
+    let (queried, _) = chain
+        .query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client_id.clone(),
+                consensus_height: header.height(),
+                query_height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .unwrap();
+    assert_eq!(queried, AnyConsensusState::from(consensus_state));
+
+    let client_message = Protobuf::<RawTmHeader>::encode_vec(&header).unwrap();
+    node.on_transaction::<UpdateClientCall>(vec![encode_event(UpdateClientFilter {
+        client_id: client_id.to_string(),
+        client_message: client_message.into(),
+    })]);
+    let msg = MsgUpdateClient::new(client_id.clone(), header.clone().into(), signer());
+    match send(&mut chain, msg).unwrap() {
+        IbcEvent::UpdateClient(event) => assert_eq!(event.client_id(), &client_id),
+        event => panic!("unexpected event {event}"),
+    }
+    assert_eq!(node.tip_block_number(), 2);
+}
+
+#[test]
+fn test_submit_tendermint_misbehaviour() {
+    let (node, mut chain) = setup();
+    let client_id = ClientId::from_str("07-tendermint-0").unwrap();
+    let misbehaviour = TmMisbehaviour {
+        client_id: client_id.clone(),
+        header1: get_dummy_ics07_header(),
+        header2: get_dummy_ics07_header(),
+    };
+    let evidence: Any = AnyMisbehaviour::from(misbehaviour).into();
+    // the evidence reaches the light client through `updateClient`
+    node.on_transaction::<UpdateClientCall>(vec![encode_event(UpdateClientFilter {
+        client_id: client_id.to_string(),
+        client_message: evidence.value.clone().into(),
+    })]);
+
+    let msg = MsgSubmitMisbehaviour {
+        client_id: client_id.clone(),
+        misbehaviour: evidence,
+        signer: signer(),
+    };
+    match send(&mut chain, msg).unwrap() {
+        IbcEvent::ClientMisbehaviour(event) => assert_eq!(event.client_id(), &client_id),
+        event => panic!("unexpected event {event}"),
+    }
+
+    let unsupported = MsgSubmitMisbehaviour {
+        client_id,
+        misbehaviour: Any {
+            type_url: "/unknown.Misbehaviour".to_owned(),
+            value: vec![],
+        },
+        signer: signer(),
+    };
+    assert!(send(&mut chain, unsupported).is_err());
+    assert_eq!(node.tip_block_number(), 1);
+}
+
+#[test]
+fn test_transaction_without_expected_event() {
+    let (node, mut chain) = setup();
+    // the transaction is committed but emits the event of another message
+    node.on_transaction::<ChannelOpenInitCall>(vec![encode_event(connection_open_init_event())]);
+
+    let msg = MsgChannelOpenInit {
+        port_id: PortId::transfer(),
+        channel: ChannelEnd::new(
+            State::Init,
+            Order::Unordered,
+            ChannelCounterparty::new(PortId::transfer(), None),
+            vec![ConnectionId::from_str("connection-0").unwrap()],
+            Version::ics20(),
+        ),
+        signer: signer(),
+    };
+    assert!(send(&mut chain, msg).is_err());
+    assert_eq!(node.tip_block_number(), 1);
+}
+
+#[test]
+fn test_query_incentivized_packet() {
+    let (node, mut chain) = setup();
+    let request = QueryIncentivizedPacketRequest {
+        packet_id: Some(PacketId {
+            port_id: "transfer".to_owned(),
+            channel_id: "channel-0".to_owned(),
+            sequence: 1,
+        }),
+        query_height: 0,
+    };
+    let response = chain.query_incentivized_packet(request.clone()).unwrap();
+    assert!(response.incentivized_packet.is_none());
+
+    chain.config.fee_contract_address = Some(Address::repeat_byte(0x33));
+    let fee_coin = || FeeCoin {
+        denom: "AT".to_owned(),
+        amount: 100.into(),
+    };
+    node.on_call::<GetIncentivizedPacketCall, _>(vec![PacketFee {
+        recv_fee: vec![fee_coin()],
+        ack_fee: vec![],
+        timeout_fee: vec![fee_coin()],
+        refund_address: Address::repeat_byte(0x44),
+    }]);
+    let packet = chain
+        .query_incentivized_packet(request.clone())
+        .unwrap()
+        .incentivized_packet
+        .expect("incentivized packet");
+    assert_eq!(packet.packet_id, request.packet_id);
+    let fee = packet.packet_fees[0].fee.clone().unwrap();
+    assert_eq!(fee.recv_fee[0].amount, "100");
+    assert!(fee.ack_fee.is_empty());
+    chain.shutdown().unwrap();
+}
+
+#[test]
+fn test_parse_storage_query() {
+    let request = format!("{}{:064x}", hex::encode(contract_address()), 7);
+    let (address, slot) = parse_storage_query(&request).unwrap();
+    assert_eq!(address, contract_address());
+    assert_eq!(slot, 7.into());
+    assert_eq!(
+        parse_storage_query(&format!("0x{request}")).unwrap().1,
+        7.into()
+    );
+
+    assert!(parse_storage_query(&request[..40]).is_err());
+    assert!(parse_storage_query("not hex").is_err());
+}
+
+#[test]
+fn test_cross_chain_query_skips_unsupported_requests() {
+    let (_node, chain) = setup();
+    let request = |query_type: &str, request: &str| CrossChainQueryRequest {
+        chain_id: chain.id(),
+        query_id: "query-0".to_owned(),
+        query_type: query_type.to_owned(),
+        request: request.to_owned(),
+        height: 0u32.into(),
+    };
+    let storage_request = format!("{}{:064x}", hex::encode(contract_address()), 0);
+    let responses = chain
+        .cross_chain_query(vec![
+            request("store/bank/key", &storage_request),
+            request(STORAGE_QUERY_TYPE, "00"),
+        ])
+        .unwrap();
+    assert!(responses.is_empty());
+    chain.shutdown().unwrap();
+}
+
+#[test]
+fn test_event_monitor_receives_contract_logs() {
+    let (node, mut chain) = setup();
+    let subscription = chain.subscribe().unwrap();
+
+    let number = node.emit(vec![encode_event(connection_open_init_event())]);
+    let batch = subscription
+        .recv_timeout(Duration::from_secs(10))
+        .expect("event batch");
+    let batch = batch.as_ref().as_ref().unwrap();
+    assert_eq!(batch.height, Height::from_noncosmos_height(number));
+    assert!(matches!(
+        batch.events[0].event,
+        IbcEvent::OpenInitConnection(_)
+    ));
+    chain.shutdown().unwrap();
+}
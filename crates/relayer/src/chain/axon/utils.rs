@@ -1,7 +1,5 @@
 use std::str::FromStr;
 
-use axon_tools::types::{Block as AxonBlock, Proof as AxonProof, ValidatorExtend};
-
 use crate::{
     chain::{axon::eth_err::Panic, SEC_TO_NANO},
     client_state::{AnyClientState, IdentifiedAnyClientState},
@@ -10,11 +8,27 @@ use crate::{
     event::IbcEventWithHeight,
     ibc_contract::OwnableIBCHandlerEvents,
 };
-use ethers::{abi::AbiDecode, contract::ContractError, providers::Middleware, types::H256};
+use ethers::{
+    abi::AbiDecode,
+    contract::ContractError,
+    providers::Middleware,
+    types::{H160, U256},
+};
+use ibc_proto::{
+    ibc::lightclients::tendermint::v1::{
+        ClientState as RawTmClientState, ConsensusState as RawTmConsensusState,
+    },
+    protobuf::Protobuf,
+};
 use ibc_relayer_types::{
+    applications::ics31_icq::error::Error as CrossChainQueryError,
     clients::{
         ics07_axon::{client_state::AxonClientState, consensus_state::AxonConsensusState},
         ics07_ckb::{client_state::CkbClientState, consensus_state::CkbConsensusState},
+        ics07_tendermint::{
+            client_state::ClientState as TmClientState,
+            consensus_state::ConsensusState as TmConsensusState,
+        },
     },
     core::{ics02_client::client_type::ClientType, ics24_host::identifier::ClientId},
     timestamp::Timestamp,
@@ -52,70 +66,85 @@ pub fn to_identified_any_client_state(
     })
 }
 
-// response format designed by IBC solidity: "ClientId|JSON(ClientState)"
+// response format designed by IBC solidity: "ClientId|State", where the state is
+// JSON for Axon and Ckb4Ibc clients and protobuf for Tendermint clients
+fn split_client_response(response: &[u8]) -> Result<(ClientId, &[u8]), Error> {
+    let Some(separator) = response.iter().position(|byte| *byte == b'|') else {
+        return Err(Error::other_error(
+            "missing client id separator in response".to_owned(),
+        ));
+    };
+    let client_id = std::str::from_utf8(&response[..separator])
+        .map_err(convert_err)
+        .and_then(|client_id| ClientId::from_str(client_id).map_err(convert_err))?;
+    Ok((client_id, &response[separator + 1..]))
+}
+
 pub fn to_any_client_state(
     response: &ethers::core::types::Bytes,
 ) -> Result<(ClientId, AnyClientState), Error> {
-    let (client_id, client_state) = {
-        let response =
-            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
-        let result = response
-            .split('|')
-            .map(ToString::to_string)
-            .collect::<Vec<_>>();
-        assert!(result.len() == 2);
-        let client_id = ClientId::from_str(result[0].as_str())
-            .map_err(|e| Error::other_error(e.to_string()))?;
-        let client_state = result[1].clone();
-        (client_id, client_state)
-    };
+    let (client_id, client_state) = split_client_response(response)?;
     let any_client_state = match client_id.clone().into() {
-        ClientType::Axon => serde_json::from_slice::<AxonClientState>(client_state.as_bytes())
+        ClientType::Axon => serde_json::from_slice::<AxonClientState>(client_state)
             .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
             .into(),
-        ClientType::Ckb4Ibc => serde_json::from_slice::<CkbClientState>(client_state.as_bytes())
+        ClientType::Ckb4Ibc => serde_json::from_slice::<CkbClientState>(client_state)
             .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
             .into(),
-        // currently, only support Axon and Ckb4Ibc
-        _ => unimplemented!(),
+        ClientType::Tendermint => {
+            <TmClientState as Protobuf<RawTmClientState>>::decode_vec(client_state)
+                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Tendermint)))?
+                .into()
+        }
+        client_type => {
+            return Err(Error::client_state_type(format!(
+                "{client_type} is not supported"
+            )))
+        }
     };
     Ok((client_id, any_client_state))
 }
 
-// response format designed by IBC solidity: "ClientId|JSON(ConsensusState)"
 pub fn to_any_consensus_state(
     response: &ethers::core::types::Bytes,
 ) -> Result<AnyConsensusState, Error> {
-    let (client_id, consensus_state) = {
-        let response =
-            String::from_utf8(response.to_vec()).map_err(|e| Error::other_error(e.to_string()))?;
-        let result = response
-            .split('|')
-            .map(ToString::to_string)
-            .collect::<Vec<_>>();
-        assert!(result.len() == 2);
-        let client_id = ClientId::from_str(result[0].as_str())
-            .map_err(|e| Error::other_error(e.to_string()))?;
-        let consensus_state = result[1].clone();
-        (client_id, consensus_state)
-    };
+    let (client_id, consensus_state) = split_client_response(response)?;
     let any_consensus_state = match client_id.into() {
-        ClientType::Axon => {
-            serde_json::from_slice::<AxonConsensusState>(consensus_state.as_bytes())
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
+        ClientType::Axon => serde_json::from_slice::<AxonConsensusState>(consensus_state)
+            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Axon)))?
+            .into(),
+        ClientType::Ckb4Ibc => serde_json::from_slice::<CkbConsensusState>(consensus_state)
+            .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
+            .into(),
+        ClientType::Tendermint => {
+            <TmConsensusState as Protobuf<RawTmConsensusState>>::decode_vec(consensus_state)
+                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Tendermint)))?
                 .into()
         }
-        ClientType::Ckb4Ibc => {
-            serde_json::from_slice::<CkbConsensusState>(consensus_state.as_bytes())
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?
-                .into()
+        client_type => {
+            return Err(Error::client_state_type(format!(
+                "{client_type} is not supported"
+            )))
         }
-        // currently, only support Axon and Ckb4Ibc
-        _ => unimplemented!(),
     };
     Ok(any_consensus_state)
 }
 
+/// Query type of the ICS31 requests reading a storage slot of an Axon contract
+pub const STORAGE_QUERY_TYPE: &str = "axon/storage";
+
+/// Split the hex encoded request of a storage query, made of the 20 bytes contract
+/// address followed by the 32 bytes storage slot
+pub fn parse_storage_query(request: &str) -> Result<(H160, U256), Error> {
+    let request = hex::decode(request.trim_start_matches("0x"))
+        .map_err(|_| Error::ics31(CrossChainQueryError::parse()))?;
+    if request.len() != 52 {
+        return Err(Error::ics31(CrossChainQueryError::parse()));
+    }
+    let (address, slot) = request.split_at(20);
+    Ok((H160::from_slice(address), U256::from_big_endian(slot)))
+}
+
 pub fn ibc_event_from_ibc_handler_event(
     height: Height,
     tx_hash: [u8; 32],
@@ -453,17 +482,3 @@ pub fn ibc_event_from_ibc_handler_event(
         tx_hash,
     }))
 }
-
-pub fn generate_debug_content(
-    block: &AxonBlock,
-    state_root: &H256,
-    block_proof: &AxonProof,
-    validators: &Vec<ValidatorExtend>,
-) -> String {
-    let block = serde_json::to_string_pretty(block).unwrap();
-    let validators = serde_json::to_string_pretty(validators).unwrap();
-    let state_root = hex::encode(state_root);
-    let block_proof = serde_json::to_string_pretty(block_proof).unwrap();
-    let content = format!("[block]\n{block}\n[validators]\n{validators}\n[state_root]\n{state_root}\n[block_proof]\n{block_proof}");
-    content
-}
@@ -6,7 +6,6 @@ use ckb_types::prelude::*;
 use eth2_types::MainnetEthSpec;
 use eth_light_client_in_ckb_verification::types::{
     packed::Client as PackedClient, packed::ClientInfo as PackedClientInfo,
-    packed::ClientTypeArgs as PackedClientTypeArgs, packed::Hash as PackedHash,
     packed::ProofUpdate as PackedProofUpdate, prelude::Unpack,
 };
 use ibc_proto::ibc::apps::fee::v1::{
@@ -81,16 +80,17 @@ use super::{
 mod assembler;
 mod communication;
 mod helper;
+pub mod mmr_storage;
 pub mod sighash;
 mod signer;
 pub mod utils;
 
-#[cfg(test)]
-pub mod mock_rpc_client;
 #[cfg(not(test))]
 pub mod rpc_client;
 #[cfg(test)]
-pub use mock_rpc_client as rpc_client;
+pub mod simulator;
+#[cfg(test)]
+pub use simulator as rpc_client;
 
 #[cfg(test)]
 mod tests;
@@ -134,15 +134,7 @@ impl CkbChain {
         let minimal_updates_count = self.config.minimal_updates_count;
         let client_type_args = &self.config.client_type_args;
 
-        if let Some(type_id) = client_type_args.type_id.as_ref() {
-            let client_type_args: PackedClientTypeArgs = {
-                let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
-                PackedClientTypeArgs::new_builder()
-                    .cells_count(client_type_args.cells_count.into())
-                    .type_id(type_id)
-                    .build()
-            };
-
+        if let Some(client_type_args) = utils::packed_client_type_args(client_type_args) {
             let update_cells = self.rt.block_on(self.rpc_client.fetch_update_cells(
                 &self.config.lightclient_contract_typeargs,
                 &client_type_args,
@@ -216,18 +208,12 @@ impl CkbChain {
         mut header_updates: Vec<EthUpdate>,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
         let chain_id = self.id().to_string();
-        let client_type_args: PackedClientTypeArgs = {
-            let Some(type_id) = self.config.client_type_args.type_id.as_ref() else {
-                // TODO: better error
-                return Err(Error::other_error(
-                    "no type id in client type args".to_owned(),
-                ));
-            };
-            let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
-            PackedClientTypeArgs::new_builder()
-                .cells_count(self.config.client_type_args.cells_count.into())
-                .type_id(type_id)
-                .build()
+        let Some(client_type_args) = utils::packed_client_type_args(&self.config.client_type_args)
+        else {
+            // TODO: better error
+            return Err(Error::other_error(
+                "no type id in client type args".to_owned(),
+            ));
         };
 
         let Some(update_cells) = self.rt.block_on(self.rpc_client.fetch_update_cells(
@@ -249,11 +235,8 @@ impl CkbChain {
 
         let (mut updated_client, packed_proof_update, prev_slot_opt) =
             self.get_new_client_and_proof(&chain_id, &mut header_updates, minimal_updates_count)?;
-        updated_client = {
-            let oldest_client =
-                PackedClient::new_unchecked(update_cells.oldest.output_data.clone());
-            updated_client.as_builder().id(oldest_client.id()).build()
-        };
+        let oldest_client = PackedClient::new_unchecked(update_cells.oldest.output_data.clone());
+        updated_client = updated_client.as_builder().id(oldest_client.id()).build();
 
         let tx_assembler_address = self.tx_assembler_address()?;
         let (tx, inputs) =
@@ -261,7 +244,7 @@ impl CkbChain {
                 .block_on(self.rpc_client.assemble_update_multi_client_transaction(
                     &tx_assembler_address,
                     update_cells,
-                    updated_client,
+                    updated_client.clone(),
                     &client_type_args,
                     &self.config.lightclient_lock_typeargs,
                     &self.config.lightclient_contract_typeargs,
@@ -273,6 +256,21 @@ impl CkbChain {
             }
             err
         })?;
+        self.cached_onchain_packed_client = Some(updated_client);
+
+        // the on-chain clients verify headers from their minimal slots, the replaced
+        // oldest client has the lowest one
+        if self.config.mmr_pruning {
+            let minimal_slot: Slot = oldest_client.minimal_slot().unpack();
+            match self.storage.prune_before(minimal_slot) {
+                Ok(removed) => {
+                    tracing::debug!("pruned {removed} MMR nodes before slot {minimal_slot}")
+                }
+                Err(err) => {
+                    tracing::warn!("failed to prune MMR nodes before slot {minimal_slot}: {err}")
+                }
+            }
+        }
 
         self.print_status_log()?;
         Ok(vec![])
@@ -420,14 +418,7 @@ impl CkbChain {
 
         let mut status_log = String::new();
 
-        if let Some(type_id) = client_type_args.type_id.as_ref() {
-            let packed_client_type_args: PackedClientTypeArgs = {
-                let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
-                PackedClientTypeArgs::new_builder()
-                    .cells_count(client_type_args.cells_count.into())
-                    .type_id(type_id)
-                    .build()
-            };
+        if let Some(packed_client_type_args) = utils::packed_client_type_args(client_type_args) {
             let clients_and_info_opt = self.rt.block_on(
                 self.rpc_client
                     .fetch_clients_and_info(contract_typeid_args, &packed_client_type_args),
@@ -454,6 +445,7 @@ impl CkbChain {
             self.storage.get_tip_beacon_header_slot()?,
         ) {
             status_log += &format!("native status: [{start_slot}, {end_slot}]");
+            crate::telemetry!(beacon_mmr_size, &self.id(), self.storage.mmr_size()?);
         } else {
             status_log += "native status: NONE";
         }
@@ -641,13 +633,14 @@ impl ChainEndpoint for CkbChain {
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
         let mut clients = vec![];
-        if self.cached_onchain_packed_client.is_some() {
+        if let Some(packed_client) = self.cached_onchain_packed_client.as_ref() {
+            let maximal_slot = packed_client.maximal_slot().unpack();
             let client_state = IdentifiedAnyClientState {
                 client_id: Default::default(),
-                client_state: AnyClientState::Ckb(CkbClientState {
-                    chain_id: self.id(),
-                    latest_height: Height::default(),
-                }),
+                client_state: AnyClientState::Ckb(CkbClientState::new(
+                    self.id(),
+                    Height::from_noncosmos_height(maximal_slot),
+                )),
             };
             clients.push(client_state);
         }
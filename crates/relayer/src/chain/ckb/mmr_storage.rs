@@ -0,0 +1,320 @@
+//! Inspection and repair of the beacon header MMR stored under `data_dir`.
+//!
+//! The RocksDB database is locked by a running relayer, so these operations
+//! are only available while the relayer is stopped.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use ckb_types::prelude::*;
+use eth2_types::MainnetEthSpec;
+use eth_light_client_in_ckb_verification::mmr;
+use eth_light_client_in_ckb_verification::types::{
+    packed::{self, Client as PackedClient},
+    prelude::Unpack,
+};
+use ibc_relayer_storage::error::Error as StorageError;
+use ibc_relayer_storage::prelude::{StorageAsMMRStore, StorageReader, StorageWriter};
+use ibc_relayer_storage::snapshot::Snapshot;
+use ibc_relayer_storage::{Slot, Storage};
+use serde::Serialize;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use super::prelude::{TxAssembler, UpdateCells};
+use super::rpc_client::RpcClient;
+use super::utils::packed_client_type_args;
+use crate::config::ckb::ChainConfig as CkbChainConfig;
+use crate::error::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StorageStatus {
+    pub base_slot: Option<Slot>,
+    pub tip_slot: Option<Slot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SlotDigest {
+    pub slot: Slot,
+    /// Hex of the packed header digest, `None` if it is missing from the storage
+    pub digest: Option<String>,
+}
+
+/// Comparison between the MMR root of the on-chain multi-client and the stored one
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RootCheck {
+    pub onchain_minimal_slot: Slot,
+    pub onchain_maximal_slot: Slot,
+    pub onchain_root: String,
+    /// `None` if the storage doesn't cover the on-chain slots
+    pub stored_root: Option<String>,
+}
+
+impl RootCheck {
+    pub fn is_consistent(&self) -> bool {
+        self.stored_root.as_ref() == Some(&self.onchain_root)
+    }
+}
+
+pub struct MmrStorage {
+    // TODO the spec of Ethereum should be selectable, same as `CkbChain`.
+    storage: Storage<MainnetEthSpec>,
+}
+
+impl MmrStorage {
+    pub fn open(config: &CkbChainConfig) -> Result<Self, Error> {
+        let storage = Storage::new(&config.data_dir)?;
+        Ok(Self { storage })
+    }
+
+    pub fn status(&self) -> Result<StorageStatus, Error> {
+        Ok(StorageStatus {
+            base_slot: self.storage.get_base_beacon_header_slot()?,
+            tip_slot: self.storage.get_tip_beacon_header_slot()?,
+        })
+    }
+
+    /// Header digests of the slots from `start` to `end` (inclusive), `end` is clamped
+    /// to the tip slot since the digests after it are leftovers of a rollback
+    pub fn digests(&self, start: Slot, end: Slot) -> Result<Vec<SlotDigest>, Error> {
+        let base_slot = self.base_slot()?;
+        let tip_slot = self
+            .storage
+            .get_tip_beacon_header_slot()?
+            .unwrap_or(base_slot);
+        if start < base_slot || start > tip_slot {
+            return Err(Error::other_error(format!(
+                "slot {start} is out of the stored range [{base_slot}, {tip_slot}]"
+            )));
+        }
+        (start..=end.min(tip_slot))
+            .map(|slot| {
+                let position = mmr::lib::leaf_index_to_pos(slot - base_slot);
+                let digest = self
+                    .storage
+                    .get_beacon_header_digest(position)?
+                    .map(|digest| hex::encode(digest.as_slice()));
+                Ok(SlotDigest { slot, digest })
+            })
+            .collect()
+    }
+
+    /// Compare the stored MMR root with the root of the latest on-chain multi-client
+    pub fn check_onchain_root(
+        &self,
+        rt: &TokioRuntime,
+        config: &CkbChainConfig,
+    ) -> Result<RootCheck, Error> {
+        let client = fetch_latest_onchain_client(rt, config)?;
+        let onchain_minimal_slot = client.minimal_slot().unpack();
+        let onchain_maximal_slot = client.maximal_slot().unpack();
+
+        let StorageStatus {
+            base_slot,
+            tip_slot,
+        } = self.status()?;
+        let stored_root = match (base_slot, tip_slot) {
+            (Some(base), Some(tip))
+                if base == onchain_minimal_slot && tip >= onchain_maximal_slot =>
+            {
+                let root = self
+                    .storage
+                    .chain_root_mmr(onchain_maximal_slot)?
+                    .get_root()
+                    .map_err(StorageError::from)?;
+                Some(hex::encode(root.as_slice()))
+            }
+            _ => None,
+        };
+
+        Ok(RootCheck {
+            onchain_minimal_slot,
+            onchain_maximal_slot,
+            onchain_root: hex::encode(client.headers_mmr_root().as_slice()),
+            stored_root,
+        })
+    }
+
+    /// Drop all the slots after `slot`, the digests will be overwritten by the next updates
+    pub fn rollback_to(&self, slot: Slot) -> Result<(), Error> {
+        let StorageStatus {
+            base_slot,
+            tip_slot,
+        } = self.status()?;
+        match (base_slot, tip_slot) {
+            (Some(base), Some(tip)) if base <= slot && slot <= tip => {
+                Ok(self.storage.rollback_to(Some(slot))?)
+            }
+            (Some(base), Some(tip)) => Err(Error::other_error(format!(
+                "slot {slot} is out of the stored range [{base}, {tip}]"
+            ))),
+            _ => Err(Error::other_error("the storage is empty".to_owned())),
+        }
+    }
+
+    /// Wipe the stored MMR and restart it from the checkpoint `slot` whose header
+    /// digest is the packed `digest`
+    pub fn reinitialize(&self, slot: Slot, digest: &[u8]) -> Result<(), Error> {
+        let digest = packed::HeaderDigest::from_slice(digest)
+            .map_err(|e| Error::other_error(format!("invalid header digest: {e}")))?;
+        self.storage.rollback_to(None)?;
+        self.storage.initialize_with(slot, digest)?;
+        self.storage.put_tip_beacon_header_slot(slot)?;
+        Ok(())
+    }
+
+    /// Remove the MMR nodes which are only needed to prove the headers before `slot`,
+    /// returns the number of removed nodes
+    pub fn prune_before(&self, slot: Slot) -> Result<u64, Error> {
+        Ok(self.storage.prune_before(slot)?)
+    }
+
+    /// Write a snapshot of the stored MMR into `path`, returns the status of the snapshot
+    pub fn export_snapshot(&self, path: &Path) -> Result<StorageStatus, Error> {
+        let snapshot = Snapshot::export(&self.storage)?;
+        let mut writer = BufWriter::new(File::create(path).map_err(Error::io)?);
+        snapshot.write_to(&mut writer)?;
+        Ok(StorageStatus {
+            base_slot: Some(snapshot.base_slot),
+            tip_slot: Some(snapshot.tip_slot),
+        })
+    }
+
+    /// Seed the empty storage with the snapshot at `path`
+    pub fn import_snapshot(&self, path: &Path) -> Result<StorageStatus, Error> {
+        let mut reader = BufReader::new(File::open(path).map_err(Error::io)?);
+        let snapshot = Snapshot::read_from(&mut reader)?;
+        snapshot.import(&self.storage)?;
+        self.status()
+    }
+
+    fn base_slot(&self) -> Result<Slot, Error> {
+        self.storage
+            .get_base_beacon_header_slot()?
+            .ok_or_else(|| Error::other_error("the storage is empty".to_owned()))
+    }
+}
+
+fn fetch_latest_onchain_client(
+    rt: &TokioRuntime,
+    config: &CkbChainConfig,
+) -> Result<PackedClient, Error> {
+    let Some(client_type_args) = packed_client_type_args(&config.client_type_args) else {
+        return Err(Error::other_error(
+            "`client_type_args.type_id` is not configured".to_owned(),
+        ));
+    };
+
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    let update_cells = rt.block_on(
+        rpc_client.fetch_update_cells(&config.lightclient_contract_typeargs, &client_type_args),
+    )?;
+    match update_cells {
+        Some(UpdateCells { latest, .. }) => Ok(PackedClient::new_unchecked(latest.output_data)),
+        None => Err(Error::other_error(
+            "no multi-client cells found for config".to_owned(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reinitialize_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MmrStorage {
+            storage: Storage::new(dir.path()).unwrap(),
+        };
+        let digest = packed::HeaderDigest::default();
+
+        storage.reinitialize(100, digest.as_slice()).unwrap();
+        assert_eq!(
+            storage.status().unwrap(),
+            StorageStatus {
+                base_slot: Some(100),
+                tip_slot: Some(100),
+            }
+        );
+        assert_eq!(
+            storage.digests(100, 101).unwrap(),
+            [SlotDigest {
+                slot: 100,
+                digest: Some(hex::encode(digest.as_slice())),
+            },]
+        );
+        assert_eq!(storage.digests(100, u64::MAX).unwrap().len(), 1);
+        assert!(storage.digests(99, 100).is_err());
+        assert!(storage.digests(101, 101).is_err());
+        assert!(storage.rollback_to(101).is_err());
+        assert!(storage.reinitialize(100, &[0]).is_err());
+    }
+
+    #[test]
+    fn test_prune_before_minimal_slot() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MmrStorage {
+            storage: Storage::new(dir.path()).unwrap(),
+        };
+        let digest = packed::HeaderDigest::default();
+        storage.reinitialize(100, digest.as_slice()).unwrap();
+        let mut mmr = storage.storage.chain_root_mmr(100).unwrap();
+        for _ in 101..=107 {
+            mmr.push(digest.clone()).unwrap();
+        }
+        mmr.commit().unwrap();
+        storage.storage.put_tip_beacon_header_slot(107).unwrap();
+
+        // the subtree of the headers from 100 to 103 is only kept as a peak
+        assert_eq!(storage.prune_before(104).unwrap(), 6);
+        assert_eq!(storage.prune_before(104).unwrap(), 0);
+        // the next pruning starts from the watermark
+        assert_eq!(storage.prune_before(106).unwrap(), 2);
+        assert!(storage.prune_before(108).is_err());
+
+        let proof = |slot: Slot| {
+            let position = mmr::lib::leaf_index_to_pos(slot - 100);
+            storage
+                .storage
+                .chain_root_mmr(107)
+                .unwrap()
+                .gen_proof(vec![position])
+        };
+        assert!(proof(106).is_ok());
+        assert!(proof(107).is_ok());
+        assert!(proof(105).is_err());
+
+        // the headers before the watermark can't be re-appended
+        assert!(storage.rollback_to(105).is_err());
+        storage.rollback_to(106).unwrap();
+        assert_eq!(
+            storage.storage.get_tip_beacon_header_slot().unwrap(),
+            Some(106)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_into_empty_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MmrStorage {
+            storage: Storage::new(dir.path().join("source")).unwrap(),
+        };
+        storage
+            .reinitialize(100, packed::HeaderDigest::default().as_slice())
+            .unwrap();
+        let snapshot_path = dir.path().join("snapshot");
+        let status = storage.export_snapshot(&snapshot_path).unwrap();
+
+        let seeded = MmrStorage {
+            storage: Storage::new(dir.path().join("seeded")).unwrap(),
+        };
+        assert_eq!(seeded.import_snapshot(&snapshot_path).unwrap(), status);
+        assert_eq!(
+            seeded.digests(100, 100).unwrap(),
+            storage.digests(100, 100).unwrap()
+        );
+        // the source storage is not empty
+        assert!(storage.import_snapshot(&snapshot_path).is_err());
+    }
+}
@@ -0,0 +1,681 @@
+//! An in-memory CKB chain standing in for the RPC client in tests.
+//!
+//! Sent transactions are resolved against the live cells, checked by the
+//! registered [`ScriptVerifier`]s and committed in a block of their own,
+//! followed by `confirmations` empty blocks, so that tests run deterministically
+//! without a node.
+
+#![allow(dead_code)]
+
+use ckb_jsonrpc_types::{
+    BlockNumber, BlockView, CellData, CellInfo, CellWithStatus, ChainInfo, HeaderView, JsonBytes,
+    MerkleProof as JsonMerkleProof, OutPoint, OutputsValidator, RawTxPool, ResponseFormat,
+    Transaction, TransactionAndWitnessProof, TransactionView, TransactionWithStatusResponse,
+    TxPoolIds, TxPoolInfo, TxStatus,
+};
+use ckb_sdk::rpc::ckb_indexer::{Cell, Pagination, ScriptSearchMode, ScriptType, SearchKey};
+use ckb_types::{
+    bytes::Bytes,
+    core, packed,
+    prelude::*,
+    utilities::{merkle_root, CBMT},
+    H256,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tendermint_rpc::Url;
+
+use super::prelude::{CkbReader, CkbWriter, Response as Rpc};
+use crate::error::Error;
+
+const GENESIS_TIMESTAMP: u64 = 1_600_000_000_000;
+const BLOCK_INTERVAL: u64 = 8_000;
+const DEFAULT_CONFIRMATIONS: u64 = 1;
+
+/// Checks the scripts of a transaction before the simulator commits it,
+/// `inputs` are the resolved input cells along with their data
+pub trait ScriptVerifier: Send + Sync {
+    fn verify(
+        &self,
+        tx: &core::TransactionView,
+        inputs: &[(packed::CellOutput, Bytes)],
+    ) -> Result<(), String>;
+}
+
+#[derive(Clone)]
+pub struct RpcClient {
+    data: Arc<RwLock<Simulator>>,
+}
+
+struct Simulator {
+    chain_info: Option<String>,
+
+    // in the order of creation, which is the order of indexer queries
+    live_cells: Vec<Cell>,
+
+    blocks: Vec<core::BlockView>,
+
+    // block number of each committed transaction
+    committed: HashMap<H256, u64>,
+
+    transactions: Vec<Transaction>,
+
+    confirmations: u64,
+
+    verifiers: Vec<Arc<dyn ScriptVerifier>>,
+}
+
+impl Simulator {
+    fn new() -> Self {
+        let mut simulator = Self {
+            chain_info: None,
+            live_cells: vec![],
+            blocks: vec![],
+            committed: HashMap::new(),
+            transactions: vec![],
+            confirmations: DEFAULT_CONFIRMATIONS,
+            verifiers: vec![],
+        };
+        simulator.produce_block(vec![]);
+        simulator
+    }
+
+    fn tip(&self) -> &core::BlockView {
+        self.blocks.last().expect("genesis block")
+    }
+
+    fn produce_block(&mut self, transactions: Vec<core::TransactionView>) -> u64 {
+        let (number, parent_hash) = match self.blocks.last() {
+            Some(parent) => (parent.number() + 1, parent.hash()),
+            None => (0, Default::default()),
+        };
+        let tx_hashes = transactions.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+        let witness_hashes = transactions
+            .iter()
+            .map(|tx| tx.witness_hash())
+            .collect::<Vec<_>>();
+        let transactions_root =
+            merkle_root(&[merkle_root(&tx_hashes), merkle_root(&witness_hashes)]);
+        let block = core::BlockBuilder::default()
+            .number(number.pack())
+            .parent_hash(parent_hash)
+            .timestamp((GENESIS_TIMESTAMP + number * BLOCK_INTERVAL).pack())
+            .transactions_root(transactions_root)
+            .transactions(transactions)
+            .build();
+        self.blocks.push(block);
+        number
+    }
+
+    fn block_by_hash(&self, hash: &H256) -> Option<&core::BlockView> {
+        let hash = hash.pack();
+        self.blocks.iter().find(|block| block.hash() == hash)
+    }
+
+    fn transaction(&self, hash: &H256) -> Option<(core::TransactionView, &core::BlockView)> {
+        let block = &self.blocks[*self.committed.get(hash)? as usize];
+        let hash = hash.pack();
+        block
+            .transactions()
+            .into_iter()
+            .find(|tx| tx.hash() == hash)
+            .map(|tx| (tx, block))
+    }
+
+    fn live_cell_index(&self, out_point: &packed::OutPoint) -> Option<usize> {
+        let out_point = OutPoint::from(out_point.clone());
+        self.live_cells
+            .iter()
+            .position(|cell| cell.out_point == out_point)
+    }
+
+    fn commit(&mut self, tx: core::TransactionView) -> Result<H256, Error> {
+        let mut input_indices = vec![];
+        let mut inputs = vec![];
+        for input in tx.inputs() {
+            let out_point = input.previous_output();
+            let index = self.live_cell_index(&out_point).ok_or_else(|| {
+                Error::rpc_response(format!(
+                    "UnknowOutpoint: {:#x}",
+                    OutPoint::from(out_point.clone()).tx_hash
+                ))
+            })?;
+            let cell = &self.live_cells[index];
+            let data = cell
+                .output_data
+                .as_ref()
+                .map(|data| data.clone().into_bytes())
+                .unwrap_or_default();
+            input_indices.push(index);
+            inputs.push((packed::CellOutput::from(cell.output.clone()), data));
+        }
+
+        let inputs_capacity = inputs
+            .iter()
+            .map(|(output, _)| Unpack::<u64>::unpack(&output.capacity()))
+            .sum::<u64>();
+        let outputs_capacity = tx
+            .outputs_capacity()
+            .map_err(|e| Error::send_tx(e.to_string()))?
+            .as_u64();
+        if !inputs.is_empty() && outputs_capacity > inputs_capacity {
+            return Err(Error::send_tx(format!(
+                "InsufficientCellCapacity: outputs {outputs_capacity} > inputs {inputs_capacity}"
+            )));
+        }
+        for verifier in &self.verifiers {
+            verifier
+                .verify(&tx, &inputs)
+                .map_err(|e| Error::send_tx(format!("script verification failed: {e}")))?;
+        }
+
+        input_indices.sort_unstable();
+        for index in input_indices.into_iter().rev() {
+            self.live_cells.remove(index);
+        }
+        let hash: H256 = tx.hash().unpack();
+        let block_number = self.tip().number() + 1;
+        for (index, (output, data)) in tx.outputs_with_data_iter().enumerate() {
+            let out_point = packed::OutPoint::new_builder()
+                .tx_hash(tx.hash())
+                .index((index as u32).pack())
+                .build();
+            self.live_cells.push(Cell {
+                output: output.into(),
+                output_data: Some(JsonBytes::from_bytes(data)),
+                out_point: out_point.into(),
+                block_number: block_number.into(),
+                tx_index: 0u32.into(),
+            });
+        }
+        self.transactions.push(tx.data().into());
+        self.committed.insert(hash.clone(), block_number);
+        self.produce_block(vec![tx]);
+        for _ in 0..self.confirmations {
+            self.produce_block(vec![]);
+        }
+        Ok(hash)
+    }
+}
+
+fn script_matches(script: &packed::Script, expected: &packed::Script, exact: bool) -> bool {
+    let args = script.args().raw_data();
+    let expected_args = expected.args().raw_data();
+    script.code_hash() == expected.code_hash()
+        && script.hash_type().as_slice() == expected.hash_type().as_slice()
+        && if exact {
+            args == expected_args
+        } else {
+            args.starts_with(&expected_args)
+        }
+}
+
+fn cell_matches(cell: &Cell, search_key: &SearchKey) -> bool {
+    let output = packed::CellOutput::from(cell.output.clone());
+    let (primary, secondary) = match search_key.script_type {
+        ScriptType::Lock => (Some(output.lock()), output.type_().to_opt()),
+        ScriptType::Type => (output.type_().to_opt(), Some(output.lock())),
+    };
+    let exact = matches!(search_key.script_search_mode, Some(ScriptSearchMode::Exact));
+    let expected = packed::Script::from(search_key.script.clone());
+    if !primary.map_or(false, |script| script_matches(&script, &expected, exact)) {
+        return false;
+    }
+    match search_key
+        .filter
+        .as_ref()
+        .and_then(|filter| filter.script.clone())
+    {
+        Some(filter_script) => secondary.map_or(false, |script| {
+            script_matches(&script, &filter_script.into(), false)
+        }),
+        None => true,
+    }
+}
+
+impl RpcClient {
+    pub fn new(_ckb_uri: &Url, _indexer_uri: &Url) -> Self {
+        Self {
+            data: Arc::new(RwLock::new(Simulator::new())),
+        }
+    }
+
+    pub fn set_blockchain_info(&self, chain_info: Option<&str>) {
+        self.data.write().unwrap().chain_info = chain_info.map(ToOwned::to_owned);
+    }
+
+    /// Number of empty blocks produced after the block of each committed transaction
+    pub fn set_confirmations(&self, confirmations: u64) {
+        self.data.write().unwrap().confirmations = confirmations;
+    }
+
+    pub fn add_verifier(&self, verifier: impl ScriptVerifier + 'static) {
+        self.data
+            .write()
+            .unwrap()
+            .verifiers
+            .push(Arc::new(verifier));
+    }
+
+    /// Adds a live cell which isn't created by any committed transaction,
+    /// e.g. the cells of deployed contracts or the capacity of the relayer
+    pub fn add_cell(&self, cell: Cell) {
+        self.data.write().unwrap().live_cells.push(cell);
+    }
+
+    pub fn clear_cells(&self) {
+        self.data.write().unwrap().live_cells.clear();
+    }
+
+    pub fn produce_empty_blocks(&self, count: u64) {
+        let mut data = self.data.write().unwrap();
+        for _ in 0..count {
+            data.produce_block(vec![]);
+        }
+    }
+
+    pub fn tip_block_number(&self) -> u64 {
+        self.data.read().unwrap().tip().number()
+    }
+
+    pub fn get_transaction_by_index(&self, index: usize) -> Option<Transaction> {
+        self.data.read().unwrap().transactions.get(index).cloned()
+    }
+
+    pub fn get_transactions_len(&self) -> usize {
+        self.data.read().unwrap().transactions.len()
+    }
+}
+
+impl CkbReader for RpcClient {
+    fn get_blockchain_info(&self) -> Rpc<ChainInfo> {
+        let resp = if let Some(ref chain_info) = self.data.read().unwrap().chain_info {
+            Ok(serde_json::from_str(chain_info).unwrap())
+        } else {
+            Err(Error::rpc_response("data is not set".to_owned()))
+        };
+        Box::pin(async { resp })
+    }
+
+    fn get_block_by_number(&self, number: BlockNumber) -> Rpc<BlockView> {
+        let number: u64 = number.into();
+        let resp = self
+            .data
+            .read()
+            .unwrap()
+            .blocks
+            .get(number as usize)
+            .cloned()
+            .map(Into::into)
+            .ok_or_else(|| Error::rpc_response(format!("block #{number} not found")));
+        Box::pin(async { resp })
+    }
+
+    fn get_block(&self, hash: &H256) -> Rpc<BlockView> {
+        let resp = self
+            .data
+            .read()
+            .unwrap()
+            .block_by_hash(hash)
+            .cloned()
+            .map(Into::into)
+            .ok_or_else(|| Error::rpc_response(format!("block {hash:#x} not found")));
+        Box::pin(async { resp })
+    }
+
+    fn get_tip_header(&self) -> Rpc<HeaderView> {
+        let resp = self.data.read().unwrap().tip().header().into();
+        Box::pin(async { Ok(resp) })
+    }
+
+    fn get_header(&self, hash: &H256) -> Rpc<Option<HeaderView>> {
+        let resp = self
+            .data
+            .read()
+            .unwrap()
+            .block_by_hash(hash)
+            .map(|block| block.header().into());
+        Box::pin(async { Ok(resp) })
+    }
+
+    fn get_transaction(&self, hash: &H256) -> Rpc<Option<TransactionWithStatusResponse>> {
+        let resp = self
+            .data
+            .read()
+            .unwrap()
+            .transaction(hash)
+            .map(|(tx, block)| TransactionWithStatusResponse {
+                transaction: Some(ResponseFormat::json(TransactionView::from(tx))),
+                tx_status: TxStatus::committed(block.hash().unpack()),
+                cycles: None,
+                time_added_to_pool: None,
+            });
+        Box::pin(async { Ok(resp) })
+    }
+
+    fn get_live_cell(&self, out_point: &OutPoint, with_data: bool) -> Rpc<CellWithStatus> {
+        let data = self.data.read().unwrap();
+        let resp = match data.live_cell_index(&out_point.clone().into()) {
+            Some(index) => {
+                let cell = &data.live_cells[index];
+                let content = cell.output_data.clone().unwrap_or_default();
+                let hash = packed::CellOutput::calc_data_hash(content.as_bytes()).unpack();
+                CellWithStatus {
+                    cell: Some(CellInfo {
+                        output: cell.output.clone(),
+                        data: with_data.then_some(CellData { content, hash }),
+                    }),
+                    status: "live".to_owned(),
+                }
+            }
+            None => CellWithStatus {
+                cell: None,
+                status: "unknown".to_owned(),
+            },
+        };
+        Box::pin(async { Ok(resp) })
+    }
+
+    fn get_txs_by_hashes(
+        &self,
+        hashes: Vec<H256>,
+    ) -> Rpc<Vec<Option<TransactionWithStatusResponse>>> {
+        let requests = hashes
+            .iter()
+            .map(|hash| self.get_transaction(hash))
+            .collect::<Vec<_>>();
+        Box::pin(async move {
+            let mut responses = vec![];
+            for request in requests {
+                responses.push(request.await?);
+            }
+            Ok(responses)
+        })
+    }
+
+    fn get_transaction_and_witness_proof(
+        &self,
+        tx_hashes: Vec<H256>,
+        block_hash: H256,
+    ) -> Rpc<TransactionAndWitnessProof> {
+        let data = self.data.read().unwrap();
+        let resp = data
+            .block_by_hash(&block_hash)
+            .ok_or_else(|| Error::rpc_response(format!("block {block_hash:#x} not found")))
+            .and_then(|block| {
+                let indices = tx_hashes
+                    .iter()
+                    .map(|hash| {
+                        let hash = hash.pack();
+                        block
+                            .tx_hashes()
+                            .iter()
+                            .position(|tx_hash| tx_hash == &hash)
+                            .map(|index| index as u32)
+                            .ok_or_else(|| {
+                                Error::rpc_response(format!(
+                                    "transaction {hash} not found in block {block_hash:#x}"
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let build_proof = |leaves: &[packed::Byte32]| {
+                    let proof = CBMT::build_merkle_proof(leaves, &indices)
+                        .expect("indices of block transactions");
+                    JsonMerkleProof {
+                        indices: proof
+                            .indices()
+                            .iter()
+                            .map(|index| (*index).into())
+                            .collect(),
+                        lemmas: proof.lemmas().iter().map(Unpack::unpack).collect(),
+                    }
+                };
+                Ok(TransactionAndWitnessProof {
+                    block_hash: block_hash.clone(),
+                    transactions_proof: build_proof(block.tx_hashes()),
+                    witnesses_proof: build_proof(block.tx_witness_hashes()),
+                })
+            });
+        Box::pin(async { resp })
+    }
+
+    fn fetch_live_cells(
+        &self,
+        search_key: SearchKey,
+        limit: u32,
+        cursor: Option<JsonBytes>,
+    ) -> Rpc<Pagination<Cell>> {
+        let start = cursor
+            .filter(|cursor| cursor.len() == 4)
+            .map(|cursor| {
+                let mut u32_be_bytes = [0u8; 4];
+                u32_be_bytes.copy_from_slice(cursor.as_bytes());
+                u32::from_be_bytes(u32_be_bytes) as usize
+            })
+            .unwrap_or(0);
+        let objects = self
+            .data
+            .read()
+            .unwrap()
+            .live_cells
+            .iter()
+            .filter(|cell| cell_matches(cell, &search_key))
+            .skip(start)
+            .take(limit as usize)
+            .cloned()
+            .collect::<Vec<_>>();
+        // the indexer returns an empty cursor once all cells are fetched
+        let last_cursor = if objects.is_empty() {
+            JsonBytes::default()
+        } else {
+            let next = (start + objects.len()) as u32;
+            JsonBytes::from_vec(next.to_be_bytes().to_vec())
+        };
+        Box::pin(async {
+            Ok(Pagination {
+                objects,
+                last_cursor,
+            })
+        })
+    }
+
+    fn get_raw_tx_pool(&self, _verbose: bool) -> Rpc<RawTxPool> {
+        // transactions are committed as soon as they are sent
+        let resp = RawTxPool::Ids(TxPoolIds {
+            pending: vec![],
+            proposed: vec![],
+        });
+        Box::pin(async { Ok(resp) })
+    }
+
+    fn tx_pool_info(&self) -> Rpc<TxPoolInfo> {
+        // the pool is always empty, the fields are parsed to stay in line with the RPC types
+        let tx_pool_info = serde_json::json!({
+            "tip_hash": H256::default(),
+            "tip_number": "0x0",
+            "pending": "0x0",
+            "proposed": "0x0",
+            "orphan": "0x0",
+            "total_tx_size": "0x0",
+            "total_tx_cycles": "0x0",
+            "min_fee_rate": "0x3e8",
+            "min_rbf_rate": "0x5dc",
+            "last_txs_updated_at": "0x0",
+            "tx_size_limit": "0x7d000",
+            "max_tx_pool_size": "0xaba9500",
+        });
+        let resp =
+            serde_json::from_value(tx_pool_info).map_err(|e| Error::rpc_response(e.to_string()));
+        Box::pin(async { resp })
+    }
+}
+
+impl CkbWriter for RpcClient {
+    fn send_transaction(
+        &self,
+        tx: &Transaction,
+        _outputs_validator: Option<OutputsValidator>,
+    ) -> Rpc<H256> {
+        let packed_tx: packed::Transaction = tx.clone().into();
+        let resp = self.data.write().unwrap().commit(packed_tx.into_view());
+        Box::pin(async { resp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ckb_sdk::traits::PrimaryScriptType;
+    use ckb_types::{core::Capacity, utilities::MerkleProof};
+
+    use super::*;
+    use crate::chain::ckb::prelude::CellSearcher;
+
+    fn lock_script(args: u8) -> packed::Script {
+        packed::Script::new_builder()
+            .code_hash([1u8; 32].pack())
+            .hash_type(core::ScriptHashType::Type.into())
+            .args(vec![args; 20].pack())
+            .build()
+    }
+
+    fn genesis_cell(lock: packed::Script, capacity: u64) -> Cell {
+        let output = packed::CellOutput::new_builder()
+            .lock(lock)
+            .capacity(Capacity::bytes(capacity as usize).unwrap().pack())
+            .build();
+        Cell {
+            output: output.into(),
+            output_data: Some(Default::default()),
+            out_point: packed::OutPoint::new_builder()
+                .tx_hash([9u8; 32].pack())
+                .build()
+                .into(),
+            block_number: 0u64.into(),
+            tx_index: 0u32.into(),
+        }
+    }
+
+    fn transfer(from: &Cell, to: packed::Script, capacity: u64) -> core::TransactionView {
+        let output = packed::CellOutput::new_builder()
+            .lock(to)
+            .capacity(Capacity::bytes(capacity as usize).unwrap().pack())
+            .build();
+        core::TransactionBuilder::default()
+            .input(
+                packed::CellInput::new_builder()
+                    .previous_output(from.out_point.clone().into())
+                    .build(),
+            )
+            .output(output)
+            .output_data(Bytes::new().pack())
+            .witness(Bytes::from_static(b"witness").pack())
+            .build()
+    }
+
+    fn url() -> Url {
+        "http://127.0.0.1:8114".parse().unwrap()
+    }
+
+    #[test]
+    fn test_commit_consumes_inputs_and_produces_blocks() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let client = RpcClient::new(&url(), &url());
+        let cell = genesis_cell(lock_script(1), 1000);
+        client.add_cell(cell.clone());
+
+        let tx = transfer(&cell, lock_script(2), 900);
+        let hash = rt
+            .block_on(client.send_transaction(&tx.data().into(), None))
+            .unwrap();
+        assert_eq!(client.tip_block_number(), 1 + DEFAULT_CONFIRMATIONS);
+
+        let committed = rt.block_on(client.get_transaction(&hash)).unwrap().unwrap();
+        let block = rt
+            .block_on(client.get_block(&committed.tx_status.block_hash.unwrap()))
+            .unwrap();
+        assert_eq!(u64::from(block.header.inner.number), 1);
+
+        let spent = rt
+            .block_on(client.search_cell(&lock_script(1), PrimaryScriptType::Lock))
+            .unwrap();
+        assert!(spent.is_none());
+        let created = rt
+            .block_on(client.search_cell(&lock_script(2), PrimaryScriptType::Lock))
+            .unwrap()
+            .unwrap();
+        assert_eq!(created.out_point.tx_hash().unpack(), hash);
+
+        // the input is already consumed
+        let err = rt
+            .block_on(client.send_transaction(&tx.data().into(), None))
+            .unwrap_err();
+        assert!(err.to_string().contains("UnknowOutpoint"));
+    }
+
+    #[test]
+    fn test_tx_pool_is_empty() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let client = RpcClient::new(&url(), &url());
+        let info = rt.block_on(client.tx_pool_info()).unwrap();
+        assert_eq!(u64::from(info.pending), 0);
+        assert_eq!(u64::from(info.proposed), 0);
+    }
+
+    #[test]
+    fn test_reject_insufficient_capacity() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let client = RpcClient::new(&url(), &url());
+        let cell = genesis_cell(lock_script(1), 1000);
+        client.add_cell(cell.clone());
+
+        let tx = transfer(&cell, lock_script(2), 1001);
+        assert!(rt
+            .block_on(client.send_transaction(&tx.data().into(), None))
+            .is_err());
+        assert_eq!(client.tip_block_number(), 0);
+    }
+
+    #[test]
+    fn test_transaction_and_witness_proof() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let client = RpcClient::new(&url(), &url());
+        let cell = genesis_cell(lock_script(1), 1000);
+        client.add_cell(cell.clone());
+
+        let tx = transfer(&cell, lock_script(2), 900);
+        let hash = rt
+            .block_on(client.send_transaction(&tx.data().into(), None))
+            .unwrap();
+        let block_hash = rt
+            .block_on(client.get_transaction(&hash))
+            .unwrap()
+            .unwrap()
+            .tx_status
+            .block_hash
+            .unwrap();
+        let header = rt
+            .block_on(client.get_header(&block_hash))
+            .unwrap()
+            .unwrap();
+        let proof = rt
+            .block_on(client.get_transaction_and_witness_proof(vec![hash], block_hash))
+            .unwrap();
+
+        let root = |proof: JsonMerkleProof, leaf: packed::Byte32| {
+            MerkleProof::new(
+                proof.indices.into_iter().map(Into::into).collect(),
+                proof.lemmas.into_iter().map(|lemma| lemma.pack()).collect(),
+            )
+            .root(&[leaf])
+            .unwrap()
+        };
+        let transactions_root = merkle_root(&[
+            root(proof.transactions_proof, tx.hash()),
+            root(proof.witnesses_proof, tx.witness_hash()),
+        ]);
+        assert_eq!(transactions_root.unpack(), header.inner.transactions_root);
+    }
+}
@@ -1,11 +1,6 @@
 use std::{fs, str::FromStr, sync::Arc};
 
-use ckb_sdk::{
-    constants::TYPE_ID_CODE_HASH,
-    rpc::ckb_indexer::{Cell, SearchKey},
-    traits::{CellQueryOptions, PrimaryScriptType},
-    NetworkType,
-};
+use ckb_sdk::{constants::TYPE_ID_CODE_HASH, rpc::ckb_indexer::Cell, NetworkType};
 use ckb_types::{
     core::{BlockNumber, Capacity, ScriptHashType},
     h256, packed,
@@ -118,6 +113,7 @@ fn test_create_eth_multi_client(case_id: usize) {
             minimal_updates_count: 1,
             key_name: "ckb-chain-test".to_string(),
             data_dir: tmp_dir.path().to_path_buf(),
+            mmr_pruning: false,
         };
         let config = ChainConfig::Ckb(ckb_config);
         let rt = Arc::new(TokioRuntime::new().unwrap());
@@ -170,8 +166,7 @@ fn test_create_eth_multi_client(case_id: usize) {
             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
             .unwrap();
         let cell = random_cell(1001, output, Default::default());
-        let key: SearchKey = CellQueryOptions::new(contract, PrimaryScriptType::Type).into();
-        rpc_client.add_cell(&key, cell);
+        rpc_client.add_cell(cell);
     }
 
     {
@@ -182,8 +177,7 @@ fn test_create_eth_multi_client(case_id: usize) {
             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
             .unwrap();
         let cell = random_cell(1002, output, Default::default());
-        let key: SearchKey = CellQueryOptions::new(lock_script, PrimaryScriptType::Lock).into();
-        rpc_client.add_cell(&key, cell);
+        rpc_client.add_cell(cell);
     }
 
     let updates_part_1 = load_updates_from_file(&testdata_dir, "headers_part_1.json");
@@ -262,8 +256,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
 //             .unwrap();
 //         let cell = random_cell(1001, output, Default::default());
-//         let key: SearchKey = CellQueryOptions::new(contract, PrimaryScriptType::Type).into();
-//         rpc_client.add_cell(&key, cell);
+//         rpc_client.add_cell(cell);
 //     }
 
 //     {
@@ -274,8 +267,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
 //             .unwrap();
 //         let cell = random_cell(1002, output, Default::default());
-//         let key: SearchKey = CellQueryOptions::new(lock_script, PrimaryScriptType::Lock).into();
-//         rpc_client.add_cell(&key, cell);
+//         rpc_client.add_cell(cell);
 //     }
 
 //     let updates_part_1 = load_updates_from_file(&testdata_dir, "headers_part_1.json");
@@ -312,8 +304,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
 //             .unwrap();
 //         let cell = random_cell(1003, output, Default::default());
-//         let key: SearchKey = CellQueryOptions::new(contract, PrimaryScriptType::Type).into();
-//         rpc_client.add_cell(&key, cell);
+//         rpc_client.add_cell(cell);
 //     }
 
 //     {
@@ -324,8 +315,7 @@ fn test_create_eth_multi_client(case_id: usize) {
 //             .build_exact_capacity(Capacity::bytes(100_000).unwrap())
 //             .unwrap();
 //         let cell = random_cell(1004, output, Default::default());
-//         let key: SearchKey = CellQueryOptions::new(lock_script, PrimaryScriptType::Lock).into();
-//         rpc_client.add_cell(&key, cell);
+//         rpc_client.add_cell(cell);
 //     }
 
 //     {
@@ -346,11 +336,10 @@ fn test_create_eth_multi_client(case_id: usize) {
 //             .hash_type(ScriptHashType::Type.into())
 //             .args(client_as_type_args.pack())
 //             .build();
-//         let key: SearchKey = CellQueryOptions::new(contract, PrimaryScriptType::Type).into();
 //         let output: packed::CellOutput = tx_create_client.outputs[0].clone().into();
 //         let output_data: Vec<u8> = tx_create_client.outputs_data[0].as_bytes().to_vec();
 //         let cell = random_cell(1005, output, output_data);
-//         rpc_client.add_cell(&key, cell);
+//         rpc_client.add_cell(cell);
 //     }
 
 //     let updates_part_2 = load_updates_from_file(&testdata_dir, "headers_part_2.json");
@@ -5,7 +5,10 @@ use eth2_types::EthSpec;
 use eth_light_client_in_ckb_verification::mmr::{self, HeaderWithCache};
 use eth_light_client_in_ckb_verification::types::{
     core::{Client as EthLcClient, Header as EthLcHeader},
-    packed::{self, Client as PackedClient, ProofUpdate as PackedProofUpdate},
+    packed::{
+        self, Client as PackedClient, ClientTypeArgs as PackedClientTypeArgs, Hash as PackedHash,
+        ProofUpdate as PackedProofUpdate,
+    },
     prelude::*,
 };
 use ibc_relayer_storage::{
@@ -20,6 +23,7 @@ use tendermint_light_client::errors::Error as LightClientError;
 use tracing::debug;
 
 use crate::chain::ckb::communication::CkbReader;
+use crate::config::ckb::ClientTypeArgs;
 use crate::error::Error;
 
 use super::rpc_client::RpcClient;
@@ -359,6 +363,17 @@ pub fn calculate_type_id(first_input: &CellInput, cell_count: usize) -> [u8; BLA
     ret
 }
 
+// Pack the configured client type args, `None` if the multi-client isn't created yet.
+pub fn packed_client_type_args(client_type_args: &ClientTypeArgs) -> Option<PackedClientTypeArgs> {
+    let type_id = client_type_args.type_id.as_ref()?;
+    let type_id = PackedHash::from_slice(type_id.0.as_slice()).expect("build type id");
+    let client_type_args = PackedClientTypeArgs::new_builder()
+        .cells_count(client_type_args.cells_count.into())
+        .type_id(type_id)
+        .build();
+    Some(client_type_args)
+}
+
 pub async fn collect_ckb_tx_pool_info_on_duplicate_tx(
     rpc: &impl CkbReader,
     send_tx_err: &Error,
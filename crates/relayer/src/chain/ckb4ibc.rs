@@ -5,7 +5,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::account::Balance;
-use crate::chain::ckb::prelude::{CellSearcher, CkbReader, CkbWriter, TxCompleter};
+use crate::chain::ckb::prelude::{CellSearcher, CkbReader, CkbWriter};
 use crate::chain::ckb4ibc::extractor::extract_channel_end_from_tx;
 use crate::chain::ckb4ibc::utils::{get_connection_index_by_id, get_connection_search_key};
 use crate::chain::endpoint::ChainEndpoint;
@@ -25,18 +25,17 @@ use ckb_ics_axon::commitment::{
     channel_path, connection_path, packet_acknowledgement_commitment_path, packet_commitment_path,
 };
 use ckb_ics_axon::handler::{IbcChannel, IbcConnections, IbcPacket, PacketStatus};
-use ckb_ics_axon::message::{Envelope, MsgType};
-use ckb_ics_axon::object::Ordering;
+use ckb_ics_axon::message::MsgType;
+use ckb_ics_axon::object::{Ordering, State as CkbState};
 use ckb_ics_axon::{ChannelArgs, ConnectionArgs};
 use ckb_jsonrpc_types::{Status, TransactionView};
 use ckb_sdk::constants::TYPE_ID_CODE_HASH;
-use ckb_sdk::traits::SecpCkbRawKeySigner;
-use ckb_sdk::unlock::{ScriptSigner, SecpSighashScriptSigner};
-use ckb_sdk::{Address, AddressPayload, NetworkType, ScriptGroup, ScriptGroupType};
+use ckb_sdk::rpc::ckb_indexer::Cell;
+use ckb_sdk::traits::LiveCell;
+use ckb_sdk::{Address, AddressPayload, NetworkType};
 use ckb_types::core::ScriptHashType;
-use ckb_types::core::TransactionView as CoreTransactionView;
 use ckb_types::molecule::prelude::Entity;
-use ckb_types::packed::{CellInput, OutPoint, Script, WitnessArgs};
+use ckb_types::packed::{CellInput, OutPoint, Script};
 use ckb_types::prelude::{Builder, Pack, Unpack};
 use ckb_types::H256;
 use futures::TryFutureExt;
@@ -44,11 +43,16 @@ use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
+use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawTmHeader;
+use ibc_proto::protobuf::Protobuf;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::clients::ics07_ckb::{
     client_state::CkbClientState, consensus_state::CkbConsensusState, header::CkbHeader,
     light_block::CkbLightBlock,
 };
+use ibc_relayer_types::clients::ics07_tendermint::{
+    consensus_state::ConsensusState as TmConsensusState, header::Header as TmHeader,
+};
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
@@ -76,14 +80,16 @@ use tokio::runtime::Runtime;
 use tokio::sync::watch::Sender as WatchSender;
 use tracing::{info, warn};
 
-use self::extractor::{extract_connections_from_tx, extract_ibc_packet_from_tx};
-use self::message::{convert_msg_to_ckb_tx, CkbTxInfo, Converter, MsgToTxConverter};
+use self::extractor::{extract_connections_from_tx, extract_ibc_packet_from_tx, get_envelope};
+use self::message::{convert_msg_to_ckb_tx, is_packet_msg, CkbTxInfo, Converter, MsgToTxConverter};
 use self::monitor::{Ckb4IbcEventMonitor, WriteAckMonitorCmd};
+use self::reclaimer::PacketReclaimer;
 use self::utils::{
     fetch_transaction_by_hash, generate_ibc_packet_event, generate_tx_proof_from_block,
-    get_channel_search_key, get_encoded_object, get_ibc_merkle_proof, get_packet_search_key,
-    get_prefix_search_key, get_search_key_with_sudt, parse_transaction, transaction_to_event,
+    get_channel_search_key, get_ibc_merkle_proof, get_packet_search_key, get_prefix_search_key,
+    get_search_key_with_sudt, parse_transaction, transaction_to_event,
 };
+use self::wallet::{is_fee_cell, sudt_amount, Wallet};
 
 use super::ckb::rpc_client::RpcClient;
 use super::ckb::utils::wait_ckb_transaction_committed;
@@ -110,10 +116,20 @@ mod cache_set;
 pub mod extractor;
 pub mod message;
 mod monitor;
+pub mod reclaimer;
+#[cfg(test)]
+pub mod simulator;
 pub mod utils;
+pub mod wallet;
+
+#[cfg(test)]
+pub(crate) mod tests;
 
 pub use utils::keccak256;
 
+/// Denomination of the capacity held by the cells of the relayer that can't pay fees
+pub const LOCKED_CKB_DENOM: &str = "ckb-locked";
+
 pub struct ConnectionCache {
     pub ckb_connection: IbcConnections,
     pub cell_input: CellInput,
@@ -132,6 +148,7 @@ pub struct Ckb4IbcChain {
     config: Ckb4IbcChainConfig,
     keybase: KeyRing<Secp256k1KeyPair>,
     cached_network: RwLock<Option<NetworkType>>,
+    wallet: RefCell<Option<Arc<Wallet>>>,
 
     tx_monitor_cmd: Option<TxMonitorCmd>,
     tx_write_ack_cmd: Option<WriteAckMonitorCmd>,
@@ -189,6 +206,79 @@ impl Ckb4IbcChain {
         Ok(address)
     }
 
+    /// The address whose balances are queried, the relayer's one if unset
+    fn balance_address(&self, address: Option<&str>) -> Result<Address, Error> {
+        match address {
+            Some(address) => Address::from_str(address).map_err(|e| {
+                Error::invalid_key_address(
+                    address.to_string(),
+                    tendermint::Error::invalid_key(e.to_string()),
+                )
+            }),
+            None => self.tx_assembler_address(),
+        }
+    }
+
+    /// The wallet funding the transactions of the relayer, shared by all of them so
+    /// that concurrent transactions never pick the same cells, see [`wallet`]
+    pub fn wallet(&self) -> Result<Arc<Wallet>, Error> {
+        if let Some(wallet) = self.wallet.borrow().as_ref() {
+            return Ok(wallet.clone());
+        }
+        let key = self
+            .keybase
+            .get_key(&self.config.key_name)
+            .map_err(Error::key_base)?;
+        let wallet = Arc::new(Wallet::new(key, self.network()?));
+        *self.wallet.borrow_mut() = Some(wallet.clone());
+        Ok(wallet)
+    }
+
+    /// The reclaimer of the packet cells finished on this chain, see [`reclaimer`]
+    pub fn packet_reclaimer(&self) -> Result<PacketReclaimer, Error> {
+        Ok(PacketReclaimer::new(
+            self.rt.clone(),
+            self.rpc_client.clone(),
+            self.config.clone(),
+            self.wallet()?,
+            self.packet_outpoint.clone(),
+        ))
+    }
+
+    /// Split the wallet into `wallet.fee_cells` cells, unless it already holds them
+    fn split_wallet_cells(&self) -> Result<(), Error> {
+        let wallet = self.wallet()?;
+        let capacity = self.config.wallet.fee_cell_capacity * 100_000_000;
+        let split_tx = self.rt.block_on(wallet.split_tx(
+            self.rpc_client.as_ref(),
+            self.config.wallet.fee_cells,
+            capacity,
+        ))?;
+        let Some(tx) = split_tx else {
+            return Ok(());
+        };
+        let tx: TransactionView = tx.into();
+        let tx_hash = match self
+            .rt
+            .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+        {
+            Ok(tx_hash) => tx_hash,
+            Err(err) => {
+                wallet.release(&tx.hash);
+                return Err(err);
+            }
+        };
+        // the reservation expires on its own, the split cells are picked once committed
+        wallet.sent(&tx_hash);
+        info!(
+            "{} split its wallet into {} fee cells in transaction {}",
+            self.id(),
+            self.config.wallet.fee_cells,
+            hex::encode(&tx_hash)
+        );
+        Ok(())
+    }
+
     pub fn get_converter(&self) -> Result<Converter, Error> {
         if self.connection_cache.borrow().is_empty() {
             self.query_connection_and_cache()?;
@@ -215,6 +305,15 @@ impl Ckb4IbcChain {
             }
         });
         std::thread::spawn(move || monitor.run());
+        if self.config.wallet.fee_cells > 0 {
+            if let Err(err) = self.split_wallet_cells() {
+                warn!("{} failed to split its wallet cells: {err}", self.id());
+            }
+        }
+        if self.config.reclaim.enabled {
+            let reclaimer = self.packet_reclaimer()?;
+            std::thread::spawn(move || reclaimer.run());
+        }
         self.tx_write_ack_cmd = Some(write_ack_tx);
         Ok(monitor_tx)
     }
@@ -391,51 +490,181 @@ impl Ckb4IbcChain {
         self.connection_cache.get_mut().clear();
     }
 
-    pub fn complete_tx_with_secp256k1_change_and_envelope(
+    fn counterparty_client_type(&self) -> ClientType {
+        self.counterparty_client_type
+            .borrow()
+            .unwrap_or(ClientType::Mock)
+    }
+
+    /// The client state kept in the light client cell of `client_type`, encoded as a protobuf
+    /// `Any` along with its trusting period and frozen height. The cells of the Tendermint
+    /// client always keep it, the other light clients only report their chain id if their
+    /// cells keep states of their own
+    fn query_onchain_client_state(&self, client_type: ClientType) -> Result<AnyClientState, Error> {
+        let (lc_item, cell) = self.query_client_cell(client_type)?;
+        <AnyClientState as Protobuf<Any>>::decode_vec(&cell.output_data).map_err(|e| {
+            Error::client_state_type(format!(
+                "{client_type} client cell of {}: {e}",
+                lc_item.chain_id
+            ))
+        })
+    }
+
+    /// The live light client cell of `client_type` along with its config entry
+    fn query_client_cell(
         &self,
-        tx: CoreTransactionView,
-        input_capacity: u64,
-        envelope: Envelope,
-    ) -> Result<CoreTransactionView, Error> {
-        let fee_rate = 3000;
-        let address = self.tx_assembler_address()?;
-        let tx = self.rpc_client.complete_tx_with_secp256k1_change(
-            tx,
-            &address,
-            input_capacity,
-            fee_rate,
-        );
-        let (tx, extra_inputs) = self.rt.block_on(tx)?;
+        client_type: ClientType,
+    ) -> Result<(&LightClientItem, LiveCell), Error> {
+        let Some(lc_item) = self.config.onchain_light_clients.get(&client_type) else {
+            return Err(Error::other_error(format!(
+                "config.toml missing client_type {client_type}"
+            )));
+        };
+        let client_cell = self.rt.block_on(self.rpc_client.search_cell_by_typescript(
+            &self.config.client_code_hash.pack(),
+            &lc_item.client_cell_type_args.as_bytes().to_owned(),
+        ))?;
+        let Some(cell) = client_cell else {
+            return Err(Error::other_error(format!(
+                "client cell not found on {}",
+                lc_item.chain_id
+            )));
+        };
+        Ok((lc_item, cell))
+    }
 
-        let total_inputs_capacity = extra_inputs
-            .into_iter()
-            .map(|v| Unpack::<u64>::unpack(&v.capacity()))
-            .sum::<u64>()
-            + input_capacity;
-        let total_outputs_capacity = tx.outputs_capacity().unwrap().as_u64();
-        assert!(
-            total_inputs_capacity > total_outputs_capacity,
-            "capacity overflow: {total_inputs_capacity} > {total_outputs_capacity}"
-        );
+    /// The consensus state of the Tendermint client at `height`, which is only known for
+    /// its latest height, from the header of the update which recreated its cell
+    fn query_tendermint_consensus_state(
+        &self,
+        height: Height,
+    ) -> Result<Option<TmConsensusState>, Error> {
+        let (_, cell) = self.query_client_cell(ClientType::Tendermint)?;
+        let tx = self
+            .rt
+            .block_on(
+                self.rpc_client
+                    .get_transaction(&cell.out_point.tx_hash().unpack()),
+            )?
+            .and_then(|tx| tx.transaction);
+        let Some(tx) = tx else {
+            return Ok(None);
+        };
+        let tx = parse_transaction(tx);
+        let Ok(envelope) = get_envelope(&tx) else {
+            return Ok(None);
+        };
+        if !matches!(envelope.msg_type, MsgType::MsgClientUpdate) {
+            return Ok(None);
+        }
+        let header = <TmHeader as Protobuf<RawTmHeader>>::decode_vec(&envelope.content)
+            .map_err(|e| Error::other_error(format!("invalid tendermint header: {e}")))?;
+        if header.height() != height {
+            return Ok(None);
+        }
+        Ok(Some(header.into()))
+    }
 
-        let witness = WitnessArgs::new_builder()
-            .output_type(get_encoded_object(&envelope).witness)
-            .build()
-            .as_bytes()
-            .pack();
-        let tx = tx
-            .as_advanced_builder()
-            // placeholder for the secp256k1 script, it will be used in the signing step
-            .witness(WitnessArgs::new_builder().build().as_bytes().pack())
-            .witness(witness)
-            .build();
-        Ok(tx)
+    /// The header fields of the CKB block at `height` which make up its consensus state
+    fn query_light_block(&self, height: QueryHeight) -> Result<CkbLightBlock, Error> {
+        let header = match height {
+            QueryHeight::Latest => self.rt.block_on(self.rpc_client.get_tip_header())?,
+            QueryHeight::Specific(height) => {
+                self.rt
+                    .block_on(
+                        self.rpc_client
+                            .get_block_by_number(height.revision_height().into()),
+                    )?
+                    .header
+            }
+        };
+        let ts_milisec = header.inner.timestamp.value();
+        let timestamp =
+            Timestamp::from_nanoseconds(ts_milisec * 1_000_000).map_err(Error::other)?;
+        Ok(CkbLightBlock {
+            height: Height::from_noncosmos_height(header.inner.number.value()),
+            transactions_root: header.inner.transactions_root.as_bytes().to_vec(),
+            timestamp,
+            block_hash: header.hash.as_bytes().to_vec(),
+        })
     }
 
-    fn counterparty_client_type(&self) -> ClientType {
-        self.counterparty_client_type
-            .borrow()
-            .unwrap_or(ClientType::Mock)
+    /// Send the transaction of `msg` and return once it's in the tx pool, the wallet cells
+    /// it spends stay reserved until it's committed, so that other channels can send their
+    /// transactions meanwhile. The workers confirm the transaction by its hash.
+    fn send_message_without_commit(&self, msg: &Any) -> Result<Response, Error> {
+        let (commitment_path, Some(event), Some((tx, msg_type, fee))) =
+            self.assemble_transaction_from_msg(msg)?
+        else {
+            return Err(Error::other_error(format!(
+                "no transaction assembled from {}",
+                msg.type_url
+            )));
+        };
+        let wallet = self.wallet()?;
+        let sent = self
+            .rt
+            .block_on(self.rpc_client.send_transaction(&tx.inner, None));
+        self.clear_cache();
+        let tx_hash = match sent {
+            Ok(tx_hash) => tx_hash,
+            Err(err) => {
+                wallet.release(&tx.hash);
+                return Err(err);
+            }
+        };
+        wallet.sent(&tx_hash);
+        info!(
+            "{msg_type:?} transaction {} (fee {fee} shannons) sent to {}",
+            hex::encode(&tx_hash),
+            self.id()
+        );
+
+        let rpc_client = self.rpc_client.clone();
+        let ibc_transactions_cache = self.ibc_transactions_cache.clone();
+        let chain_id = self.id();
+        let hash = tx_hash.clone();
+        self.rt.spawn(async move {
+            let committed = wait_ckb_transaction_committed(
+                &rpc_client,
+                hash.clone(),
+                Duration::from_secs(10),
+                1,
+                Duration::from_secs(600),
+            )
+            .await;
+            wallet.release(&hash);
+            match committed {
+                Ok(_) => {
+                    crate::telemetry!({
+                        let cycles = rpc_client
+                            .get_transaction(&hash)
+                            .await
+                            .ok()
+                            .flatten()
+                            .and_then(|tx| tx.cycles)
+                            .map(|cycles| cycles.value());
+                        ::ibc_telemetry::global().ckb_tx_committed(
+                            &chain_id,
+                            &format!("{msg_type:?}"),
+                            cycles,
+                            fee,
+                        );
+                    });
+                    if let Ok(mut cache) = ibc_transactions_cache.lock() {
+                        cache.insert(commitment_path, hash);
+                    }
+                }
+                Err(err) => warn!("wait transaction {} failed: {err}", hex::encode(&hash)),
+            }
+        });
+
+        Ok(Response {
+            code: tendermint::abci::Code::Ok,
+            data: event.to_string().into_bytes().into(),
+            log: String::new(),
+            hash: tendermint::Hash::Sha256(tx_hash.into()),
+        })
     }
 
     fn sync_counterparty_client_type(&self, client_type: ClientType) {
@@ -473,7 +702,14 @@ impl Ckb4IbcChain {
     fn assemble_transaction_from_msg(
         &self,
         msg: &Any,
-    ) -> Result<(String, Option<IbcEvent>, Option<(TransactionView, MsgType)>), Error> {
+    ) -> Result<
+        (
+            String,
+            Option<IbcEvent>,
+            Option<(TransactionView, MsgType, u64)>,
+        ),
+        Error,
+    > {
         let converter = self.get_converter()?;
         let CkbTxInfo {
             unsigned_tx,
@@ -487,66 +723,29 @@ impl Ckb4IbcChain {
         }
         let unsigned_tx = unsigned_tx.unwrap();
         let msg_type = envelope.msg_type;
-        match self.complete_tx_with_secp256k1_change_and_envelope(
+        // the first input of client updates is the Tendermint client cell owned by the relayer
+        let owned_inputs = if matches!(msg_type, MsgType::MsgClientUpdate) {
+            vec![0]
+        } else {
+            vec![]
+        };
+        let (tx, fee) = self.rt.block_on(self.wallet()?.complete_owned_tx(
+            self.rpc_client.as_ref(),
             unsigned_tx,
             input_capacity,
-            envelope,
-        ) {
-            Ok(tx) => {
-                let last_input_idx = tx.inputs().len() - 1;
-                let secret_key = self
-                    .keybase
-                    .get_key(&self.config.key_name)
-                    .map_err(Error::key_base)?
-                    .into_ckb_keypair(self.network()?)
-                    .private_key;
-                let signer = SecpSighashScriptSigner::new(Box::new(
-                    SecpCkbRawKeySigner::new_with_secret_keys(vec![secret_key]),
-                ));
-                let tx = signer
-                    .sign_tx(
-                        &tx,
-                        &ScriptGroup {
-                            script: Script::from(&self.tx_assembler_address()?),
-                            group_type: ScriptGroupType::Lock,
-                            // TODO: here should be more indices in case of more than one Secp256k1 cells
-                            //       have been filled in the transaction
-                            input_indices: vec![last_input_idx],
-                            output_indices: vec![],
-                        },
-                    )
-                    .map_err(|err| Error::other_error(err.to_string()))?;
-                Ok((commitment_path, event, Some((tx.into(), msg_type))))
-            }
-            Err(err) => {
-                // return signing error such as no enough ckb
-                Err(err)
-            }
-        }
-    }
-}
-
-impl ChainEndpoint for Ckb4IbcChain {
-    type LightBlock = CkbLightBlock;
-    type Header = CkbHeader;
-    type ConsensusState = CkbConsensusState;
-    type ClientState = CkbClientState;
-    type SigningKeyPair = Secp256k1KeyPair;
-
-    fn config(&self) -> ChainConfig {
-        ChainConfig::Ckb4Ibc(self.config.clone())
+            Some(envelope),
+            &owned_inputs,
+        ))?;
+        Ok((commitment_path, event, Some((tx.into(), msg_type, fee))))
     }
 
-    fn bootstrap(config: ChainConfig, rt: Arc<Runtime>) -> Result<Self, Error> {
-        let config: Ckb4IbcChainConfig = config.try_into()?;
-        let rpc_client = Arc::new(RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc));
-
-        #[cfg(not(test))]
-        {
-            use super::ckb::sighash::init_sighash_celldep;
-            rt.block_on(init_sighash_celldep(rpc_client.as_ref()))?;
-        }
-
+    /// Looks up the cells of the light clients and of the IBC contracts through
+    /// `rpc_client`, which is the CKB simulator in tests
+    fn with_rpc_client(
+        config: Ckb4IbcChainConfig,
+        rt: Arc<Runtime>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Result<Self, Error> {
         let mut client_outpoints = HashMap::new();
         for (
             client_type,
@@ -596,13 +795,14 @@ impl ChainEndpoint for Ckb4IbcChain {
         }
 
         let keybase =
-            KeyRing::new(Default::default(), "ckb", &config.id).map_err(Error::key_base)?;
+            KeyRing::new(config.key_store_type, "ckb", &config.id).map_err(Error::key_base)?;
         let chain = Ckb4IbcChain {
             rt,
             rpc_client,
             config,
             keybase,
             cached_network: RwLock::new(None),
+            wallet: RefCell::new(None),
             tx_monitor_cmd: None,
             tx_write_ack_cmd: None,
             client_outpoints: RefCell::new(client_outpoints),
@@ -619,6 +819,31 @@ impl ChainEndpoint for Ckb4IbcChain {
         };
         Ok(chain)
     }
+}
+
+impl ChainEndpoint for Ckb4IbcChain {
+    type LightBlock = CkbLightBlock;
+    type Header = CkbHeader;
+    type ConsensusState = CkbConsensusState;
+    type ClientState = CkbClientState;
+    type SigningKeyPair = Secp256k1KeyPair;
+
+    fn config(&self) -> ChainConfig {
+        ChainConfig::Ckb4Ibc(self.config.clone())
+    }
+
+    fn bootstrap(config: ChainConfig, rt: Arc<Runtime>) -> Result<Self, Error> {
+        let config: Ckb4IbcChainConfig = config.try_into()?;
+        let rpc_client = Arc::new(RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc));
+
+        #[cfg(not(test))]
+        {
+            use super::ckb::sighash::init_sighash_celldep;
+            rt.block_on(init_sighash_celldep(rpc_client.as_ref()))?;
+        }
+
+        Self::with_rpc_client(config, rt, rpc_client)
+    }
 
     fn shutdown(self) -> Result<(), Error> {
         if let Some(monitor_tx) = self.tx_monitor_cmd {
@@ -696,30 +921,56 @@ impl ChainEndpoint for Ckb4IbcChain {
                         return Ok(vec![]);
                     }
                 }
-                (commitment_path, Some(event), Some((tx, msg_type))) => match self
+                (commitment_path, Some(event), Some((tx, msg_type, fee))) => match self
                     .rt
                     .block_on(self.rpc_client.send_transaction(&tx.inner, None))
                 {
                     Ok(tx_hash) => {
+                        let wallet = self.wallet()?;
+                        wallet.sent(&tx_hash);
                         // TODO: put confirms count into config
                         let confirms = 1;
                         info!(
-                            "{msg_type:?} transaction {} committed to {}, wait {confirms} blocks confirmation",
+                            "{msg_type:?} transaction {} (fee {fee} shannons) committed to {}, wait {confirms} blocks confirmation",
                             hex::encode(&tx_hash),
                             self.id()
                         );
                         retry_times = 0;
-                        match self.rt.block_on(wait_ckb_transaction_committed(
+                        let committed = self.rt.block_on(wait_ckb_transaction_committed(
                             &self.rpc_client,
                             tx_hash.clone(),
                             Duration::from_secs(10),
                             confirms,
                             Duration::from_secs(600),
-                        )) {
+                        ));
+                        wallet.release(&tx_hash);
+                        match committed {
                             Ok(height) => {
+                                crate::telemetry!({
+                                    let cycles = self
+                                        .rt
+                                        .block_on(self.rpc_client.get_transaction(&tx_hash))
+                                        .ok()
+                                        .flatten()
+                                        .and_then(|tx| tx.cycles)
+                                        .map(|cycles| cycles.value());
+                                    ::ibc_telemetry::global().ckb_tx_committed(
+                                        &self.id(),
+                                        &format!("{msg_type:?}"),
+                                        cycles,
+                                        fee,
+                                    );
+                                });
                                 if let Some(client_type) = sync_if_create_client(&event) {
                                     self.sync_counterparty_client_type(client_type);
                                 }
+                                // the updated client cell is the first output
+                                if let IbcEvent::UpdateClient(update) = &event {
+                                    self.client_outpoints.borrow_mut().insert(
+                                        update.common.client_type,
+                                        OutPoint::new_builder().tx_hash(tx_hash.pack()).build(),
+                                    );
+                                }
                                 self.ibc_transactions_cache
                                     .lock()
                                     .unwrap()
@@ -738,6 +989,7 @@ impl ChainEndpoint for Ckb4IbcChain {
                         }
                     }
                     Err(e) => {
+                        self.wallet()?.release(&tx.hash);
                         let json_tx = serde_json::to_string_pretty(&tx).unwrap();
                         let error =
                             format!("{e}\n\n======== transaction info ========\n\n{json_tx}\n");
@@ -760,27 +1012,33 @@ impl ChainEndpoint for Ckb4IbcChain {
         Ok(result_events)
     }
 
-    // FIXME: this method should be in non-blocking mode, but we can't be confident it
-    //        won't leave a protential issue if we do so, and working in blocking mode
-    //        is ok for now, so leave this comment to fix in upcomming days
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<Response>, Error> {
-        let responses = self
-            .send_messages_and_wait_commit(tracked_msgs)?
-            .into_iter()
-            .map(|event| {
-                let value = event.to_string();
-                let data = value.as_bytes().to_vec();
-                Response {
-                    code: tendermint::abci::Code::Ok,
-                    data: data.into(),
-                    log: String::new(),
-                    hash: tendermint::Hash::Sha256(event.tx_hash),
-                }
-            })
-            .collect();
+        // the messages of a batch spend the cells created by the previous ones, which are
+        // only indexed once committed, so only a trailing packet message isn't waited for
+        let TrackedMsgs {
+            mut msgs,
+            tracking_id,
+        } = tracked_msgs;
+        let last = match msgs.last() {
+            Some(msg) if is_packet_msg(msg) => msgs.pop(),
+            _ => None,
+        };
+        let mut responses = vec![];
+        if !msgs.is_empty() {
+            let events = self.send_messages_and_wait_commit(TrackedMsgs::new(msgs, tracking_id))?;
+            responses.extend(events.into_iter().map(|event| Response {
+                code: tendermint::abci::Code::Ok,
+                data: event.to_string().into_bytes().into(),
+                log: String::new(),
+                hash: tendermint::Hash::Sha256(event.tx_hash),
+            }));
+        }
+        if let Some(msg) = last {
+            responses.push(self.send_message_without_commit(&msg)?);
+        }
         Ok(responses)
     }
 
@@ -788,12 +1046,20 @@ impl ChainEndpoint for Ckb4IbcChain {
     fn verify_header(
         &mut self,
         _trusted: Height,
-        _target: Height,
+        target: Height,
         _client_state: &AnyClientState,
     ) -> Result<Self::LightBlock, Error> {
         // Ckb4Ibc doesn't have light client module until we insert cell-emitter crate to
-        // relay ckb headers and cells to Axon endpoint
-        Ok(CkbLightBlock {})
+        // relay ckb headers and cells to Axon endpoint, so the header of the connected CKB
+        // node is trusted as is, only the heights beyond its tip are rejected
+        let tip = self.query_light_block(QueryHeight::Latest)?;
+        if target > tip.height {
+            return Err(Error::other_error(format!(
+                "target height {target} is beyond the CKB tip {}",
+                tip.height
+            )));
+        }
+        self.query_light_block(QueryHeight::Specific(target))
     }
 
     fn check_misbehaviour(
@@ -806,18 +1072,8 @@ impl ChainEndpoint for Ckb4IbcChain {
     }
 
     fn query_balance(&self, address: Option<&str>, symbol: Option<&str>) -> Result<Balance, Error> {
-        let lock_script: Script = match address {
-            Some(address) => Address::from_str(address)
-                .map_err(|e| {
-                    Error::invalid_key_address(
-                        address.to_string(),
-                        tendermint::Error::invalid_key(e.to_string()),
-                    )
-                })?
-                .payload()
-                .into(),
-            None => self.tx_assembler_address()?.payload().into(),
-        };
+        let address = self.balance_address(address)?;
+        let lock_script: Script = address.payload().into();
         let search_key = match symbol {
             Some(symbol) => get_search_key_with_sudt(lock_script, symbol, self.network()?)?,
             None => get_prefix_search_key(lock_script),
@@ -825,34 +1081,83 @@ impl ChainEndpoint for Ckb4IbcChain {
         let asset_cells =
             self.rt
                 .block_on(self.rpc_client.fetch_live_cells(search_key, u32::MAX, None))?;
-        let balance: u128 = asset_cells
+        let balance = asset_cells
             .objects
             .into_iter()
             .filter_map(|cell| {
                 if symbol.is_some() {
-                    let data: [u8; 16] = cell
+                    let amount = cell
                         .output_data
-                        .unwrap()
-                        .as_bytes()
-                        .try_into()
-                        .expect("sudt capacity");
-                    Some(u128::from_le_bytes(data))
-                } else if cell.output.type_.is_some() {
-                    None
-                } else {
+                        .as_ref()
+                        .and_then(|data| sudt_amount(data.as_bytes()));
+                    if amount.is_none() {
+                        warn!(
+                            "skip malformed sUDT cell {:#x}#{} of {address}",
+                            cell.out_point.tx_hash,
+                            cell.out_point.index.value()
+                        );
+                    }
+                    amount
+                } else if is_fee_cell(&cell) {
                     Some(cell.output.capacity.value() as u128)
+                } else {
+                    None
                 }
             })
-            .sum();
+            .fold(0u128, u128::saturating_add);
+        let denom = symbol.unwrap_or("ckb");
+        crate::telemetry!(
+            ckb_balance,
+            &self.id(),
+            &address.to_string(),
+            balance as f64,
+            denom
+        );
         Ok(Balance {
             amount: balance.to_string(),
-            denom: symbol.unwrap_or("ckb").to_owned(),
+            denom: denom.to_owned(),
         })
     }
 
+    /// The capacity spendable for fees and the capacity locked in the other cells of the
+    /// address, followed by the balances of the sUDT tokens in `balance.tokens`
     fn query_all_balances(&self, address: Option<&str>) -> Result<Vec<Balance>, Error> {
-        let ckb_balance = self.query_balance(address, None)?;
-        Ok(vec![ckb_balance])
+        let address = self.balance_address(address)?;
+        let search_key = get_prefix_search_key(address.payload().into());
+        let cells =
+            self.rt
+                .block_on(self.rpc_client.fetch_live_cells(search_key, u32::MAX, None))?;
+        let (spendable, locked): (Vec<_>, Vec<_>) =
+            cells.objects.into_iter().partition(is_fee_cell);
+        let capacity = |cells: Vec<Cell>| -> u128 {
+            cells
+                .into_iter()
+                .map(|cell| cell.output.capacity.value() as u128)
+                .sum()
+        };
+        let locked = capacity(locked);
+        crate::telemetry!(
+            ckb_balance,
+            &self.id(),
+            &address.to_string(),
+            locked as f64,
+            LOCKED_CKB_DENOM
+        );
+        let mut balances = vec![
+            Balance {
+                amount: capacity(spendable).to_string(),
+                denom: "ckb".to_owned(),
+            },
+            Balance {
+                amount: locked.to_string(),
+                denom: LOCKED_CKB_DENOM.to_owned(),
+            },
+        ];
+        let address = address.to_string();
+        for symbol in &self.config.balance.tokens {
+            balances.push(self.query_balance(Some(&address), Some(symbol))?);
+        }
+        Ok(balances)
     }
 
     // TODO: Ckb4Ibc's denom is hash of `sudt_type_script`, we cannot recover it to type_script,
@@ -882,27 +1187,16 @@ impl ChainEndpoint for Ckb4IbcChain {
         &self,
         _request: QueryClientStatesRequest,
     ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
-        Ok(self
-            .config
+        self.config
             .onchain_light_clients
             .keys()
             .map(|client_type| {
-                // TODO query latest_height from light client cell (for example Axon metadata cell)
-                let client_id = self.config.lc_client_id(*client_type).unwrap();
-                let chain_id = self
-                    .config
-                    .lc_chain_id_by_client_id(&client_id.to_string())
-                    .unwrap();
-                IdentifiedAnyClientState {
-                    client_id,
-                    client_state: CkbClientState {
-                        chain_id,
-                        latest_height: Height::default(),
-                    }
-                    .into(),
-                }
+                Ok(IdentifiedAnyClientState {
+                    client_id: self.config.lc_client_id(*client_type)?,
+                    client_state: self.query_onchain_client_state(*client_type)?,
+                })
             })
-            .collect())
+            .collect()
     }
 
     fn query_client_state(
@@ -910,26 +1204,30 @@ impl ChainEndpoint for Ckb4IbcChain {
         request: QueryClientStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
-        let chain_id = self
-            .config
-            .lc_chain_id_by_client_id(&request.client_id.to_string())?;
-        // TODO query latest_height
-        let client_state = CkbClientState {
-            chain_id,
-            latest_height: Height::default(),
-        };
         let client_type = self.config.lc_client_type(&request.client_id.to_string())?;
         self.sync_counterparty_client_type(client_type);
-        Ok((client_state.into(), None))
+        Ok((self.query_onchain_client_state(client_type)?, None))
     }
 
     fn query_consensus_state(
         &self,
-        _request: QueryConsensusStateRequest,
+        request: QueryConsensusStateRequest,
         _include_proof: IncludeProof,
     ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
-        // TODO: fix it when Ckb4Ibc contract refactorred
-        Ok((CkbConsensusState {}.into(), None))
+        // the client cells only keep the latest client state, the CKB headers are the
+        // consensus states of the clients of CKB, see `query_host_consensus_state`
+        let client_type = self.config.lc_client_type(request.client_id.as_str())?;
+        if client_type == ClientType::Tendermint {
+            if let Some(consensus_state) =
+                self.query_tendermint_consensus_state(request.consensus_height)?
+            {
+                return Ok((consensus_state.into(), None));
+            }
+        }
+        Err(Error::other_error(format!(
+            "consensus state of client {} at {} is not stored on CKB",
+            request.client_id, request.consensus_height
+        )))
     }
 
     fn query_consensus_state_heights(
@@ -1321,10 +1619,13 @@ impl ChainEndpoint for Ckb4IbcChain {
                     .collect::<Vec<_>>()
             }
             QueryTxRequest::Transaction(QueryTxHash(TxHash::Sha256(hash))) => {
+                // the transactions sent without waiting may not be known to the node yet
                 let tx = self
                     .rt
-                    .block_on(self.rpc_client.get_transaction(&hash.into()))?
-                    .expect("query_txs");
+                    .block_on(self.rpc_client.get_transaction(&hash.into()))?;
+                let Some(tx) = tx else {
+                    return Ok(vec![]);
+                };
                 let Some(block_hash) = tx.tx_status.block_hash else {
                     return Ok(vec![]);
                 };
@@ -1417,11 +1718,9 @@ impl ChainEndpoint for Ckb4IbcChain {
 
     fn query_host_consensus_state(
         &self,
-        _request: QueryHostConsensusStateRequest,
+        request: QueryHostConsensusStateRequest,
     ) -> Result<Self::ConsensusState, Error> {
-        // TODO
-        warn!("axon query_host_consensus_state() not support");
-        Ok(CkbConsensusState {})
+        Ok(self.query_light_block(request.height)?.into())
     }
 
     fn build_client_state(
@@ -1430,19 +1729,22 @@ impl ChainEndpoint for Ckb4IbcChain {
         settings: ClientSettings,
     ) -> Result<Self::ClientState, Error> {
         match settings {
-            ClientSettings::AxonCkb | ClientSettings::Other => Ok(CkbClientState {
-                chain_id: self.id(),
-                latest_height: height,
-            }),
+            ClientSettings::AxonCkb | ClientSettings::Other => {
+                let genesis = self
+                    .query_light_block(QueryHeight::Specific(Height::from_noncosmos_height(0)))?;
+                Ok(CkbClientState::new(self.id(), height)
+                    .with_genesis_hash(genesis.block_hash)
+                    .with_trusting_period(self.config.trusting_period))
+            }
             _ => Err(Error::build_client_state_failure()),
         }
     }
 
     fn build_consensus_state(
         &self,
-        _light_block: Self::LightBlock,
+        light_block: Self::LightBlock,
     ) -> Result<Self::ConsensusState, Error> {
-        Ok(CkbConsensusState {})
+        Ok(light_block.into())
     }
 
     fn build_header(
@@ -1493,10 +1795,12 @@ impl ChainEndpoint for Ckb4IbcChain {
         _client_id: &ClientId,
         height: Height,
     ) -> Result<(Option<AnyClientState>, Proofs), Error> {
-        let client_state = AnyClientState::Ckb(CkbClientState {
-            chain_id: self.id(),
-            latest_height: height,
-        });
+        // Cosmos chains validate the state of their own client in the handshake
+        let client_state = if self.counterparty_client_type() == ClientType::Tendermint {
+            self.query_onchain_client_state(ClientType::Tendermint)?
+        } else {
+            AnyClientState::Ckb(self.build_client_state(height, ClientSettings::Other)?)
+        };
 
         // use dummy merkle proof when the counterparty is aslo CKB
         if matches!(self.counterparty_client_type(), ClientType::Ckb4Ibc) {
@@ -1565,27 +1869,32 @@ impl ChainEndpoint for Ckb4IbcChain {
             .get(&commitment_path)
             .cloned();
 
-        // search frist channel cell on-chain if cache is missing
+        // search the live channel cell on-chain if cache is missing, it's marked as
+        // not open while the channel is in handshake or once the channel is closed
         if tx_hash.is_none() {
-            let channel_key = get_channel_search_key(
-                &self.config,
-                self.counterparty_client_type(),
-                channel_id,
-                port_id,
-                true,
-            )?;
-            let result =
-                self.rt
-                    .block_on(self.rpc_client.fetch_live_cells(channel_key, 1, None))?;
-            let Some(cell) = result.objects.first() else {
-                return Err(Error::other_error(format!(
-                    "no channel event found for {channel_id}/{port_id} at block {height}"
-                )));
-            };
-            tx_hash = Some(cell.clone().out_point.tx_hash);
+            for open in [true, false] {
+                let channel_key = get_channel_search_key(
+                    &self.config,
+                    self.counterparty_client_type(),
+                    channel_id,
+                    port_id,
+                    open,
+                )?;
+                let result =
+                    self.rt
+                        .block_on(self.rpc_client.fetch_live_cells(channel_key, 1, None))?;
+                if let Some(cell) = result.objects.first() {
+                    tx_hash = Some(cell.clone().out_point.tx_hash);
+                    break;
+                }
+            }
         }
 
-        let tx_hash = tx_hash.unwrap();
+        let Some(tx_hash) = tx_hash else {
+            return Err(Error::other_error(format!(
+                "no channel event found for {channel_id}/{port_id} at block {height}"
+            )));
+        };
         let Some(proof) = self.rt.block_on(generate_tx_proof_from_block(
             self.rpc_client.as_ref(),
             &tx_hash,
@@ -1618,6 +1927,40 @@ impl ChainEndpoint for Ckb4IbcChain {
             return get_ibc_merkle_proof(height, vec![0u8]);
         }
 
+        // packets are timed out on close against the transaction which closed the channel,
+        // its channel object keeps the received sequences, so the proof of the closed channel
+        // also proves that the packet was never received
+        if matches!(
+            packet_type,
+            PacketMsgType::TimeoutOnCloseUnordered | PacketMsgType::TimeoutOnCloseOrdered
+        ) {
+            let (_, channel) = self.fetch_channel_cell_and_extract(&channel_id, &port_id, false)?;
+            let seq = u64::from(sequence);
+            let received = match channel.order {
+                Ordering::Ordered => seq < channel.sequence.next_sequence_recvs,
+                _ => channel.sequence.received_sequences.contains(&seq),
+            };
+            if channel.state != CkbState::Closed {
+                return Err(Error::other_error(format!(
+                    "channel {channel_id}/{port_id} isn't closed"
+                )));
+            }
+            if received {
+                return Err(Error::other_error(format!(
+                    "packet {sequence} was received on {channel_id}/{port_id} before it was closed"
+                )));
+            }
+            let proofs = self.build_channel_proofs(&port_id, &channel_id, height)?;
+            return Proofs::new(
+                proofs.object_proof().clone(),
+                None,
+                None,
+                Some(proofs.object_proof().clone()),
+                proofs.height(),
+            )
+            .map_err(Error::malformed_proof);
+        }
+
         let commitment_path = match packet_type {
             PacketMsgType::Recv => {
                 packet_commitment_path(port_id.as_str(), channel_id.as_str(), sequence.into())
@@ -267,7 +267,7 @@ pub fn get_envelope(tx: &TransactionView) -> Result<Envelope, Error> {
         .map_err(|_| Error::ckb_decode_witness_args())?
         .output_type()
         .to_opt()
-        .unwrap();
+        .ok_or(Error::ckb_decode_envelope())?;
     let envelope_slice = envelope_bytes.raw_data();
 
     let envelope =
@@ -10,6 +10,7 @@ use ckb_ics_axon::{
     ChannelArgs, ConnectionArgs,
 };
 use ckb_types::{
+    bytes::Bytes,
     core::{Capacity, DepType, TransactionBuilder, TransactionView},
     packed::{
         Byte32, Bytes as PackedBytes, BytesOpt, CellDep, CellInput, CellOutput, OutPoint, Script,
@@ -21,6 +22,7 @@ use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::{
     core::ics02_client::msgs::{
         create_client::{MsgCreateClient, TYPE_URL as CREATE_CLIENT_TYPE_URL},
+        misbehaviour::{MsgSubmitMisbehaviour, TYPE_URL as MISBEHAVIOUR_TYPE_URL},
         update_client::{MsgUpdateClient, TYPE_URL as UPDATE_CLIENT_TYPE_URL},
     },
     core::ics03_connection::msgs::{
@@ -45,6 +47,7 @@ use ibc_relayer_types::{
                 chan_open_init::{MsgChannelOpenInit, TYPE_URL as CHAN_OPEN_INIT_TYPE_URL},
                 chan_open_try::{MsgChannelOpenTry, TYPE_URL as CHAN_OPEN_TRY_TYPE_URL},
                 recv_packet::{MsgRecvPacket, TYPE_URL as RECV_PACKET_TYPE_URL},
+                timeout_on_close::{MsgTimeoutOnClose, TYPE_URL as TIMEOUT_ON_CLOSE_TYPE_URL},
             },
             packet::Sequence,
         },
@@ -56,7 +59,7 @@ use ibc_relayer_types::{
 };
 
 use super::{monitor::WriteAckMonitorCmd, utils::get_script_hash, Ckb4IbcChain};
-use client::{convert_create_client, convert_update_client};
+use client::{convert_create_client, convert_submit_misbehaviour, convert_update_client};
 
 use channel::*;
 use connection::*;
@@ -70,7 +73,7 @@ macro_rules! convert {
     }};
 }
 
-struct EmptyClient;
+pub(crate) struct EmptyClient;
 
 impl Client for EmptyClient {
     fn verify_membership(
@@ -111,6 +114,8 @@ pub trait MsgToTxConverter {
 
     fn get_client_outpoint(&self, client_id: &str) -> Option<OutPoint>;
 
+    fn get_client_cell(&self, client_id: &str) -> Result<(CellInput, CellOutput, Bytes), Error>;
+
     fn get_conn_contract_outpoint(&self) -> &OutPoint;
 
     fn get_chan_contract_outpoint(&self) -> &OutPoint;
@@ -280,6 +285,15 @@ impl<'a> MsgToTxConverter for Converter<'a> {
             .cloned()
     }
 
+    fn get_client_cell(&self, client_id: &str) -> Result<(CellInput, CellOutput, Bytes), Error> {
+        let client_type = self.get_config().lc_client_type(client_id)?;
+        let (_, cell) = self.ckb_instance.query_client_cell(client_type)?;
+        let input = CellInput::new_builder()
+            .previous_output(cell.out_point)
+            .build();
+        Ok((input, cell.output, cell.output_data))
+    }
+
     fn get_conn_contract_outpoint(&self) -> &OutPoint {
         &self.ckb_instance.connection_outpoint
     }
@@ -387,6 +401,15 @@ pub struct CkbTxInfo {
     pub commitment_path: String,
 }
 
+// The packet messages, whose transactions are confirmed by the packet workers instead of
+// being waited for, see `Ckb4IbcChain::send_message_without_commit`
+pub fn is_packet_msg(msg: &Any) -> bool {
+    matches!(
+        msg.type_url.as_str(),
+        RECV_PACKET_TYPE_URL | ACK_PACKET_TYPE_URL | TIMEOUT_ON_CLOSE_TYPE_URL
+    )
+}
+
 // Return a transaction which needs to be added relayer's input in it and to be signed.
 pub fn convert_msg_to_ckb_tx<C: MsgToTxConverter>(
     msg: &Any,
@@ -396,6 +419,12 @@ pub fn convert_msg_to_ckb_tx<C: MsgToTxConverter>(
         // client
         CREATE_CLIENT_TYPE_URL => convert!(msg, converter, MsgCreateClient, convert_create_client),
         UPDATE_CLIENT_TYPE_URL => convert!(msg, converter, MsgUpdateClient, convert_update_client),
+        MISBEHAVIOUR_TYPE_URL => convert!(
+            msg,
+            converter,
+            MsgSubmitMisbehaviour,
+            convert_submit_misbehaviour
+        ),
         // connection
         CONN_OPEN_INIT_TYPE_URL => convert!(
             msg,
@@ -463,6 +492,12 @@ pub fn convert_msg_to_ckb_tx<C: MsgToTxConverter>(
         ACK_PACKET_TYPE_URL => {
             convert!(msg, converter, MsgAcknowledgement, convert_ack_packet_to_tx)
         }
+        TIMEOUT_ON_CLOSE_TYPE_URL => convert!(
+            msg,
+            converter,
+            MsgTimeoutOnClose,
+            convert_timeout_on_close_to_tx
+        ),
         _ => Err(Error::other(format!(
             "cannot convert ibc_msg: {}",
             msg.type_url
@@ -514,6 +549,19 @@ impl TxBuilder {
             .into()
     }
 
+    /// An output keeping the lock and the type script of `output`
+    pub fn output_cell(self, output: CellOutput, data: PackedBytes) -> Self {
+        self.builder
+            .output(
+                output
+                    .as_builder()
+                    .build_exact_capacity(Capacity::bytes(data.len()).unwrap())
+                    .expect("transaction output capacity"),
+            )
+            .output_data(data)
+            .into()
+    }
+
     pub fn witness(self, input_type: BytesOpt, output_type: BytesOpt) -> Self {
         self.builder
             .witness(
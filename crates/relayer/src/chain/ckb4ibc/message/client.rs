@@ -2,16 +2,35 @@ use ckb_ics_axon::{
     handler::IbcConnections,
     message::{Envelope, MsgType},
 };
-use ckb_types::packed::BytesOpt;
+use ckb_types::{
+    packed::BytesOpt,
+    prelude::{Pack, Unpack},
+};
+use ibc_proto::{
+    google::protobuf::Any, ibc::lightclients::tendermint::v1::Header as RawTmHeader,
+    protobuf::Protobuf,
+};
 use ibc_relayer_types::{
     clients::{
-        ics07_axon::client_state::AXON_CLIENT_STATE_TYPE_URL,
+        ics07_axon::{
+            client_state::AXON_CLIENT_STATE_TYPE_URL,
+            header::{AxonHeader, AXON_HEADER_TYPE_URL},
+            misbehaviour::AxonMisbehaviour,
+        },
         ics07_ckb::client_state::CKB_CLIENT_STATE_TYPE_URL,
+        ics07_tendermint::{
+            client_state::{ClientState as TmClientState, TENDERMINT_CLIENT_STATE_TYPE_URL},
+            header::{Header as TmHeader, TENDERMINT_HEADER_TYPE_URL},
+        },
     },
     core::ics02_client::{
         client_type::ClientType,
         events::{Attributes, CreateClient, UpdateClient},
-        msgs::{create_client::MsgCreateClient, update_client::MsgUpdateClient},
+        header::Header,
+        msgs::{
+            create_client::MsgCreateClient, misbehaviour::MsgSubmitMisbehaviour,
+            update_client::MsgUpdateClient,
+        },
     },
     events::IbcEvent,
     Height,
@@ -21,6 +40,7 @@ use super::{CkbTxInfo, MsgToTxConverter, TxBuilder};
 
 use crate::{
     chain::ckb4ibc::utils::{get_connection_lock_script, get_encoded_object},
+    client_state::AnyClientState,
     error::Error,
 };
 
@@ -28,27 +48,20 @@ pub fn convert_create_client<C: MsgToTxConverter>(
     msg: MsgCreateClient,
     converter: &C,
 ) -> Result<CkbTxInfo, Error> {
-    let (client_type, client_id) = match msg.client_state.type_url.as_str() {
-        AXON_CLIENT_STATE_TYPE_URL => (
-            ClientType::Axon,
-            converter
-                .get_config()
-                .lc_client_id(ClientType::Axon)
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?,
-        ),
-        CKB_CLIENT_STATE_TYPE_URL => (
-            ClientType::Ckb4Ibc,
-            converter
-                .get_config()
-                .lc_client_id(ClientType::Ckb4Ibc)
-                .map_err(|e| Error::client_state_type(format!("{}: {e}", ClientType::Ckb4Ibc)))?,
-        ),
+    let client_type = match msg.client_state.type_url.as_str() {
+        AXON_CLIENT_STATE_TYPE_URL => ClientType::Axon,
+        CKB_CLIENT_STATE_TYPE_URL => ClientType::Ckb4Ibc,
+        TENDERMINT_CLIENT_STATE_TYPE_URL => ClientType::Tendermint,
         url => {
             return Err(Error::other_error(format!(
                 "unsupport client_state url: {url}"
             )));
         }
     };
+    let client_id = converter
+        .get_config()
+        .lc_client_id(client_type)
+        .map_err(|e| Error::client_state_type(format!("{client_type}: {e}")))?;
     // one light client only matches one unique connections cell on CKB, if not exist, create it
     let find_unique_connections = converter.get_ibc_connections(client_id.as_str()).is_ok();
     let unsigned_tx = if !find_unique_connections {
@@ -82,10 +95,34 @@ pub fn convert_create_client<C: MsgToTxConverter>(
     })
 }
 
+// the light client cells are updated by their own maintainers, the header is only
+// decoded to report the updated height, and to let the Axon updates be checked
+// for misbehaviour. The Tendermint client cell is maintained by the relayer instead
 pub fn convert_update_client<C: MsgToTxConverter>(
     msg: MsgUpdateClient,
-    _converter: &C,
+    converter: &C,
 ) -> Result<CkbTxInfo, Error> {
+    let client_type = converter
+        .get_config()
+        .lc_client_type(msg.client_id.as_str())?;
+    if client_type == ClientType::Tendermint {
+        return convert_update_tendermint_client(msg, converter);
+    }
+    let header: Option<Box<dyn Header>> = match msg.header.type_url.as_str() {
+        TENDERMINT_HEADER_TYPE_URL => Some(Box::new(
+            <TmHeader as Protobuf<RawTmHeader>>::decode_vec(&msg.header.value)
+                .map_err(|e| Error::other_error(format!("invalid tendermint header: {e}")))?,
+        )),
+        AXON_HEADER_TYPE_URL => Some(Box::new(
+            AxonHeader::try_from(msg.header.clone())
+                .map_err(|e| Error::other_error(format!("invalid axon header: {e}")))?,
+        )),
+        _ => None,
+    };
+    let consensus_height = header
+        .as_ref()
+        .map(|header| header.height())
+        .unwrap_or_default();
     Ok(CkbTxInfo {
         unsigned_tx: None,
         envelope: Envelope {
@@ -97,11 +134,141 @@ pub fn convert_update_client<C: MsgToTxConverter>(
         event: Some(IbcEvent::UpdateClient(UpdateClient {
             common: Attributes {
                 client_id: msg.client_id,
-                client_type: ClientType::Ckb4Ibc,
-                consensus_height: Height::default(),
+                client_type,
+                consensus_height,
             },
-            header: None,
+            header,
         })),
         commitment_path: Default::default(),
     })
 }
+
+// the Tendermint client cell is locked by the relayer, which recreates it with the
+// client state advanced to the header, the header is carried by the envelope.
+//
+// The commit signatures of the header are NOT verified, neither by the relayer nor
+// by the cell, so the Tendermint client on CKB trusts the relayer which owns the cell.
+// Only the headers of another chain or from an untrusted height are rejected.
+fn convert_update_tendermint_client<C: MsgToTxConverter>(
+    msg: MsgUpdateClient,
+    converter: &C,
+) -> Result<CkbTxInfo, Error> {
+    if msg.header.type_url != TENDERMINT_HEADER_TYPE_URL {
+        return Err(Error::other_error(format!(
+            "unsupported header of tendermint client {}: {}",
+            msg.client_id, msg.header.type_url
+        )));
+    }
+    let header = <TmHeader as Protobuf<RawTmHeader>>::decode_vec(&msg.header.value)
+        .map_err(|e| Error::other_error(format!("invalid tendermint header: {e}")))?;
+    let (client_input, client_output, client_data) =
+        converter.get_client_cell(msg.client_id.as_str())?;
+    let Ok(AnyClientState::Tendermint(client_state)) =
+        <AnyClientState as Protobuf<Any>>::decode_vec(&client_data)
+    else {
+        return Err(Error::client_state_type(format!(
+            "{}: cell data isn't a tendermint client state",
+            msg.client_id
+        )));
+    };
+    if let Some(frozen_height) = client_state.frozen_height {
+        return Err(Error::other_error(format!(
+            "tendermint client {} is frozen at {frozen_height}",
+            msg.client_id
+        )));
+    }
+    check_tendermint_header(msg.client_id.as_str(), &client_state, &header)?;
+    let consensus_height = header.height();
+    let new_client_state = if consensus_height > client_state.latest_height {
+        client_state
+            .with_header(header.clone())
+            .map_err(|e| Error::other_error(e.to_string()))?
+    } else {
+        client_state
+    };
+    let new_client_data = <AnyClientState as Protobuf<Any>>::encode_vec(&new_client_state.into())
+        .map_err(|e| Error::other_error(e.to_string()))?;
+
+    let input_capacity: u64 = client_output.capacity().unpack();
+    let packed_tx = TxBuilder::default()
+        .input(client_input)
+        .output_cell(client_output, new_client_data.pack())
+        .witness(BytesOpt::default(), BytesOpt::default())
+        .build();
+    Ok(CkbTxInfo {
+        unsigned_tx: Some(packed_tx),
+        envelope: Envelope {
+            msg_type: MsgType::MsgClientUpdate,
+            content: msg.header.value,
+            commitments: vec![],
+        },
+        input_capacity,
+        event: Some(IbcEvent::UpdateClient(UpdateClient {
+            common: Attributes {
+                client_id: msg.client_id,
+                client_type: ClientType::Tendermint,
+                consensus_height,
+            },
+            header: Some(Box::new(header)),
+        })),
+        commitment_path: Default::default(),
+    })
+}
+
+fn check_tendermint_header(
+    client_id: &str,
+    client_state: &TmClientState,
+    header: &TmHeader,
+) -> Result<(), Error> {
+    let invalid = |reason: String| {
+        Err(Error::other_error(format!(
+            "invalid header of tendermint client {client_id}: {reason}"
+        )))
+    };
+    let chain_id = header.signed_header.header.chain_id.as_str();
+    if chain_id != client_state.chain_id.as_str() {
+        return invalid(format!(
+            "chain id {chain_id} doesn't match {}",
+            client_state.chain_id
+        ));
+    }
+    if header.trusted_height > client_state.latest_height {
+        return invalid(format!(
+            "trusted height {} is above the latest height {}",
+            header.trusted_height, client_state.latest_height
+        ));
+    }
+    Ok(())
+}
+
+// the light client cells are only writable by their own maintainers, so the relayer
+// checks the evidence and hands it over to them by failing loudly, it's reported
+// once per client
+pub fn convert_submit_misbehaviour<C: MsgToTxConverter>(
+    msg: MsgSubmitMisbehaviour,
+    converter: &C,
+) -> Result<CkbTxInfo, Error> {
+    let client_type = converter
+        .get_config()
+        .lc_client_type(msg.client_id.as_str())?;
+    if client_type != ClientType::Axon {
+        return Err(Error::other_error(format!(
+            "unsupported misbehaviour of {client_type} client {}",
+            msg.client_id
+        )));
+    }
+    let misbehaviour = AxonMisbehaviour::try_from(msg.misbehaviour)
+        .map_err(|e| Error::other_error(format!("invalid axon misbehaviour: {e}")))?;
+    if misbehaviour.client_id != msg.client_id
+        || misbehaviour.block1.number() != misbehaviour.block2.number()
+        || misbehaviour.block1.hash() == misbehaviour.block2.hash()
+    {
+        return Err(Error::other_error(format!(
+            "inconsistent axon misbehaviour: {misbehaviour}"
+        )));
+    }
+    Err(Error::other_error(format!(
+        "client {} on CKB can only be frozen by its maintainer, misbehaviour: {misbehaviour}",
+        msg.client_id
+    )))
+}
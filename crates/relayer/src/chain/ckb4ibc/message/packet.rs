@@ -1,19 +1,23 @@
 use ckb_ics_axon::commitment::packet_commitment_path;
 use ckb_ics_axon::handler::handle_msg_ack_packet;
 use ckb_ics_axon::handler::handle_msg_recv_packet;
+use ckb_ics_axon::handler::handle_msg_timeout_packet;
 use ckb_ics_axon::handler::IbcPacket;
 use ckb_ics_axon::handler::PacketStatus;
 use ckb_ics_axon::message::Envelope;
 use ckb_ics_axon::message::MsgAckPacket as CkbMsgAckPacket;
 use ckb_ics_axon::message::MsgRecvPacket as CkbMsgRecvPacket;
+use ckb_ics_axon::message::MsgTimeoutPacket as CkbMsgTimeoutPacket;
 use ckb_ics_axon::message::MsgType;
-use ckb_ics_axon::object::{Ordering, Packet as CkbPacket};
+use ckb_ics_axon::object::{Ordering, Packet as CkbPacket, State as CkbState};
 use ckb_ics_axon::{ChannelArgs, PacketArgs};
 use ckb_types::packed::BytesOpt;
 use ibc_relayer_types::core::ics04_channel::events::AcknowledgePacket;
 use ibc_relayer_types::core::ics04_channel::events::ReceivePacket;
+use ibc_relayer_types::core::ics04_channel::events::TimeoutOnClosePacket;
 use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use ibc_relayer_types::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
 use ibc_relayer_types::core::ics04_channel::packet::Packet;
 use ibc_relayer_types::events::IbcEvent;
 
@@ -113,7 +117,8 @@ pub fn convert_recv_packet_to_tx<C: MsgToTxConverter>(
     let mut write_ack_witness = BytesOpt::default();
 
     // fetch useless packet cell as input to save capacity
-    let useless_write_ack_packet = converter.require_useless_write_ack_packet(15); // TODO: make block number gap setup in config
+    let block_gap = converter.get_config().reclaim.block_gap;
+    let useless_write_ack_packet = converter.require_useless_write_ack_packet(block_gap);
     if let Some((packet, input, capacity)) = &useless_write_ack_packet {
         tracing::info!(
             "use useless WriteAck({}) to save CKB capacity",
@@ -288,3 +293,127 @@ pub fn convert_ack_packet_to_tx<C: MsgToTxConverter>(
         commitment_path,
     })
 }
+
+pub fn convert_timeout_on_close_to_tx<C: MsgToTxConverter>(
+    msg: MsgTimeoutOnClose,
+    converter: &C,
+) -> Result<CkbTxInfo, Error> {
+    let channel_id = msg.packet.source_channel.clone();
+    let old_channel_end = converter.get_ibc_channel(&channel_id, Some(&msg.packet.source_port))?;
+    let mut new_channel_end = old_channel_end.clone();
+
+    // the timeout of a packet closes its ordered channel, as it's closed on the counterparty
+    match old_channel_end.order {
+        Ordering::Ordered => new_channel_end.state = CkbState::Closed,
+        Ordering::Unordered => {}
+        Ordering::Unknown => return Err(Error::other("channel ordering must be Order or Unorder")),
+    }
+
+    let old_channel = get_encoded_object(&old_channel_end);
+    let new_channel = get_encoded_object(&new_channel_end);
+
+    let channel_number = get_channel_number(&channel_id)?;
+    let port_id = convert_port_id_to_array(&msg.packet.source_port)?;
+    let packet_args = PacketArgs {
+        sequence: msg.packet.sequence.into(),
+        channel_id: channel_number,
+        port_id,
+    };
+
+    // the packet cell is left to the sending application, which refunds the packet
+    let (channel_input, channel_capacity, old_channel_args) =
+        converter.get_ibc_channel_input(&channel_id, &msg.packet.source_port)?;
+    let (old_packet_input, packet_capacity) = converter.get_ibc_packet_input(
+        &channel_id,
+        &msg.packet.source_port,
+        msg.packet.sequence,
+    )?;
+    let old_ibc_packet =
+        converter.get_ibc_packet(&channel_id, &msg.packet.source_port, msg.packet.sequence)?;
+    if old_ibc_packet.status != PacketStatus::Send {
+        return Err(Error::other_error(format!(
+            "packet {} on {channel_id} is already settled",
+            msg.packet.sequence
+        )));
+    }
+    let packet = get_encoded_object(&old_ibc_packet);
+
+    let connection_id = new_channel_end.connection_hops[0].parse().unwrap();
+    let connection_args = converter
+        .get_ibc_connections_by_connection_id(&connection_id)?
+        .0;
+    let client_id = connection_args.client_id();
+    let new_channel_args = ChannelArgs {
+        metadata_type_id: connection_args.metadata_type_id,
+        ibc_handler_address: connection_args.ibc_handler_address,
+        open: new_channel_end.state != CkbState::Closed,
+        channel_id: channel_number,
+        port_id,
+    };
+
+    let channel_lock = get_channel_lock_script(converter, new_channel_args.to_args());
+    let packet_lock = get_packet_lock_script(converter, packet_args.to_args());
+
+    // the object proof proves the absence of the packet receipt on the counterparty, and
+    // the other proof its closed channel end
+    let timeout_packet = CkbMsgTimeoutPacket {
+        proof_height: convert_proof_height(msg.proofs.height()),
+        proof_unreceived: msg.proofs.object_proof().clone().into(),
+        proof_close: msg
+            .proofs
+            .other_proof()
+            .clone()
+            .ok_or_else(|| Error::other_error("missing proof of the closed channel".to_owned()))?
+            .into(),
+        next_sequence_recv: msg.next_sequence_recv.into(),
+    };
+    let content = rlp::encode(&timeout_packet).to_vec();
+    let mut commitments = vec![];
+    handle_msg_timeout_packet(
+        EmptyClient,
+        old_channel_end.clone(),
+        old_channel_args,
+        new_channel_end.clone(),
+        new_channel_args,
+        old_ibc_packet.clone(),
+        packet_args,
+        old_ibc_packet,
+        packet_args,
+        &mut commitments,
+        timeout_packet,
+    )
+    .map_err(|err| Error::other_error(format!("handle error: {}", err as i8)))?;
+
+    let envelope = Envelope {
+        msg_type: MsgType::MsgTimeoutPacket,
+        content,
+        commitments,
+    };
+
+    let packed_tx = TxBuilder::default()
+        .cell_dep(get_client_outpoint(converter, &client_id)?)
+        .cell_dep(converter.get_chan_contract_outpoint().clone())
+        .cell_dep(converter.get_packet_contract_outpoint().clone())
+        .input(channel_input.clone())
+        .input(old_packet_input.clone())
+        .output(channel_lock, new_channel.data)
+        .output(packet_lock, packet.data)
+        .witness(old_channel.witness, new_channel.witness)
+        .witness(packet.witness.clone(), packet.witness)
+        .build();
+
+    let commitment_path = packet_commitment_path(
+        msg.packet.source_port.as_ref(),
+        channel_id.as_ref(),
+        msg.packet.sequence.into(),
+    );
+    let event = IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket { packet: msg.packet });
+
+    Ok(CkbTxInfo {
+        unsigned_tx: Some(packed_tx),
+        envelope,
+        input_capacity: channel_capacity + packet_capacity,
+        event: Some(event),
+        commitment_path,
+    })
+}
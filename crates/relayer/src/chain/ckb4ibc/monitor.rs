@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ckb_ics_axon::commitment::{
     channel_path, connection_path, packet_acknowledgement_commitment_path, packet_commitment_path,
@@ -34,7 +34,7 @@ use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::timestamp::Timestamp;
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::watch::Receiver as WatchReceiver;
-use tracing::{error, info};
+use tracing::{error, info, trace};
 
 use crate::chain::ckb::prelude::CkbReader;
 use crate::chain::ckb::rpc_client::RpcClient;
@@ -168,9 +168,17 @@ impl Ckb4IbcEventMonitor {
         }
 
         // 'mut self' cannot be used in tokio::join macro, it can only be handled in sequence
+        let started = Instant::now();
         let connection_events = self.fetch_connection_events().await;
         let channel_events = self.fetch_channel_events().await;
         let packet_events = self.fetch_packet_events().await;
+        let scan_duration = started.elapsed();
+        trace!("{} scanned IBC cells in {scan_duration:?}", self.config.id);
+        crate::telemetry!(
+            ckb4ibc_monitor_scan_duration,
+            &self.config.id,
+            scan_duration
+        );
 
         self.process_batch(connection_events);
         self.process_batch(channel_events);
@@ -0,0 +1,559 @@
+//! Reclamation of the capacity locked by the packet cells of finished packets.
+//!
+//! The `Ack` packet cell of an acknowledged packet, and the packet cell left by a
+//! `MsgTimeoutPacket`, are consumed by the sending application to settle the packet,
+//! e.g. to refund a transfer, so they aren't reclaimed by default. Once they're older
+//! than `reclaim.abandoned_block_gap` blocks, they're considered abandoned and
+//! consumed by a `MsgConsumeAckPacket` transaction which pays their capacity back to
+//! the relayer.
+//!
+//! `WriteAck` packet cells can only be consumed along with a `MsgRecvPacket`, so they
+//! are recycled by the `RecvPacket` transactions, after `reclaim.block_gap` blocks,
+//! instead.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ckb_ics_axon::handler::{IbcPacket, PacketStatus};
+use ckb_ics_axon::message::{Envelope, MsgConsumeAckPacket, MsgType};
+use ckb_jsonrpc_types::{Script as JsonScript, TransactionView};
+use ckb_types::core::{ScriptHashType, TransactionView as CoreTransactionView};
+use ckb_types::packed::{BytesOpt, CellInput, OutPoint, Script};
+use ckb_types::prelude::{Builder, Entity};
+use ckb_types::H256;
+use serde::Serialize;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{info, warn};
+
+use super::extractor::{extract_ibc_packet_from_tx, get_envelope};
+use super::message::TxBuilder;
+use super::utils::{
+    fetch_transaction_by_hash, get_encoded_object, get_prefix_search_key, get_script_hash,
+    tip_block_number,
+};
+use super::wallet::Wallet;
+use crate::chain::ckb::prelude::{CkbReader, CkbWriter};
+use crate::chain::ckb::rpc_client::RpcClient;
+use crate::chain::ckb::utils::wait_ckb_transaction_committed;
+use crate::config::ckb4ibc::ChainConfig;
+use crate::error::Error;
+
+/// Number of packet cells fetched by a single indexer request
+const CELLS_PAGE_SIZE: u32 = 100;
+
+/// How the packet of a finished packet cell was settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Finished {
+    Acknowledged,
+    TimedOut,
+}
+
+/// A finished packet cell, along with the packet object it carries
+#[derive(Clone)]
+struct FinishedPacketCell {
+    packet: IbcPacket,
+    finished: Finished,
+    input: CellInput,
+    capacity: u64,
+}
+
+/// A committed transaction which consumed finished packet cells
+#[derive(Debug, Clone, Serialize)]
+pub struct Reclaimed {
+    pub tx_hash: H256,
+    pub cells: usize,
+    /// Number of the cells left by timed out packets, the others were acknowledged
+    pub timed_out: usize,
+    /// Capacity paid back to the relayer once the fee is deducted, in shannons
+    pub capacity: u64,
+}
+
+pub struct PacketReclaimer {
+    rt: Arc<TokioRuntime>,
+    rpc_client: Arc<RpcClient>,
+    config: ChainConfig,
+    wallet: Arc<Wallet>,
+    packet_outpoint: OutPoint,
+}
+
+impl PacketReclaimer {
+    pub fn new(
+        rt: Arc<TokioRuntime>,
+        rpc_client: Arc<RpcClient>,
+        config: ChainConfig,
+        wallet: Arc<Wallet>,
+        packet_outpoint: OutPoint,
+    ) -> Self {
+        Self {
+            rt,
+            rpc_client,
+            config,
+            wallet,
+            packet_outpoint,
+        }
+    }
+
+    /// Reclaim the finished packet cells every `reclaim.interval`, until the process exits
+    pub fn run(self) {
+        info!(
+            "{} starts reclaiming finished packet cells every {:?}",
+            self.config.id, self.config.reclaim.interval
+        );
+        loop {
+            std::thread::sleep(self.config.reclaim.interval);
+            match self.reclaim() {
+                Ok(reclaimed) => {
+                    for Reclaimed {
+                        tx_hash,
+                        cells,
+                        timed_out,
+                        capacity,
+                    } in reclaimed
+                    {
+                        info!(
+                            "♻️  {} reclaimed {capacity} shannons from {cells} packet cells ({timed_out} timed out) in transaction {tx_hash:#x}",
+                            self.config.id
+                        );
+                    }
+                }
+                Err(err) => warn!("{} failed to reclaim packet cells: {err}", self.config.id),
+            }
+        }
+    }
+
+    /// Consume all the finished packet cells, `reclaim.batch_size` cells per transaction.
+    ///
+    /// A failed batch, e.g. because the application consumed one of its cells in the
+    /// meantime, is skipped and picked up again by the next call.
+    pub fn reclaim(&self) -> Result<Vec<Reclaimed>, Error> {
+        let cells = self.rt.block_on(self.finished_packet_cells())?;
+        let mut reclaimed = vec![];
+        for batch in cells.chunks(self.config.reclaim.batch_size.max(1)) {
+            let result = self
+                .assemble_reclaim_tx(batch)
+                .and_then(|(tx, capacity)| Ok((self.send_and_wait(tx)?, capacity)));
+            match result {
+                Ok((tx_hash, capacity)) => reclaimed.push(Reclaimed {
+                    tx_hash,
+                    cells: batch.len(),
+                    timed_out: batch
+                        .iter()
+                        .filter(|cell| cell.finished == Finished::TimedOut)
+                        .count(),
+                    capacity,
+                }),
+                Err(err) => warn!(
+                    "{} skips reclaiming {} packet cells: {err}",
+                    self.config.id,
+                    batch.len()
+                ),
+            }
+        }
+        Ok(reclaimed)
+    }
+
+    /// The `Ack` and timed out packet cells created by this relayer, which their applications
+    /// left unconsumed for at least `reclaim.abandoned_block_gap` blocks
+    async fn finished_packet_cells(&self) -> Result<Vec<FinishedPacketCell>, Error> {
+        let Some(block_gap) = self.config.reclaim.abandoned_block_gap else {
+            return Ok(vec![]);
+        };
+        let tip_block_number = tip_block_number(self.rpc_client.as_ref()).await?;
+        let relayer_lock = JsonScript::from(self.wallet.lock().clone());
+        let packet_lock = Script::new_builder()
+            .code_hash(get_script_hash(&self.config.packet_type_args))
+            .hash_type(ScriptHashType::Type.into())
+            .build();
+        let search_key = get_prefix_search_key(packet_lock);
+
+        let mut finished = vec![];
+        let mut cursor = None;
+        loop {
+            let cells = self
+                .rpc_client
+                .fetch_live_cells(search_key.clone(), CELLS_PAGE_SIZE, cursor)
+                .await?;
+            for cell in &cells.objects {
+                let block_number: u64 = cell.block_number.into();
+                if block_number.saturating_add(block_gap) > tip_block_number {
+                    continue;
+                }
+                let tx =
+                    fetch_transaction_by_hash(self.rpc_client.as_ref(), &cell.out_point.tx_hash)
+                        .await?;
+                // the change of the transaction tells whether this relayer paid for the cell
+                if !tx
+                    .inner
+                    .outputs
+                    .iter()
+                    .any(|output| output.lock == relayer_lock)
+                {
+                    continue;
+                }
+                let packet = get_envelope(&tx).and_then(|envelope| {
+                    let (packet, _) = extract_ibc_packet_from_tx(&tx)?;
+                    Ok((envelope.msg_type, packet))
+                });
+                let (packet, kind) = match packet {
+                    Ok((MsgType::MsgAckPacket, packet)) if packet.status == PacketStatus::Ack => {
+                        (packet, Finished::Acknowledged)
+                    }
+                    Ok((MsgType::MsgTimeoutPacket, packet)) => (packet, Finished::TimedOut),
+                    Ok(_) => continue,
+                    Err(err) => {
+                        warn!("skip packet cell of transaction {:#x}: {err}", tx.hash);
+                        continue;
+                    }
+                };
+                finished.push(FinishedPacketCell {
+                    packet,
+                    finished: kind,
+                    input: CellInput::new_builder()
+                        .previous_output(cell.out_point.clone().into())
+                        .build(),
+                    capacity: u64::from(cell.output.capacity),
+                });
+            }
+            if cells.objects.len() < CELLS_PAGE_SIZE as usize {
+                break;
+            }
+            cursor = Some(cells.last_cursor);
+        }
+        Ok(finished)
+    }
+
+    /// Returns the signed transaction consuming `cells` along with the capacity it pays back
+    fn assemble_reclaim_tx(
+        &self,
+        cells: &[FinishedPacketCell],
+    ) -> Result<(CoreTransactionView, u64), Error> {
+        let mut tx = TxBuilder::default().cell_dep(self.packet_outpoint.clone());
+        let mut input_capacity = 0;
+        for cell in cells {
+            let packet = get_encoded_object(&cell.packet);
+            tx = tx
+                .input(cell.input.clone())
+                .witness(packet.witness, BytesOpt::default());
+            input_capacity += cell.capacity;
+        }
+        let envelope = Envelope {
+            msg_type: MsgType::MsgConsumeAckPacket,
+            content: rlp::encode(&MsgConsumeAckPacket {}).to_vec(),
+            commitments: vec![],
+        };
+        let (tx, fee) = self.rt.block_on(self.wallet.complete_tx(
+            self.rpc_client.as_ref(),
+            tx.build(),
+            input_capacity,
+            Some(envelope),
+        ))?;
+        Ok((tx, input_capacity.saturating_sub(fee)))
+    }
+
+    fn send_and_wait(&self, tx: CoreTransactionView) -> Result<H256, Error> {
+        let tx: TransactionView = tx.into();
+        let tx_hash = match self
+            .rt
+            .block_on(self.rpc_client.send_transaction(&tx.inner, None))
+        {
+            Ok(tx_hash) => tx_hash,
+            Err(err) => {
+                self.wallet.release(&tx.hash);
+                return Err(err);
+            }
+        };
+        self.wallet.sent(&tx_hash);
+        let result = self.rt.block_on(wait_ckb_transaction_committed(
+            &self.rpc_client,
+            tx_hash.clone(),
+            Duration::from_secs(10),
+            1,
+            Duration::from_secs(600),
+        ));
+        self.wallet.release(&tx_hash);
+        result.map(|_| tx_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ckb_ics_axon::object::Packet as CkbPacket;
+    use ckb_ics_axon::PacketArgs;
+    use ckb_jsonrpc_types::JsonBytes;
+    use ckb_sdk::rpc::ckb_indexer::Cell;
+    use ckb_types::core::Capacity;
+    use ckb_types::packed::CellOutput;
+    use ckb_types::prelude::{Pack, Unpack};
+    use tendermint_rpc::Url;
+
+    use ckb_sdk::NetworkType;
+
+    use super::*;
+    use crate::config::ckb4ibc::ReclaimConfig;
+    use crate::config::AddressType;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+    const SECRET_KEY: &str = "e0ed6b4ef8ac7a4d5d3b5d3e1cf6ea42ff4e4a3ff6b1d5c1a7a7a4c3e0bb3b48";
+
+    const CKB_CAPACITY: u64 = 100_000_000;
+
+    fn config(abandoned_block_gap: Option<u64>) -> ChainConfig {
+        let url: Url = "http://127.0.0.1:8114".parse().unwrap();
+        ChainConfig {
+            id: "ckb4ibc-0".parse().unwrap(),
+            ckb_rpc: url.clone(),
+            ckb_indexer_rpc: url,
+            key_name: "relayer".to_owned(),
+            key_store_type: Default::default(),
+            store_prefix: "ibc".to_owned(),
+            client_code_hash: H256::default(),
+            connection_type_args: H256([1; 32]),
+            channel_type_args: H256([2; 32]),
+            packet_type_args: H256([3; 32]),
+            trusting_period: None,
+            reclaim: ReclaimConfig {
+                enabled: true,
+                block_gap: 15,
+                abandoned_block_gap,
+                batch_size: 2,
+                interval: Duration::from_secs(1),
+            },
+            wallet: Default::default(),
+            balance: Default::default(),
+            packet_filter: Default::default(),
+            onchain_light_clients: HashMap::new(),
+        }
+    }
+
+    fn reclaimer(abandoned_block_gap: Option<u64>) -> PacketReclaimer {
+        let key = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Ckb).unwrap();
+        PacketReclaimer::new(
+            Arc::new(TokioRuntime::new().unwrap()),
+            Arc::new(RpcClient::new(
+                &"http://127.0.0.1:8114".parse().unwrap(),
+                &"http://127.0.0.1:8116".parse().unwrap(),
+            )),
+            config(abandoned_block_gap),
+            Arc::new(Wallet::new(key, NetworkType::Dev)),
+            OutPoint::default(),
+        )
+    }
+
+    /// Commits a `msg_type` transaction creating the packet cell of `status`, with a change
+    /// cell locked by `change_lock`
+    fn commit_packet_cell(
+        reclaimer: &PacketReclaimer,
+        sequence: u64,
+        msg_type: MsgType,
+        status: PacketStatus,
+        change_lock: Script,
+    ) {
+        let packet = IbcPacket {
+            packet: CkbPacket {
+                sequence,
+                source_port_id: "transfer".to_owned(),
+                source_channel_id: "channel-0".to_owned(),
+                destination_port_id: "transfer".to_owned(),
+                destination_channel_id: "channel-1".to_owned(),
+                data: vec![],
+                timeout_height: 0,
+                timeout_timestamp: 0,
+            },
+            status,
+            ack: Some(vec![1]),
+        };
+        let packet_lock = Script::new_builder()
+            .code_hash(get_script_hash(&reclaimer.config.packet_type_args))
+            .hash_type(ScriptHashType::Type.into())
+            .args(
+                PacketArgs {
+                    channel_id: 0,
+                    port_id: [0; 32],
+                    sequence,
+                }
+                .to_args()
+                .pack(),
+            )
+            .build();
+        let packet_object = get_encoded_object(&packet);
+        let envelope = Envelope {
+            msg_type,
+            content: vec![],
+            commitments: vec![],
+        };
+        let change = CellOutput::new_builder()
+            .lock(change_lock)
+            .build_exact_capacity(Capacity::zero())
+            .unwrap();
+        let tx = TxBuilder::default()
+            .output(Script::default(), Default::default())
+            .output(packet_lock, packet_object.data)
+            .witness(BytesOpt::default(), BytesOpt::default())
+            .witness(BytesOpt::default(), packet_object.witness)
+            .build()
+            .as_advanced_builder()
+            .output(change)
+            .output_data(Default::default())
+            .witness(
+                ckb_types::packed::WitnessArgs::new_builder()
+                    .output_type(get_encoded_object(&envelope).witness)
+                    .build()
+                    .as_bytes()
+                    .pack(),
+            )
+            .build();
+        let tx: TransactionView = tx.into();
+        reclaimer
+            .rt
+            .block_on(reclaimer.rpc_client.send_transaction(&tx.inner, None))
+            .unwrap();
+    }
+
+    fn add_relayer_capacity(reclaimer: &PacketReclaimer) {
+        reclaimer.rpc_client.add_cell(Cell {
+            output: CellOutput::new_builder()
+                .lock(reclaimer.wallet.lock().clone())
+                .capacity((1000 * CKB_CAPACITY).pack())
+                .build()
+                .into(),
+            output_data: Some(JsonBytes::default()),
+            out_point: OutPoint::new_builder()
+                .tx_hash(H256([9; 32]).pack())
+                .build()
+                .into(),
+            block_number: 0.into(),
+            tx_index: 0.into(),
+        });
+    }
+
+    fn live_packet_cells(reclaimer: &PacketReclaimer) -> usize {
+        let packet_lock = Script::new_builder()
+            .code_hash(get_script_hash(&reclaimer.config.packet_type_args))
+            .hash_type(ScriptHashType::Type.into())
+            .build();
+        reclaimer
+            .rt
+            .block_on(reclaimer.rpc_client.fetch_live_cells(
+                get_prefix_search_key(packet_lock),
+                CELLS_PAGE_SIZE,
+                None,
+            ))
+            .unwrap()
+            .objects
+            .len()
+    }
+
+    fn finished_sequences(reclaimer: &PacketReclaimer) -> Vec<(u64, Finished)> {
+        reclaimer
+            .rt
+            .block_on(reclaimer.finished_packet_cells())
+            .unwrap()
+            .into_iter()
+            .map(|cell| (cell.packet.packet.sequence, cell.finished))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_finished_packet_cells() {
+        let reclaimer = reclaimer(Some(5));
+        let relayer_lock = reclaimer.wallet.lock().clone();
+        let ack = |sequence, status, lock| {
+            commit_packet_cell(&reclaimer, sequence, MsgType::MsgAckPacket, status, lock)
+        };
+        ack(1, PacketStatus::Ack, relayer_lock.clone());
+        commit_packet_cell(
+            &reclaimer,
+            5,
+            MsgType::MsgTimeoutPacket,
+            PacketStatus::Send,
+            relayer_lock.clone(),
+        );
+        commit_packet_cell(
+            &reclaimer,
+            2,
+            MsgType::MsgRecvPacket,
+            PacketStatus::WriteAck,
+            relayer_lock.clone(),
+        );
+        // paid by somebody else
+        ack(3, PacketStatus::Ack, Script::default());
+
+        // too recent to be considered abandoned
+        ack(4, PacketStatus::Ack, relayer_lock);
+        assert_eq!(
+            finished_sequences(&reclaimer),
+            vec![(1, Finished::Acknowledged), (5, Finished::TimedOut)]
+        );
+
+        reclaimer.rpc_client.produce_empty_blocks(5);
+        assert_eq!(
+            finished_sequences(&reclaimer),
+            vec![
+                (1, Finished::Acknowledged),
+                (5, Finished::TimedOut),
+                (4, Finished::Acknowledged)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_packet_cells_without_abandoned_gap() {
+        let reclaimer = reclaimer(None);
+        let relayer_lock = reclaimer.wallet.lock().clone();
+        commit_packet_cell(
+            &reclaimer,
+            1,
+            MsgType::MsgAckPacket,
+            PacketStatus::Ack,
+            relayer_lock,
+        );
+        reclaimer.rpc_client.produce_empty_blocks(100);
+        assert!(finished_sequences(&reclaimer).is_empty());
+        assert!(reclaimer.reclaim().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_consume_finished_packet_cells() {
+        let reclaimer = reclaimer(Some(0));
+        add_relayer_capacity(&reclaimer);
+        let relayer_lock = reclaimer.wallet.lock().clone();
+        for sequence in 1..=3 {
+            commit_packet_cell(
+                &reclaimer,
+                sequence,
+                MsgType::MsgAckPacket,
+                PacketStatus::Ack,
+                relayer_lock.clone(),
+            );
+        }
+        assert_eq!(live_packet_cells(&reclaimer), 3);
+
+        let cells = reclaimer
+            .rt
+            .block_on(reclaimer.finished_packet_cells())
+            .unwrap();
+        let (tx, capacity) = reclaimer.assemble_reclaim_tx(&cells[..2]).unwrap();
+        assert_eq!(tx.outputs().len(), 1);
+        assert_eq!(tx.output(0).unwrap().lock(), relayer_lock);
+        let change: u64 = tx.output(0).unwrap().capacity().unpack();
+        assert!(change >= capacity);
+
+        // every witness of the packet inputs carries the consumed packet
+        for (witness, cell) in tx.witnesses().into_iter().zip(&cells[..2]) {
+            let witness = ckb_types::packed::WitnessArgs::from_slice(&witness.raw_data()).unwrap();
+            assert_eq!(
+                witness.input_type().to_opt().unwrap().raw_data(),
+                rlp::encode(&cell.packet).as_ref()
+            );
+        }
+
+        let tx: TransactionView = tx.into();
+        reclaimer
+            .rt
+            .block_on(reclaimer.rpc_client.send_transaction(&tx.inner, None))
+            .unwrap();
+        assert_eq!(live_packet_cells(&reclaimer), 1);
+    }
+}
@@ -0,0 +1,540 @@
+//! Checks the IBC transactions sent to the CKB simulator the way the
+//! on-chain connection and channel contracts do.
+
+use ckb_ics_axon::handler::{
+    handle_msg_ack_packet, handle_msg_channel_close_confirm, handle_msg_channel_close_init,
+    handle_msg_channel_open_ack, handle_msg_channel_open_confirm, handle_msg_channel_open_init,
+    handle_msg_channel_open_try, handle_msg_connection_open_ack,
+    handle_msg_connection_open_confirm, handle_msg_connection_open_init,
+    handle_msg_connection_open_try, handle_msg_recv_packet, handle_msg_send_packet,
+    handle_msg_timeout_packet, IbcChannel, IbcConnections, IbcPacket,
+};
+use ckb_ics_axon::message::{Envelope, MsgType};
+use ckb_ics_axon::{ChannelArgs, ConnectionArgs, PacketArgs};
+use ckb_types::bytes::Bytes;
+use ckb_types::core::TransactionView;
+use ckb_types::packed::{CellOutput, WitnessArgs};
+use ckb_types::prelude::Entity;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawTmHeader;
+use ibc_proto::protobuf::Protobuf;
+use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc_relayer_types::core::ics02_client::header::Header;
+
+use super::message::EmptyClient;
+use super::utils::keccak256;
+use crate::chain::ckb::rpc_client::ScriptVerifier;
+use crate::client_state::AnyClientState;
+
+/// Verifies the object witnesses against the cell data and replays the
+/// handler of the envelope message, whose commitments must match the envelope.
+///
+/// Transactions without an envelope, such as client creations, and the write
+/// acknowledgements of the applications only get the witness checks, client
+/// updates get the checks of the Tendermint client cell.
+pub struct Ckb4IbcVerifier;
+
+struct Objects<'a> {
+    tx: &'a TransactionView,
+    inputs: &'a [(CellOutput, Bytes)],
+    witnesses: Vec<WitnessArgs>,
+}
+
+impl<'a> Objects<'a> {
+    fn input<T: rlp::Decodable>(&self, index: usize) -> Result<T, String> {
+        let witness = self
+            .witnesses
+            .get(index)
+            .and_then(|w| w.input_type().to_opt());
+        let object = witness.ok_or(format!("missing input object #{index}"))?;
+        rlp::decode(&object.raw_data()).map_err(|e| format!("input object #{index}: {e}"))
+    }
+
+    fn output<T: rlp::Decodable>(&self, index: usize) -> Result<T, String> {
+        let witness = self
+            .witnesses
+            .get(index)
+            .and_then(|w| w.output_type().to_opt());
+        let object = witness.ok_or(format!("missing output object #{index}"))?;
+        rlp::decode(&object.raw_data()).map_err(|e| format!("output object #{index}: {e}"))
+    }
+
+    fn input_lock_args(&self, index: usize) -> Result<Bytes, String> {
+        self.inputs
+            .get(index)
+            .map(|(output, _)| output.lock().args().raw_data())
+            .ok_or(format!("missing input #{index}"))
+    }
+
+    fn output_lock_args(&self, index: usize) -> Result<Bytes, String> {
+        self.tx
+            .output(index)
+            .map(|output| output.lock().args().raw_data())
+            .ok_or(format!("missing output #{index}"))
+    }
+
+    fn connection_args(&self, args: Bytes) -> Result<ConnectionArgs, String> {
+        ConnectionArgs::from_slice(&args).map_err(|_| "invalid connection args".to_owned())
+    }
+
+    fn channel_args(&self, args: Bytes) -> Result<ChannelArgs, String> {
+        ChannelArgs::from_slice(&args).map_err(|_| "invalid channel args".to_owned())
+    }
+
+    fn packet_args(&self, args: Bytes) -> Result<PacketArgs, String> {
+        PacketArgs::from_slice(&args).map_err(|_| "invalid packet args".to_owned())
+    }
+
+    /// The objects carried by the witnesses must be the preimages of the cell data
+    fn check_hashes(&self) -> Result<(), String> {
+        for (index, witness) in self.witnesses.iter().enumerate() {
+            if let Some(object) = witness.input_type().to_opt() {
+                let data = self.inputs.get(index).map(|(_, data)| data.as_ref());
+                if data != Some(&keccak256(&object.raw_data())[..]) {
+                    return Err(format!("input object #{index} doesn't match the cell data"));
+                }
+            }
+            if let Some(object) = witness.output_type().to_opt() {
+                let data = self
+                    .tx
+                    .outputs_data()
+                    .get(index)
+                    .map(|data| data.raw_data());
+                if data.as_deref() != Some(&keccak256(&object.raw_data())[..]) {
+                    return Err(format!(
+                        "output object #{index} doesn't match the cell data"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The type id script of the Tendermint client cell only lets it be recreated, and the
+/// recreated cell must keep a client state at least as high as the header
+fn check_client_update(objects: &Objects, content: &[u8]) -> Result<(), String> {
+    let (input, _) = objects.inputs.first().ok_or("missing client cell")?;
+    let output = objects.tx.output(0).ok_or("missing new client cell")?;
+    if input.type_().is_none() || input.type_().as_slice() != output.type_().as_slice() {
+        return Err("the client cell must keep its type script".to_owned());
+    }
+    let header = <TmHeader as Protobuf<RawTmHeader>>::decode_vec(content)
+        .map_err(|e| format!("message content: {e}"))?;
+    let data = objects.tx.outputs_data().get(0).unwrap_or_default();
+    let client_state = <AnyClientState as Protobuf<Any>>::decode_vec(&data.raw_data())
+        .map_err(|e| format!("client cell data: {e}"))?;
+    if client_state.latest_height() < header.height() {
+        return Err("the client state is behind the header".to_owned());
+    }
+    Ok(())
+}
+
+macro_rules! decode_msg {
+    ($content:expr) => {
+        rlp::decode($content).map_err(|e| format!("message content: {e}"))?
+    };
+}
+
+impl ScriptVerifier for Ckb4IbcVerifier {
+    fn verify(&self, tx: &TransactionView, inputs: &[(CellOutput, Bytes)]) -> Result<(), String> {
+        let witnesses = tx
+            .witnesses()
+            .into_iter()
+            .map(|witness| WitnessArgs::from_slice(&witness.raw_data()))
+            .collect::<Result<Vec<_>, _>>();
+        // not an IBC transaction
+        let Ok(mut witnesses) = witnesses else {
+            return Ok(());
+        };
+        let Some(received) = witnesses
+            .last()
+            .and_then(|witness| witness.output_type().to_opt())
+            .map(|envelope| envelope.raw_data())
+        else {
+            return Ok(());
+        };
+        let Ok(envelope) = rlp::decode::<Envelope>(&received) else {
+            return Ok(());
+        };
+        // the secp256k1 placeholder and the envelope
+        witnesses.truncate(witnesses.len().saturating_sub(2));
+        let objects = Objects {
+            tx,
+            inputs,
+            witnesses,
+        };
+        objects.check_hashes()?;
+
+        let (msg_type, content) = (&envelope.msg_type, &envelope.content);
+        let mut commitments = vec![];
+        let result = match msg_type {
+            MsgType::MsgConnectionOpenInit
+            | MsgType::MsgConnectionOpenTry
+            | MsgType::MsgConnectionOpenAck
+            | MsgType::MsgConnectionOpenConfirm => {
+                let old: IbcConnections = objects.input(0)?;
+                let new: IbcConnections = objects.output(0)?;
+                let old_args = objects.connection_args(objects.input_lock_args(0)?)?;
+                let new_args = objects.connection_args(objects.output_lock_args(0)?)?;
+                match msg_type {
+                    MsgType::MsgConnectionOpenInit => handle_msg_connection_open_init(
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                    ),
+                    MsgType::MsgConnectionOpenTry => handle_msg_connection_open_try(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                    MsgType::MsgConnectionOpenAck => handle_msg_connection_open_ack(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                    _ => handle_msg_connection_open_confirm(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                }
+            }
+            MsgType::MsgChannelOpenInit | MsgType::MsgChannelOpenTry => {
+                let old: IbcConnections = objects.input(0)?;
+                let new: IbcConnections = objects.output(0)?;
+                let old_args = objects.connection_args(objects.input_lock_args(0)?)?;
+                let new_args = objects.connection_args(objects.output_lock_args(0)?)?;
+                let channel: IbcChannel = objects.output(1)?;
+                let channel_args = objects.channel_args(objects.output_lock_args(1)?)?;
+                if matches!(msg_type, MsgType::MsgChannelOpenInit) {
+                    handle_msg_channel_open_init(
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        channel,
+                        channel_args,
+                        &mut commitments,
+                    )
+                } else {
+                    handle_msg_channel_open_try(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        channel,
+                        channel_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    )
+                }
+            }
+            MsgType::MsgChannelOpenAck
+            | MsgType::MsgChannelOpenConfirm
+            | MsgType::MsgChannelCloseInit
+            | MsgType::MsgChannelCloseConfirm => {
+                let old: IbcChannel = objects.input(0)?;
+                let new: IbcChannel = objects.output(0)?;
+                let old_args = objects.channel_args(objects.input_lock_args(0)?)?;
+                let new_args = objects.channel_args(objects.output_lock_args(0)?)?;
+                match msg_type {
+                    MsgType::MsgChannelOpenAck => handle_msg_channel_open_ack(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                    MsgType::MsgChannelOpenConfirm => handle_msg_channel_open_confirm(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                    MsgType::MsgChannelCloseInit => handle_msg_channel_close_init(
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                    ),
+                    _ => handle_msg_channel_close_confirm(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    ),
+                }
+            }
+            MsgType::MsgSendPacket | MsgType::MsgRecvPacket => {
+                let old: IbcChannel = objects.input(0)?;
+                let new: IbcChannel = objects.output(0)?;
+                let old_args = objects.channel_args(objects.input_lock_args(0)?)?;
+                let new_args = objects.channel_args(objects.output_lock_args(0)?)?;
+                let packet: IbcPacket = objects.output(1)?;
+                let packet_args = objects.packet_args(objects.output_lock_args(1)?)?;
+                if matches!(msg_type, MsgType::MsgSendPacket) {
+                    handle_msg_send_packet(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        packet,
+                        packet_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    )
+                } else {
+                    // the write acknowledged packet consumed to save capacity, if any, the
+                    // other inputs pay for the transaction
+                    let useless_packet = match objects.witnesses.get(1) {
+                        Some(witness) if witness.input_type().to_opt().is_some() => {
+                            Some(objects.input(1)?)
+                        }
+                        _ => None,
+                    };
+                    handle_msg_recv_packet(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        useless_packet,
+                        packet,
+                        packet_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    )
+                }
+            }
+            MsgType::MsgAckPacket | MsgType::MsgTimeoutPacket => {
+                let old: IbcChannel = objects.input(0)?;
+                let new: IbcChannel = objects.output(0)?;
+                let old_args = objects.channel_args(objects.input_lock_args(0)?)?;
+                let new_args = objects.channel_args(objects.output_lock_args(0)?)?;
+                let old_packet: IbcPacket = objects.input(1)?;
+                let new_packet: IbcPacket = objects.output(1)?;
+                let old_packet_args = objects.packet_args(objects.input_lock_args(1)?)?;
+                let new_packet_args = objects.packet_args(objects.output_lock_args(1)?)?;
+                if matches!(msg_type, MsgType::MsgAckPacket) {
+                    handle_msg_ack_packet(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        old_packet,
+                        old_packet_args,
+                        new_packet,
+                        new_packet_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    )
+                } else {
+                    handle_msg_timeout_packet(
+                        EmptyClient,
+                        old,
+                        old_args,
+                        new,
+                        new_args,
+                        old_packet,
+                        old_packet_args,
+                        new_packet,
+                        new_packet_args,
+                        &mut commitments,
+                        decode_msg!(content),
+                    )
+                }
+            }
+            MsgType::MsgClientUpdate => return check_client_update(&objects, content),
+            _ => return Ok(()),
+        };
+        result.map_err(|err| format!("handle error: {}", err as i8))?;
+
+        let expected = Envelope {
+            commitments,
+            ..envelope
+        };
+        if rlp::encode(&expected)[..] != received[..] {
+            return Err("commitments of the envelope don't match the message".to_owned());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ckb_ics_axon::message::MsgConnectionOpenInit;
+    use ckb_ics_axon::object::{ConnectionCounterparty, ConnectionEnd, State};
+    use ckb_jsonrpc_types::Transaction;
+    use ckb_sdk::rpc::ckb_indexer::Cell;
+    use ckb_types::core::{Capacity, ScriptHashType};
+    use ckb_types::packed;
+    use ckb_types::prelude::{Builder, Pack};
+    use tendermint_rpc::Url;
+
+    use super::*;
+    use crate::chain::ckb::prelude::CkbWriter;
+    use crate::chain::ckb::rpc_client::RpcClient;
+    use crate::chain::ckb4ibc::utils::get_encoded_object;
+
+    fn simulator() -> RpcClient {
+        let url: Url = "http://127.0.0.1:8114".parse().unwrap();
+        let client = RpcClient::new(&url, &url);
+        client.add_verifier(Ckb4IbcVerifier);
+        client
+    }
+
+    fn connection_cell(client: &RpcClient, lock: packed::Script, connections: &IbcConnections) {
+        let output = packed::CellOutput::new_builder()
+            .lock(lock)
+            .build_exact_capacity(Capacity::bytes(1000).unwrap())
+            .unwrap();
+        let data = get_encoded_object(connections).data;
+        client.add_cell(Cell {
+            output: output.into(),
+            output_data: Some(data.into()),
+            out_point: packed::OutPoint::new_builder()
+                .tx_hash([7u8; 32].pack())
+                .build()
+                .into(),
+            block_number: 0u64.into(),
+            tx_index: 0u32.into(),
+        });
+    }
+
+    /// A connection open init transaction, the commitments of the handler are
+    /// dropped from the envelope if `tamper` is set
+    fn connection_open_init_tx(
+        lock: packed::Script,
+        old: &IbcConnections,
+        new: &IbcConnections,
+        tamper: bool,
+    ) -> Transaction {
+        let args = || ConnectionArgs::from_slice(&lock.args().raw_data()).unwrap();
+        let mut commitments = vec![];
+        handle_msg_connection_open_init(old.clone(), args(), new.clone(), args(), &mut commitments)
+            .unwrap();
+        if tamper {
+            commitments.clear();
+        }
+        let envelope = Envelope {
+            msg_type: MsgType::MsgConnectionOpenInit,
+            content: rlp::encode(&MsgConnectionOpenInit {}).to_vec(),
+            commitments,
+        };
+        let old = get_encoded_object(old);
+        let new = get_encoded_object(new);
+        let object = WitnessArgs::new_builder()
+            .input_type(old.witness)
+            .output_type(new.witness)
+            .build();
+        let envelope = WitnessArgs::new_builder()
+            .output_type(get_encoded_object(&envelope).witness)
+            .build();
+        TransactionView::new_advanced_builder()
+            .input(
+                packed::CellInput::new_builder()
+                    .previous_output(
+                        packed::OutPoint::new_builder()
+                            .tx_hash([7u8; 32].pack())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .output(
+                packed::CellOutput::new_builder()
+                    .lock(lock)
+                    .build_exact_capacity(Capacity::bytes(new.data.len()).unwrap())
+                    .unwrap(),
+            )
+            .output_data(new.data)
+            .witness(object.as_bytes().pack())
+            .witness(WitnessArgs::new_builder().build().as_bytes().pack())
+            .witness(envelope.as_bytes().pack())
+            .build()
+            .data()
+            .into()
+    }
+
+    fn setup() -> (RpcClient, packed::Script, IbcConnections, IbcConnections) {
+        let client = simulator();
+        let args = ConnectionArgs {
+            metadata_type_id: [1u8; 32],
+            ibc_handler_address: [2u8; 20],
+        };
+        let lock = packed::Script::new_builder()
+            .code_hash([3u8; 32].pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(args.encode().pack())
+            .build();
+        let old = IbcConnections::default();
+        let mut new = old.clone();
+        new.connections.push(ConnectionEnd {
+            state: State::Init,
+            counterparty: ConnectionCounterparty {
+                client_id: "07-tendermint-0".to_owned(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        (client, lock, old, new)
+    }
+
+    #[test]
+    fn test_accept_connection_open_init() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (client, lock, old, new) = setup();
+        connection_cell(&client, lock.clone(), &old);
+        let tx = connection_open_init_tx(lock, &old, &new, false);
+        rt.block_on(client.send_transaction(&tx, None)).unwrap();
+        assert_eq!(client.get_transactions_len(), 1);
+    }
+
+    #[test]
+    fn test_reject_mismatched_commitments() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (client, lock, old, new) = setup();
+        connection_cell(&client, lock.clone(), &old);
+        let tx = connection_open_init_tx(lock, &old, &new, true);
+        let err = rt.block_on(client.send_transaction(&tx, None)).unwrap_err();
+        assert!(err.to_string().contains("commitments"));
+        assert_eq!(client.get_transactions_len(), 0);
+    }
+
+    #[test]
+    fn test_reject_mismatched_cell_data() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (client, lock, old, new) = setup();
+        // the input object isn't the one stored in the cell
+        connection_cell(&client, lock.clone(), &new);
+        let tx = connection_open_init_tx(lock, &old, &new, false);
+        let err = rt.block_on(client.send_transaction(&tx, None)).unwrap_err();
+        assert!(err.to_string().contains("cell data"));
+    }
+}
@@ -0,0 +1,927 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+
+use ckb_ics_axon::handler::{
+    handle_msg_send_packet, handle_msg_timeout_packet, IbcChannel, IbcPacket, PacketStatus,
+};
+use ckb_ics_axon::message::{
+    Envelope, MsgSendPacket as CkbMsgSendPacket, MsgTimeoutPacket as CkbMsgTimeoutPacket, MsgType,
+};
+use ckb_ics_axon::object::Packet as CkbPacket;
+use ckb_ics_axon::{ChannelArgs, PacketArgs};
+use ckb_jsonrpc_types::{JsonBytes, TransactionView};
+use ckb_sdk::{constants::TYPE_ID_CODE_HASH, rpc::ckb_indexer::Cell, NetworkType};
+use ckb_types::{
+    core::{Capacity, ScriptHashType},
+    packed,
+    prelude::*,
+    H160, H256,
+};
+use ethers::types::Bytes;
+use ibc_proto::{google::protobuf::Any, protobuf::Protobuf};
+use ibc_relayer_types::{
+    clients::{
+        ics07_axon::client_state::AxonClientState,
+        ics07_ckb::{client_state::CkbClientState, consensus_state::CkbConsensusState},
+        ics07_tendermint::{
+            client_state::test_util::get_dummy_tendermint_client_state,
+            consensus_state::ConsensusState as TmConsensusState,
+            header::{test_util::get_dummy_ics07_header, Header as TmHeader},
+        },
+    },
+    core::{
+        ics02_client::{
+            client_type::ClientType,
+            header::Header,
+            msgs::{create_client::MsgCreateClient, update_client::MsgUpdateClient},
+        },
+        ics03_connection::{
+            connection::{Counterparty, State},
+            msgs::{
+                conn_open_confirm::MsgConnectionOpenConfirm, conn_open_try::MsgConnectionOpenTry,
+            },
+            version::Version,
+        },
+        ics04_channel::{
+            channel::{
+                ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+            },
+            msgs::{
+                acknowledgement::MsgAcknowledgement, chan_open_confirm::MsgChannelOpenConfirm,
+                chan_open_try::MsgChannelOpenTry, recv_packet::MsgRecvPacket,
+                timeout_on_close::MsgTimeoutOnClose,
+            },
+            packet::{Packet, PacketMsgType, Sequence},
+            timeout::TimeoutHeight,
+            version::Version as ChannelVersion,
+        },
+        ics23_commitment::commitment::CommitmentProofBytes,
+        ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
+    },
+    events::IbcEvent,
+    proofs::{ConsensusProof, Proofs},
+    timestamp::Timestamp,
+    tx_msg::Msg,
+    Height,
+};
+use tendermint::Hash as TxHash;
+use tendermint_rpc::Url;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use super::{
+    message::{convert_msg_to_ckb_tx, CkbTxInfo, EmptyClient, MsgToTxConverter, TxBuilder},
+    simulator::Ckb4IbcVerifier,
+    utils::{get_channel_lock_script, get_encoded_object, get_packet_lock_script, keccak256},
+    wallet::Wallet,
+    Ckb4IbcChain,
+};
+use crate::{
+    chain::{
+        axon::{
+            contract::{GetClientStateCall, GetConsensusStateCall},
+            tests::setup as setup_axon,
+        },
+        ckb::{prelude::CkbWriter, rpc_client::RpcClient},
+        endpoint::ChainEndpoint,
+        handle::BaseChainHandle,
+        requests::{
+            IncludeProof, QueryChannelRequest, QueryConnectionRequest, QueryConsensusStateRequest,
+            QueryHeight, QueryHostConsensusStateRequest, QueryTxHash, QueryTxRequest,
+        },
+        runtime::ChainRuntime,
+        tracking::TrackedMsgs,
+    },
+    client_state::AnyClientState,
+    config::{
+        ckb4ibc::{ChainConfig, LightClientItem},
+        AddressType,
+    },
+    consensus_state::AnyConsensusState,
+    foreign_client::ForeignClient,
+    keyring::{Secp256k1KeyPair, SigningKeyPair, Store},
+};
+
+/// Time of the genesis block of the CKB simulator
+const GENESIS_TIMESTAMP: Duration = Duration::from_millis(1_600_000_000_000);
+
+/// Secret key of the relayer wallet
+const SECRET_KEY: &str = "e0ed6b4ef8ac7a4d5d3b5d3e1cf6ea42ff4e4a3ff6b1d5c1a7a7a4c3e0bb3b48";
+
+const CHAIN_INFO: &str = r#"
+    {
+      "alerts": [],
+      "chain": "ckb-dev",
+      "difficulty": "0x10000",
+      "epoch": "0x100",
+      "is_initial_block_download": false,
+      "median_time": "0x5cd2b105"
+    }"#;
+
+/// A chain whose only light client is `client_type`, tracking `axon-0` or `ibc-0`
+pub(crate) fn config(client_type: ClientType) -> ChainConfig {
+    let url: Url = "http://127.0.0.1:8114".parse().unwrap();
+    let chain_id = match client_type {
+        ClientType::Tendermint => "ibc-0",
+        _ => "axon-0",
+    };
+    let light_client = LightClientItem {
+        chain_id: chain_id.parse().unwrap(),
+        client_cell_type_args: H256([4; 32]),
+        ibc_handler_address: H160([5; 20]),
+    };
+    ChainConfig {
+        id: "ckb4ibc-0".parse().unwrap(),
+        ckb_rpc: url.clone(),
+        ckb_indexer_rpc: url,
+        key_name: "relayer".to_owned(),
+        key_store_type: Store::Memory,
+        store_prefix: "ibc".to_owned(),
+        client_code_hash: H256([6; 32]),
+        connection_type_args: H256([1; 32]),
+        channel_type_args: H256([2; 32]),
+        packet_type_args: H256([3; 32]),
+        trusting_period: None,
+        reclaim: Default::default(),
+        wallet: Default::default(),
+        balance: Default::default(),
+        packet_filter: Default::default(),
+        onchain_light_clients: HashMap::from([(client_type, light_client)]),
+    }
+}
+
+/// A live cell of type `code_hash` and `args`, stored in a transaction of its own
+pub(crate) fn type_cell(
+    index: u8,
+    lock: packed::Script,
+    code_hash: &H256,
+    args: &H256,
+    data: Vec<u8>,
+) -> Cell {
+    let type_script = packed::Script::new_builder()
+        .code_hash(code_hash.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(args.as_bytes().pack())
+        .build();
+    let output = packed::CellOutput::new_builder()
+        .lock(lock)
+        .type_(Some(type_script).pack())
+        .build_exact_capacity(Capacity::bytes(data.len()).unwrap())
+        .unwrap();
+    Cell {
+        output: output.into(),
+        output_data: Some(data.pack().into()),
+        out_point: packed::OutPoint::new_builder()
+            .tx_hash([index; 32].pack())
+            .build()
+            .into(),
+        block_number: 0u64.into(),
+        tx_index: 0u32.into(),
+    }
+}
+
+/// A chain on the CKB simulator checking its IBC transactions, whose light client cell
+/// of `client_type` keeps `client_data` and is locked by the funded relayer wallet
+pub(crate) fn setup(
+    client_type: ClientType,
+    client_data: Vec<u8>,
+) -> (Arc<RpcClient>, Ckb4IbcChain) {
+    let config = config(client_type);
+    let rpc_client = Arc::new(RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc));
+    rpc_client.set_blockchain_info(Some(CHAIN_INFO));
+    rpc_client.add_verifier(Ckb4IbcVerifier);
+
+    let key = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Ckb).unwrap();
+    let wallet_lock = Wallet::new(key.clone(), NetworkType::Dev).lock().clone();
+    let type_id = H256(TYPE_ID_CODE_HASH.0);
+    let contract_lock = packed::Script::default();
+    for (index, args) in [
+        &config.connection_type_args,
+        &config.channel_type_args,
+        &config.packet_type_args,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let cell = type_cell(
+            index as u8 + 1,
+            contract_lock.clone(),
+            &type_id,
+            args,
+            vec![],
+        );
+        rpc_client.add_cell(cell);
+    }
+    let light_client = &config.onchain_light_clients[&client_type];
+    rpc_client.add_cell(type_cell(
+        4,
+        wallet_lock.clone(),
+        &config.client_code_hash,
+        &light_client.client_cell_type_args,
+        client_data,
+    ));
+    rpc_client.add_cell(Cell {
+        output: packed::CellOutput::new_builder()
+            .lock(wallet_lock)
+            .capacity(Capacity::bytes(100_000).unwrap().pack())
+            .build()
+            .into(),
+        output_data: Some(JsonBytes::default()),
+        out_point: packed::OutPoint::new_builder()
+            .tx_hash([5; 32].pack())
+            .build()
+            .into(),
+        block_number: 0u64.into(),
+        tx_index: 0u32.into(),
+    });
+
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+    let mut chain = Ckb4IbcChain::with_rpc_client(config, rt, rpc_client.clone()).unwrap();
+    let key_name = chain.config.key_name.clone();
+    chain.keybase_mut().add_key(&key_name, key).unwrap();
+    (rpc_client, chain)
+}
+
+fn axon_client_state(frozen: bool) -> AxonClientState {
+    let latest_height = Height::new(0, 10).unwrap();
+    let client_state = AxonClientState::new("axon-0".parse().unwrap(), latest_height)
+        .with_trusting_period(Some(Duration::from_secs(3600)));
+    if frozen {
+        client_state.with_frozen_height(Height::new(0, 8).unwrap())
+    } else {
+        client_state
+    }
+}
+
+fn encode(client_state: &AnyClientState) -> Vec<u8> {
+    <AnyClientState as Protobuf<Any>>::encode_vec(client_state).unwrap()
+}
+
+#[test]
+fn test_query_onchain_axon_client_state() {
+    let client_state = AnyClientState::Axon(axon_client_state(true));
+    let (_, chain) = setup(ClientType::Axon, encode(&client_state));
+    assert_eq!(
+        chain.query_onchain_client_state(ClientType::Axon).unwrap(),
+        client_state
+    );
+
+    // the cells not keeping a client state are reported instead of read as empty ones
+    let (_, chain) = setup(ClientType::Axon, vec![1, 2, 3]);
+    assert!(chain.query_onchain_client_state(ClientType::Axon).is_err());
+}
+
+#[test]
+fn test_query_light_block_and_consensus_state() {
+    let (rpc_client, mut chain) = setup(
+        ClientType::Axon,
+        encode(&AnyClientState::Axon(axon_client_state(false))),
+    );
+    rpc_client.produce_empty_blocks(10);
+    let tip = Height::from_noncosmos_height(rpc_client.tip_block_number());
+
+    let light_block = chain
+        .query_light_block(QueryHeight::Specific(Height::from_noncosmos_height(5)))
+        .unwrap();
+    assert_eq!(light_block.height, Height::from_noncosmos_height(5));
+    // the blocks of the simulator are 8 seconds apart
+    let timestamp = GENESIS_TIMESTAMP + Duration::from_secs(40);
+    assert_eq!(
+        light_block.timestamp,
+        Timestamp::from_nanoseconds(timestamp.as_nanos() as u64).unwrap()
+    );
+    let latest = chain.query_light_block(QueryHeight::Latest).unwrap();
+    assert_eq!(latest.height, tip);
+    assert_ne!(latest.block_hash, light_block.block_hash);
+
+    let consensus_state = chain
+        .query_host_consensus_state(QueryHostConsensusStateRequest {
+            height: QueryHeight::Specific(light_block.height),
+        })
+        .unwrap();
+    assert_eq!(
+        consensus_state,
+        CkbConsensusState::from(light_block.clone())
+    );
+    // the consensus states of the Axon client are kept by its maintainer only
+    assert!(chain
+        .query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: chain.config.lc_client_id(ClientType::Axon).unwrap(),
+                consensus_height: Height::new(0, 10).unwrap(),
+                query_height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .is_err());
+
+    let client_state = AnyClientState::Axon(axon_client_state(false));
+    let verified = chain
+        .verify_header(
+            Height::from_noncosmos_height(1),
+            light_block.height,
+            &client_state,
+        )
+        .unwrap();
+    assert_eq!(verified.block_hash, light_block.block_hash);
+    assert!(chain
+        .verify_header(
+            Height::from_noncosmos_height(1),
+            tip.increment(),
+            &client_state
+        )
+        .is_err());
+}
+
+#[test]
+fn test_query_unknown_tx() {
+    let (_, chain) = setup(
+        ClientType::Axon,
+        encode(&AnyClientState::Axon(axon_client_state(false))),
+    );
+    // the packet transactions sent without waiting may not be known to the node yet
+    let request = QueryTxRequest::Transaction(QueryTxHash(TxHash::Sha256([9; 32])));
+    assert!(chain.query_txs(request).unwrap().is_empty());
+}
+
+#[test]
+fn test_foreign_client_reads_frozen_axon_client_on_ckb() {
+    let (_node, axon) = setup_axon();
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+    let (axon, _) = ChainRuntime::init::<BaseChainHandle>(axon, rt.clone());
+    for frozen in [true, false] {
+        let client_state = AnyClientState::Axon(axon_client_state(frozen));
+        let (_, ckb) = setup(ClientType::Axon, encode(&client_state));
+        let client_id = ckb.config.lc_client_id(ClientType::Axon).unwrap();
+        let (ckb, _) = ChainRuntime::init::<BaseChainHandle>(ckb, rt.clone());
+
+        let client = ForeignClient::restore(client_id, ckb, axon.clone());
+        assert_eq!(client.is_expired_or_frozen(), frozen);
+        if !frozen {
+            // the trusting period can't be checked without the consensus states
+            assert!(client.validated_client_state().is_err());
+        }
+    }
+}
+
+#[test]
+fn test_foreign_client_expires_ckb_client_on_axon() {
+    let (node, axon) = setup_axon();
+    let (rpc_client, ckb) = setup(
+        ClientType::Axon,
+        encode(&AnyClientState::Axon(axon_client_state(false))),
+    );
+    let client_id = ClientId::from_str("07-ckb4ibc-0").unwrap();
+    let client_state = CkbClientState::new(ckb.config.id.clone(), Height::new(0, 1).unwrap())
+        .with_trusting_period(Some(Duration::from_secs(3600)));
+    let consensus_state = CkbConsensusState {
+        root: vec![0; 32].into(),
+        timestamp: Timestamp::from_nanoseconds(GENESIS_TIMESTAMP.as_nanos() as u64).unwrap(),
+        block_hash: vec![0; 32],
+    };
+    let stored =
+        |state: Vec<u8>| Bytes::from([format!("{client_id}|").into_bytes(), state].concat());
+    node.on_call::<GetClientStateCall, _>((
+        stored(serde_json::to_vec(&client_state).unwrap()),
+        true,
+    ));
+    node.on_call::<GetConsensusStateCall, _>((
+        stored(serde_json::to_vec(&consensus_state).unwrap()),
+        true,
+    ));
+
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+    let (axon, _) = ChainRuntime::init::<BaseChainHandle>(axon, rt.clone());
+    let (ckb, _) = ChainRuntime::init::<BaseChainHandle>(ckb, rt);
+    let client = ForeignClient::restore(client_id, axon, ckb);
+
+    // the blocks of the simulator are 8 seconds apart
+    rpc_client.produce_empty_blocks(400);
+    let (_, elapsed) = client.validated_client_state().unwrap();
+    assert_eq!(elapsed, Some(Duration::from_secs(3200)));
+
+    rpc_client.produce_empty_blocks(100);
+    assert!(client.is_expired_or_frozen());
+}
+
+fn proof() -> CommitmentProofBytes {
+    CommitmentProofBytes::try_from(vec![1]).unwrap()
+}
+
+/// Proofs of a handshake message from the Cosmos chain at `height`
+fn proofs(height: Height) -> Proofs {
+    Proofs::new(
+        proof(),
+        Some(proof()),
+        Some(ConsensusProof::new(proof(), height).unwrap()),
+        None,
+        height,
+    )
+    .unwrap()
+}
+
+fn send(chain: &mut Ckb4IbcChain, msg: Any) -> IbcEvent {
+    let tracked_msgs = TrackedMsgs::new_static(vec![msg], "test");
+    let mut events = chain.send_messages_and_wait_commit(tracked_msgs).unwrap();
+    assert_eq!(events.len(), 1);
+    events.remove(0).event
+}
+
+#[test]
+fn test_tendermint_client_connection_handshake() {
+    let header = get_dummy_ics07_header();
+    let mut client_state = get_dummy_tendermint_client_state(header.signed_header.header.clone());
+    client_state.latest_height = Height::new(header.height().revision_number(), 1).unwrap();
+    let consensus_state = TmConsensusState::from(header.clone());
+    let (_, mut chain) = setup(ClientType::Tendermint, encode(&client_state.clone().into()));
+    let client_id = chain.config.lc_client_id(ClientType::Tendermint).unwrap();
+    let signer = chain.get_signer().unwrap();
+
+    // the connections cell of the client is created along with it
+    let create =
+        MsgCreateClient::new(client_state.into(), consensus_state.into(), signer.clone()).unwrap();
+    let IbcEvent::CreateClient(created) = send(&mut chain, create.to_any()) else {
+        panic!("client not created");
+    };
+    assert_eq!(created.client_id(), &client_id);
+
+    let query_consensus_state = |chain: &Ckb4IbcChain, height: Height| {
+        let request = QueryConsensusStateRequest {
+            client_id: client_id.clone(),
+            consensus_height: height,
+            query_height: QueryHeight::Latest,
+        };
+        chain
+            .query_consensus_state(request, IncludeProof::No)
+            .map(|(consensus_state, _)| consensus_state)
+    };
+    // the deployed client cell carries no header
+    assert!(query_consensus_state(&chain, header.height()).is_err());
+
+    // the relayer advances the client cell to the header, twice to read the recreated cell
+    for _ in 0..2 {
+        let update = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: header.clone().into(),
+            signer: signer.clone(),
+        };
+        let IbcEvent::UpdateClient(updated) = send(&mut chain, update.to_any()) else {
+            panic!("client not updated");
+        };
+        assert_eq!(updated.consensus_height(), header.height());
+        let client_state = chain
+            .query_onchain_client_state(ClientType::Tendermint)
+            .unwrap();
+        assert_eq!(client_state.latest_height(), header.height());
+    }
+    // the consensus state at the latest height is the one of the header
+    assert_eq!(
+        query_consensus_state(&chain, header.height()).unwrap(),
+        AnyConsensusState::Tendermint(TmConsensusState::from(header.clone()))
+    );
+    assert!(query_consensus_state(&chain, header.height().increment()).is_err());
+
+    // the proofs of the Cosmos chain are verified against the client cell
+    let proofs = proofs(header.height());
+    let open_try = MsgConnectionOpenTry {
+        previous_connection_id: None,
+        client_id: client_id.clone(),
+        client_state: None,
+        counterparty: Counterparty::new(
+            "07-ckb4ibc-0".parse().unwrap(),
+            Some(ConnectionId::new(0)),
+            b"ibc".to_vec().try_into().unwrap(),
+        ),
+        counterparty_versions: vec![Version::default()],
+        proofs,
+        delay_period: Duration::ZERO,
+        signer,
+    };
+    let IbcEvent::OpenTryConnection(opened) = send(&mut chain, open_try.to_any()) else {
+        panic!("connection not opened");
+    };
+    let (connection, _) = chain
+        .query_connection(
+            QueryConnectionRequest {
+                connection_id: opened.connection_id().unwrap().clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .unwrap();
+    assert_eq!(connection.state(), &State::TryOpen);
+    assert_eq!(connection.client_id(), &client_id);
+}
+
+#[test]
+fn test_reject_inconsistent_tendermint_header() {
+    let header = get_dummy_ics07_header();
+    let mut client_state = get_dummy_tendermint_client_state(header.signed_header.header.clone());
+    client_state.latest_height = Height::new(header.height().revision_number(), 1).unwrap();
+    let (_, mut chain) = setup(ClientType::Tendermint, encode(&client_state.clone().into()));
+    let client_id = chain.config.lc_client_id(ClientType::Tendermint).unwrap();
+    let signer = chain.get_signer().unwrap();
+    let mut update = |header: TmHeader| {
+        let update = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: header.into(),
+            signer: signer.clone(),
+        };
+        let tracked_msgs = TrackedMsgs::new_static(vec![update.to_any()], "test");
+        chain.send_messages_and_wait_commit(tracked_msgs)
+    };
+
+    // the client never trusted the height of the header
+    let mut untrusted = header.clone();
+    untrusted.trusted_height = header.height();
+    assert!(update(untrusted).is_err());
+    // the header of another chain
+    let mut other_chain = header.clone();
+    other_chain.signed_header.header.chain_id = "other-0".parse().unwrap();
+    assert!(update(other_chain).is_err());
+
+    update(header.clone()).unwrap();
+    let client_state = chain
+        .query_onchain_client_state(ClientType::Tendermint)
+        .unwrap();
+    assert_eq!(client_state.latest_height(), header.height());
+}
+
+/// A chain with an unordered channel of `port_id` open with the Cosmos chain on a new
+/// connection of the Tendermint client, along with the height of the Cosmos proofs
+fn open_channel(port_id: &PortId) -> (Arc<RpcClient>, Ckb4IbcChain, ChannelId, Height) {
+    let header = get_dummy_ics07_header();
+    let height = header.height();
+    let client_state = get_dummy_tendermint_client_state(header.signed_header.header.clone());
+    let (rpc_client, mut chain) =
+        setup(ClientType::Tendermint, encode(&client_state.clone().into()));
+    let client_id = chain.config.lc_client_id(ClientType::Tendermint).unwrap();
+    let signer = chain.get_signer().unwrap();
+    let create = MsgCreateClient::new(
+        client_state.into(),
+        TmConsensusState::from(header).into(),
+        signer.clone(),
+    )
+    .unwrap();
+    send(&mut chain, create.to_any());
+
+    let open_try = MsgConnectionOpenTry {
+        previous_connection_id: None,
+        client_id,
+        client_state: None,
+        counterparty: Counterparty::new(
+            "07-ckb4ibc-0".parse().unwrap(),
+            Some(ConnectionId::new(0)),
+            b"ibc".to_vec().try_into().unwrap(),
+        ),
+        counterparty_versions: vec![Version::default()],
+        proofs: proofs(height),
+        delay_period: Duration::ZERO,
+        signer: signer.clone(),
+    };
+    let IbcEvent::OpenTryConnection(opened) = send(&mut chain, open_try.to_any()) else {
+        panic!("connection not opened");
+    };
+    let connection_id = opened.connection_id().unwrap().clone();
+    let open_confirm = MsgConnectionOpenConfirm {
+        connection_id: connection_id.clone(),
+        proofs: proofs(height),
+        signer: signer.clone(),
+    };
+    send(&mut chain, open_confirm.to_any());
+
+    let channel = ChannelEnd::new(
+        ChannelState::TryOpen,
+        Order::Unordered,
+        ChannelCounterparty::new(PortId::transfer(), Some(ChannelId::new(0))),
+        vec![connection_id],
+        ChannelVersion::ics20(),
+    );
+    let open_try = MsgChannelOpenTry {
+        port_id: port_id.clone(),
+        previous_channel_id: None,
+        channel,
+        counterparty_version: ChannelVersion::ics20(),
+        proofs: proofs(height),
+        signer: signer.clone(),
+    };
+    let IbcEvent::OpenTryChannel(opened) = send(&mut chain, open_try.to_any()) else {
+        panic!("channel not opened");
+    };
+    let channel_id = opened.channel_id.unwrap();
+
+    // the relayer reads the channel end before confirming it
+    let query = QueryChannelRequest {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        height: QueryHeight::Latest,
+    };
+    chain.query_channel(query, IncludeProof::No).unwrap();
+    let open_confirm = MsgChannelOpenConfirm {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        proofs: proofs(height),
+        signer,
+    };
+    send(&mut chain, open_confirm.to_any());
+    (rpc_client, chain, channel_id, height)
+}
+
+/// Commits the transaction of the application sending `packet` on its open channel,
+/// paid by the relayer wallet
+fn send_packet(chain: &Ckb4IbcChain, packet: &Packet) {
+    let (channel_id, port_id) = (&packet.source_channel, &packet.source_port);
+    let (_, old_channel) = chain
+        .fetch_channel_cell_and_extract(channel_id, port_id, true)
+        .unwrap();
+    let (input, capacity, channel_args) =
+        chain.channel_input_data.borrow()[&(channel_id.clone(), port_id.clone())].clone();
+    let mut new_channel = old_channel.clone();
+    new_channel.sequence.next_sequence_sends += 1;
+    let ibc_packet = IbcPacket {
+        packet: CkbPacket {
+            sequence: packet.sequence.into(),
+            source_port_id: port_id.to_string(),
+            source_channel_id: channel_id.to_string(),
+            destination_port_id: packet.destination_port.to_string(),
+            destination_channel_id: packet.destination_channel.to_string(),
+            data: packet.data.clone(),
+            timeout_height: 0,
+            timeout_timestamp: 0,
+        },
+        status: PacketStatus::Send,
+        ack: None,
+    };
+    let packet_args = PacketArgs {
+        channel_id: channel_args.channel_id,
+        port_id: channel_args.port_id,
+        sequence: packet.sequence.into(),
+    };
+    let mut commitments = vec![];
+    handle_msg_send_packet(
+        EmptyClient,
+        old_channel.clone(),
+        channel_args,
+        new_channel.clone(),
+        channel_args,
+        ibc_packet.clone(),
+        packet_args,
+        &mut commitments,
+        CkbMsgSendPacket {},
+    )
+    .unwrap();
+
+    let converter = chain.get_converter().unwrap();
+    let old_channel = get_encoded_object(&old_channel);
+    let new_channel = get_encoded_object(&new_channel);
+    let ibc_packet = get_encoded_object(&ibc_packet);
+    let tx = TxBuilder::default()
+        .cell_dep(chain.channel_outpoint.clone())
+        .input(input)
+        .output(
+            get_channel_lock_script(&converter, channel_args.to_args()),
+            new_channel.data,
+        )
+        .output(
+            get_packet_lock_script(&converter, packet_args.to_args()),
+            ibc_packet.data,
+        )
+        .witness(old_channel.witness, new_channel.witness)
+        .witness(packed::BytesOpt::default(), ibc_packet.witness)
+        .build();
+    let envelope = Envelope {
+        msg_type: MsgType::MsgSendPacket,
+        content: rlp::encode(&CkbMsgSendPacket {}).to_vec(),
+        commitments,
+    };
+    let wallet = chain.wallet().unwrap();
+    let (tx, _) = chain
+        .rt
+        .block_on(wallet.complete_tx(chain.rpc_client.as_ref(), tx, capacity, Some(envelope)))
+        .unwrap();
+    let tx: TransactionView = tx.into();
+    chain
+        .rt
+        .block_on(chain.rpc_client.send_transaction(&tx.inner, None))
+        .unwrap();
+}
+
+#[test]
+fn test_timeout_packet_on_close() {
+    let port_id = PortId::from_str(&hex::encode([7u8; 32])).unwrap();
+    let (_, mut chain, channel_id, height) = open_channel(&port_id);
+    let packet = Packet {
+        sequence: Sequence::from(1),
+        source_port: port_id.clone(),
+        source_channel: channel_id.clone(),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(0),
+        data: b"packet".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    send_packet(&chain, &packet);
+
+    // the channel end on CKB doesn't prove timeouts on close while it's open
+    assert!(chain
+        .build_packet_proofs(
+            PacketMsgType::TimeoutOnCloseUnordered,
+            port_id.clone(),
+            channel_id.clone(),
+            packet.sequence,
+            height,
+        )
+        .is_err());
+
+    // the counterparty closed its channel end without receiving the packet
+    let timeout = MsgTimeoutOnClose {
+        packet: packet.clone(),
+        next_sequence_recv: Sequence::from(1),
+        proofs: Proofs::new(proof(), None, None, Some(proof()), height).unwrap(),
+        signer: chain.get_signer().unwrap(),
+    };
+    let IbcEvent::TimeoutOnClosePacket(timed_out) = send(&mut chain, timeout.to_any()) else {
+        panic!("packet not timed out");
+    };
+    assert_eq!(timed_out.packet, packet);
+
+    // the packet cell is left to the application to refund, on the still open channel
+    let cell = chain
+        .fetch_packet_cell_and_extract(&channel_id, &port_id, packet.sequence, PacketStatus::Send)
+        .unwrap();
+    assert!(cell.is_some());
+    let query = QueryChannelRequest {
+        port_id,
+        channel_id,
+        height: QueryHeight::Latest,
+    };
+    let (channel, _) = chain.query_channel(query, IncludeProof::No).unwrap();
+    assert_eq!(channel.state, ChannelState::Open);
+}
+
+/// The transaction converted from `MsgTimeoutOnClose` is replayed by the packet-timeout
+/// handler of the IBC contracts against the objects it carries
+#[test]
+fn test_convert_timeout_on_close_to_tx() {
+    let port_id = PortId::from_str(&hex::encode([9u8; 32])).unwrap();
+    let (_, chain, channel_id, height) = open_channel(&port_id);
+    let packet = Packet {
+        sequence: Sequence::from(1),
+        source_port: port_id.clone(),
+        source_channel: channel_id.clone(),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(0),
+        data: b"packet".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    send_packet(&chain, &packet);
+
+    let timeout = MsgTimeoutOnClose {
+        packet: packet.clone(),
+        next_sequence_recv: Sequence::from(1),
+        proofs: Proofs::new(proof(), None, None, Some(proof()), height).unwrap(),
+        signer: chain.get_signer().unwrap(),
+    };
+    let converter = chain.get_converter().unwrap();
+    let (_, _, old_channel_args) = converter
+        .get_ibc_channel_input(&channel_id, &port_id)
+        .unwrap();
+    let CkbTxInfo {
+        unsigned_tx,
+        envelope,
+        ..
+    } = convert_msg_to_ckb_tx(&timeout.clone().to_any(), &converter).unwrap();
+    let tx = unsigned_tx.unwrap();
+    assert!(matches!(envelope.msg_type, MsgType::MsgTimeoutPacket));
+
+    // the objects in the witnesses are the preimages of the output data
+    let witnesses = tx
+        .witnesses()
+        .into_iter()
+        .map(|witness| packed::WitnessArgs::from_slice(&witness.raw_data()).unwrap())
+        .collect::<Vec<_>>();
+    let object = |index: usize, input: bool| {
+        let object = if input {
+            witnesses[index].input_type()
+        } else {
+            witnesses[index].output_type()
+        };
+        object.to_opt().unwrap().raw_data()
+    };
+    for index in 0..2 {
+        let data = tx.outputs_data().get(index).unwrap().raw_data();
+        assert_eq!(&keccak256(&object(index, false))[..], &data[..]);
+    }
+    let old_channel: IbcChannel = rlp::decode(&object(0, true)).unwrap();
+    let new_channel: IbcChannel = rlp::decode(&object(0, false)).unwrap();
+    let old_packet: IbcPacket = rlp::decode(&object(1, true)).unwrap();
+    let new_packet: IbcPacket = rlp::decode(&object(1, false)).unwrap();
+    let lock_args = |index: usize| tx.output(index).unwrap().lock().args().raw_data();
+    let new_channel_args = ChannelArgs::from_slice(&lock_args(0)).unwrap();
+    let packet_args = PacketArgs::from_slice(&lock_args(1)).unwrap();
+    assert_eq!(old_packet.status, PacketStatus::Send);
+    assert_eq!(new_packet.status, PacketStatus::Send);
+    assert_eq!(u64::from(packet_args.sequence), u64::from(packet.sequence));
+
+    // the handler accepts the transition and commits what the envelope carries
+    let msg: CkbMsgTimeoutPacket = rlp::decode(&envelope.content).unwrap();
+    assert_eq!(msg.next_sequence_recv, 1);
+    let mut commitments = vec![];
+    handle_msg_timeout_packet(
+        EmptyClient,
+        old_channel,
+        old_channel_args,
+        new_channel,
+        new_channel_args,
+        old_packet,
+        packet_args,
+        new_packet,
+        packet_args,
+        &mut commitments,
+        msg,
+    )
+    .unwrap();
+    let received = rlp::encode(&envelope);
+    let expected = Envelope {
+        commitments,
+        ..envelope
+    };
+    assert_eq!(rlp::encode(&expected), received);
+    assert!(new_channel_args.open);
+
+    // a packet without its closed channel proof can't be timed out on close
+    let timeout = MsgTimeoutOnClose {
+        proofs: Proofs::new(proof(), None, None, None, height).unwrap(),
+        ..timeout
+    };
+    assert!(convert_msg_to_ckb_tx(&timeout.to_any(), &converter).is_err());
+}
+
+#[test]
+fn test_recv_and_ack_packets() {
+    let port_id = PortId::from_str(&hex::encode([8u8; 32])).unwrap();
+    let (_, mut chain, channel_id, height) = open_channel(&port_id);
+    let signer = chain.get_signer().unwrap();
+    let packet_proofs = || Proofs::new(proof(), None, None, None, height).unwrap();
+
+    // the packet from the Cosmos chain is received by the channel end on CKB
+    let received = Packet {
+        sequence: Sequence::from(1),
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(0),
+        destination_port: port_id.clone(),
+        destination_channel: channel_id.clone(),
+        data: b"received".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    let recv = MsgRecvPacket {
+        packet: received.clone(),
+        proofs: packet_proofs(),
+        signer: signer.clone(),
+    };
+    let IbcEvent::ReceivePacket(event) = send(&mut chain, recv.to_any()) else {
+        panic!("packet not received");
+    };
+    assert_eq!(event.packet, received);
+    let cell = chain
+        .fetch_packet_cell_and_extract(&channel_id, &port_id, received.sequence, PacketStatus::Recv)
+        .unwrap();
+    assert!(cell.is_some());
+    // the simulator replays the handler, which rejects receiving the packet twice
+    let recv = MsgRecvPacket {
+        packet: received,
+        proofs: packet_proofs(),
+        signer: signer.clone(),
+    };
+    let tracked_msgs = TrackedMsgs::new_static(vec![recv.to_any()], "test");
+    assert!(chain.send_messages_and_wait_commit(tracked_msgs).is_err());
+
+    // the packet sent from CKB is acknowledged by the Cosmos chain
+    let sent = Packet {
+        sequence: Sequence::from(1),
+        source_port: port_id.clone(),
+        source_channel: channel_id.clone(),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(0),
+        data: b"sent".to_vec(),
+        timeout_height: TimeoutHeight::Never,
+        timeout_timestamp: Timestamp::none(),
+    };
+    send_packet(&chain, &sent);
+    let ack = MsgAcknowledgement {
+        packet: sent.clone(),
+        acknowledgement: vec![1].into(),
+        proofs: packet_proofs(),
+        signer,
+    };
+    // the packet workers don't wait for the transaction to be committed
+    let tracked_msgs = TrackedMsgs::new_static(vec![ack.to_any()], "test");
+    let responses = chain.send_messages_and_wait_check_tx(tracked_msgs).unwrap();
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].code.is_ok());
+    let (acked, _) = chain
+        .fetch_packet_cell_and_extract(&channel_id, &port_id, sent.sequence, PacketStatus::Ack)
+        .unwrap()
+        .unwrap();
+    assert_eq!(acked.ack, Some(vec![1]));
+}
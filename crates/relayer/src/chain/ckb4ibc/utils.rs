@@ -640,11 +640,20 @@ pub async fn generate_tx_proof_from_block(
     };
 
     let object_proof = object_proof.encode();
-    let hex_object_proof: String = object_proof.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join("");
+    let hex_object_proof: String = object_proof
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<String>>()
+        .join("");
     let file_name = format!("{}.txt", header.inner.transactions_root.clone());
     let mut file = File::create(file_name).unwrap();
     writeln!(file, "0x{}", hex_object_proof).unwrap();
-    info!("AxonObjectProof: 0x{}, len: {}, transactions_root: {}", &hex_object_proof[0..200], hex_object_proof.len(), header.inner.transactions_root);
+    info!(
+        "AxonObjectProof: 0x{}, len: {}, transactions_root: {}",
+        &hex_object_proof[0..200],
+        hex_object_proof.len(),
+        header.inner.transactions_root
+    );
     // assemble ibc-compatible proof
     let block_number = Height::from_noncosmos_height(header.inner.number.into());
     let proofs = get_ibc_merkle_proof(block_number, object_proof)?;
@@ -670,9 +679,14 @@ fn test_vec() {
     let bytes: Vec<u8> = vec![10, 3, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0];
     println!("1: {:#?}", bytes);
     println!("2: {:x?}", bytes);
-    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join("");
+    let hex: String = bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<String>>()
+        .join("");
     println!("3: {}", hex);
 
-    let transactions_root = H256::from_str("71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c").unwrap();
+    let transactions_root =
+        H256::from_str("71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c").unwrap();
     println!("transactions_root: {}", transactions_root);
 }
@@ -0,0 +1,490 @@
+//! Management of the secp256k1 cells which fund the transactions of the relayer.
+//!
+//! The cells picked to balance a transaction are reserved until the transaction is
+//! committed or fails, so that the transactions assembled in the meantime, e.g. by
+//! the packet reclaimer, pick other cells instead of colliding on the same inputs.
+//! The change output of a sent transaction can be spent before it's committed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ckb_ics_axon::message::Envelope;
+use ckb_jsonrpc_types::OutPoint as JsonOutPoint;
+use ckb_sdk::rpc::ckb_indexer::{Cell, SearchKey};
+use ckb_sdk::traits::{CellQueryOptions, PrimaryScriptType, SecpCkbRawKeySigner};
+use ckb_sdk::unlock::{ScriptSigner, SecpSighashScriptSigner};
+use ckb_sdk::{Address, AddressPayload, NetworkType, ScriptGroup, ScriptGroupType};
+use ckb_types::bytes::Bytes;
+use ckb_types::core::{Capacity, TransactionView};
+use ckb_types::packed::{CellInput, CellOutput, OutPoint, Script, WitnessArgs};
+use ckb_types::prelude::{Builder, Entity, Pack, Unpack};
+use ckb_types::H256;
+
+use super::utils::get_encoded_object;
+use crate::chain::ckb::prelude::CkbReader;
+use crate::chain::ckb::sighash::get_secp256k1_celldep;
+use crate::error::Error;
+use crate::keyring::Secp256k1KeyPair;
+
+/// Fee rate of the relayer transactions, in shannons per byte
+const FEE_RATE: u64 = 3000;
+
+/// Number of wallet cells fetched by a single indexer request
+const CELLS_PAGE_SIZE: u32 = 20;
+
+/// How long the cells of a transaction stay reserved if it's never released
+const RESERVATION_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone)]
+struct FeeCell {
+    out_point: JsonOutPoint,
+    capacity: u64,
+}
+
+struct Reservation {
+    inputs: Vec<JsonOutPoint>,
+    change: FeeCell,
+    sent: bool,
+    since: Instant,
+}
+
+#[derive(Default)]
+struct WalletState {
+    reservations: HashMap<H256, Reservation>,
+}
+
+impl WalletState {
+    fn expire(&mut self) {
+        self.reservations
+            .retain(|_, reservation| reservation.since.elapsed() < RESERVATION_TIMEOUT);
+    }
+
+    fn is_reserved(&self, out_point: &JsonOutPoint) -> bool {
+        self.reservations
+            .values()
+            .any(|reservation| reservation.inputs.contains(out_point))
+    }
+
+    /// The change outputs of the sent transactions which aren't spent by another one
+    fn pending_changes(&self) -> Vec<FeeCell> {
+        self.reservations
+            .values()
+            .filter(|reservation| reservation.sent)
+            .map(|reservation| reservation.change.clone())
+            .filter(|change| !self.is_reserved(&change.out_point))
+            .collect()
+    }
+}
+
+pub struct Wallet {
+    key: Secp256k1KeyPair,
+    address: Address,
+    lock: Script,
+    state: Mutex<WalletState>,
+}
+
+impl Wallet {
+    pub fn new(key: Secp256k1KeyPair, network: NetworkType) -> Self {
+        let address = Address::new(network, AddressPayload::from_pubkey(&key.public_key), true);
+        let lock = Script::from(&address);
+        Self {
+            key,
+            address,
+            lock,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn lock(&self) -> &Script {
+        &self.lock
+    }
+
+    /// Balance `tx` with unreserved wallet cells, append the envelope witness and sign it,
+    /// returns the signed transaction along with its fee in shannons.
+    ///
+    /// `input_capacity` is the capacity of the inputs `tx` already has. The wallet cells
+    /// picked by the transaction stay reserved until [`Wallet::release`] is called.
+    pub async fn complete_tx(
+        &self,
+        rpc_client: &impl CkbReader,
+        tx: TransactionView,
+        input_capacity: u64,
+        envelope: Option<Envelope>,
+    ) -> Result<(TransactionView, u64), Error> {
+        self.complete_owned_tx(rpc_client, tx, input_capacity, envelope, &[])
+            .await
+    }
+
+    /// Same as [`Wallet::complete_tx`], the inputs of `tx` at `owned_inputs` are locked
+    /// by the wallet as well, e.g. the Tendermint light client cell, so they're signed
+    /// along with the wallet cells
+    pub async fn complete_owned_tx(
+        &self,
+        rpc_client: &impl CkbReader,
+        tx: TransactionView,
+        input_capacity: u64,
+        envelope: Option<Envelope>,
+        owned_inputs: &[usize],
+    ) -> Result<(TransactionView, u64), Error> {
+        let min_change_capacity: u64 = CellOutput::new_builder()
+            .lock(self.lock.clone())
+            .build_exact_capacity(Capacity::zero())
+            .unwrap()
+            .capacity()
+            .unpack();
+        let outputs_capacity = tx
+            .outputs_capacity()
+            .map_err(|err| Error::send_tx(err.to_string()))?
+            .as_u64();
+        let fee = tx.data().as_bytes().len() as u64 * FEE_RATE;
+        let need_capacity =
+            (outputs_capacity + fee + min_change_capacity).saturating_sub(input_capacity);
+
+        let candidates = if need_capacity > 0 {
+            self.collect_cells(rpc_client, need_capacity).await?
+        } else {
+            vec![]
+        };
+
+        let mut state = self.state.lock().unwrap();
+        // the candidates may have been reserved while the indexer was queried
+        let mut fee_cells = vec![];
+        let mut fee_cells_capacity = 0;
+        for cell in candidates {
+            if fee_cells_capacity >= need_capacity {
+                break;
+            }
+            if !state.is_reserved(&cell.out_point) {
+                fee_cells_capacity += cell.capacity;
+                fee_cells.push(cell);
+            }
+        }
+        if fee_cells_capacity < need_capacity {
+            return Err(Error::send_tx(format!(
+                "no enough ckb ({fee_cells_capacity}/{need_capacity}) on address: {}",
+                self.address
+            )));
+        }
+
+        let wallet_input_index = tx.inputs().len();
+        let change_capacity = input_capacity + fee_cells_capacity - outputs_capacity - fee;
+        let change = CellOutput::new_builder()
+            .lock(self.lock.clone())
+            .capacity(change_capacity.pack())
+            .build();
+        let mut builder = tx
+            .as_advanced_builder()
+            .inputs(fee_cells.iter().map(|cell| {
+                CellInput::new_builder()
+                    .previous_output(cell.out_point.clone().into())
+                    .build()
+            }))
+            .output(change)
+            .output_data(Bytes::new().pack())
+            .cell_dep(get_secp256k1_celldep(self.address.network()));
+        // placeholder for the secp256k1 script, it will be used in the signing step
+        builder = builder.witness(WitnessArgs::new_builder().build().as_bytes().pack());
+        if let Some(envelope) = envelope {
+            let witness = WitnessArgs::new_builder()
+                .output_type(get_encoded_object(&envelope).witness)
+                .build();
+            builder = builder.witness(witness.as_bytes().pack());
+        }
+        let tx = builder.build();
+        let input_indices = owned_inputs
+            .iter()
+            .copied()
+            .chain(wallet_input_index..tx.inputs().len())
+            .collect::<Vec<_>>();
+        let tx = if input_indices.is_empty() {
+            tx
+        } else {
+            self.sign_tx(&tx, input_indices)?
+        };
+
+        let change_index = tx.outputs().len() - 1;
+        state.expire();
+        state.reservations.insert(
+            tx.hash().unpack(),
+            Reservation {
+                inputs: fee_cells.into_iter().map(|cell| cell.out_point).collect(),
+                change: FeeCell {
+                    out_point: OutPoint::new_builder()
+                        .tx_hash(tx.hash())
+                        .index((change_index as u32).pack())
+                        .build()
+                        .into(),
+                    capacity: change_capacity,
+                },
+                sent: false,
+                since: Instant::now(),
+            },
+        );
+        Ok((tx, fee))
+    }
+
+    /// Make the change of the sent transaction `tx_hash` spendable by the next ones
+    pub fn sent(&self, tx_hash: &H256) {
+        if let Some(reservation) = self.state.lock().unwrap().reservations.get_mut(tx_hash) {
+            reservation.sent = true;
+        }
+    }
+
+    /// Drop the reservation of the transaction `tx_hash`, once it's committed or has failed
+    pub fn release(&self, tx_hash: &H256) {
+        self.state.lock().unwrap().reservations.remove(tx_hash);
+    }
+
+    /// Returns the transaction splitting the wallet capacity into `count` cells of
+    /// `capacity` shannons, or `None` if the wallet already holds that many of them
+    pub async fn split_tx(
+        &self,
+        rpc_client: &impl CkbReader,
+        count: usize,
+        capacity: u64,
+    ) -> Result<Option<TransactionView>, Error> {
+        let fee_cells = self
+            .collect_cells(rpc_client, u64::MAX)
+            .await?
+            .into_iter()
+            .filter(|cell| cell.capacity >= capacity)
+            .count();
+        if fee_cells >= count {
+            return Ok(None);
+        }
+        let output = CellOutput::new_builder()
+            .lock(self.lock.clone())
+            .capacity(capacity.pack())
+            .build();
+        let tx = TransactionView::new_advanced_builder()
+            .outputs(vec![output; count])
+            .outputs_data(vec![Bytes::new().pack(); count])
+            .build();
+        let (tx, _) = self.complete_tx(rpc_client, tx, 0, None).await?;
+        Ok(Some(tx))
+    }
+
+    /// Unreserved wallet cells holding at least `need_capacity` shannons, or all of them,
+    /// the change outputs of the sent transactions coming last
+    async fn collect_cells(
+        &self,
+        rpc_client: &impl CkbReader,
+        need_capacity: u64,
+    ) -> Result<Vec<FeeCell>, Error> {
+        let search_key: SearchKey =
+            CellQueryOptions::new(self.lock.clone(), PrimaryScriptType::Lock).into();
+        let pending_changes = self.state.lock().unwrap().pending_changes();
+
+        let mut cells = vec![];
+        let mut capacity = 0;
+        let mut cursor = None;
+        while capacity < need_capacity {
+            let page = rpc_client
+                .fetch_live_cells(search_key.clone(), CELLS_PAGE_SIZE, cursor)
+                .await
+                .map_err(|e| Error::rpc_response(e.to_string()))?;
+            let state = self.state.lock().unwrap();
+            for cell in &page.objects {
+                if !is_fee_cell(cell)
+                    || state.is_reserved(&cell.out_point)
+                    || pending_changes
+                        .iter()
+                        .any(|change| change.out_point == cell.out_point)
+                {
+                    continue;
+                }
+                let fee_cell = FeeCell {
+                    out_point: cell.out_point.clone(),
+                    capacity: cell.output.capacity.into(),
+                };
+                capacity += fee_cell.capacity;
+                cells.push(fee_cell);
+            }
+            if page.objects.len() < CELLS_PAGE_SIZE as usize {
+                break;
+            }
+            cursor = Some(page.last_cursor);
+        }
+        cells.extend(pending_changes);
+        Ok(cells)
+    }
+
+    fn sign_tx(
+        &self,
+        tx: &TransactionView,
+        input_indices: Vec<usize>,
+    ) -> Result<TransactionView, Error> {
+        let signer = SecpSighashScriptSigner::new(Box::new(
+            SecpCkbRawKeySigner::new_with_secret_keys(vec![self.key.private_key]),
+        ));
+        signer
+            .sign_tx(
+                tx,
+                &ScriptGroup {
+                    script: self.lock.clone(),
+                    group_type: ScriptGroupType::Lock,
+                    input_indices,
+                    output_indices: vec![],
+                },
+            )
+            .map_err(|err| Error::other_error(err.to_string()))
+    }
+}
+
+/// Only the plain cells of the wallet can pay fees
+pub fn is_fee_cell(cell: &Cell) -> bool {
+    cell.output.type_.is_none()
+        && cell
+            .output_data
+            .as_ref()
+            .map_or(true, |data| data.is_empty())
+}
+
+/// The amount of a sUDT cell, which is the little-endian u128 of the first 16 bytes
+/// of its data, the bytes after it belong to the token extensions
+pub fn sudt_amount(data: &[u8]) -> Option<u128> {
+    let amount = data.get(..16)?.try_into().ok()?;
+    Some(u128::from_le_bytes(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use ckb_jsonrpc_types::JsonBytes;
+
+    use super::*;
+    use crate::chain::ckb::prelude::CkbWriter;
+    use crate::chain::ckb::rpc_client::RpcClient;
+    use crate::config::AddressType;
+    use crate::keyring::SigningKeyPair;
+
+    const SECRET_KEY: &str = "e0ed6b4ef8ac7a4d5d3b5d3e1cf6ea42ff4e4a3ff6b1d5c1a7a7a4c3e0bb3b48";
+
+    const ONE_CKB: u64 = 100_000_000;
+
+    fn setup(cells: &[u64]) -> (tokio::runtime::Runtime, RpcClient, Wallet) {
+        let key = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Ckb).unwrap();
+        let wallet = Wallet::new(key, NetworkType::Dev);
+        let rpc_client = RpcClient::new(
+            &"http://127.0.0.1:8114".parse().unwrap(),
+            &"http://127.0.0.1:8116".parse().unwrap(),
+        );
+        for (index, capacity) in cells.iter().enumerate() {
+            rpc_client.add_cell(Cell {
+                output: CellOutput::new_builder()
+                    .lock(wallet.lock().clone())
+                    .capacity((capacity * ONE_CKB).pack())
+                    .build()
+                    .into(),
+                output_data: Some(JsonBytes::default()),
+                out_point: OutPoint::new_builder()
+                    .tx_hash(H256([index as u8 + 1; 32]).pack())
+                    .build()
+                    .into(),
+                block_number: 0.into(),
+                tx_index: 0.into(),
+            });
+        }
+        (tokio::runtime::Runtime::new().unwrap(), rpc_client, wallet)
+    }
+
+    /// A transaction paying `capacity` CKB to some other lock
+    fn payment(capacity: u64) -> TransactionView {
+        TransactionView::new_advanced_builder()
+            .output(
+                CellOutput::new_builder()
+                    .capacity((capacity * ONE_CKB).pack())
+                    .build(),
+            )
+            .output_data(Bytes::new().pack())
+            .build()
+    }
+
+    fn send(rt: &tokio::runtime::Runtime, rpc_client: &RpcClient, tx: TransactionView) -> H256 {
+        let tx: ckb_jsonrpc_types::TransactionView = tx.into();
+        rt.block_on(rpc_client.send_transaction(&tx.inner, None))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_concurrent_transactions_pick_distinct_cells() {
+        let (rt, rpc_client, wallet) = setup(&[1000, 1000]);
+        let (tx1, _) = rt
+            .block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None))
+            .unwrap();
+        let (tx2, _) = rt
+            .block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None))
+            .unwrap();
+        assert_eq!(tx1.inputs().len(), 1);
+        assert_eq!(tx2.inputs().len(), 1);
+        assert_ne!(
+            tx1.inputs().get(0).unwrap().as_slice(),
+            tx2.inputs().get(0).unwrap().as_slice()
+        );
+
+        // both cells are reserved
+        let result = rt.block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None));
+        assert!(result.is_err());
+
+        send(&rt, &rpc_client, tx1);
+        send(&rt, &rpc_client, tx2);
+    }
+
+    #[test]
+    fn test_release_and_reuse_pending_change() {
+        let (rt, rpc_client, wallet) = setup(&[1000]);
+        let (tx1, fee) = rt
+            .block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None))
+            .unwrap();
+        let change: u64 = tx1.output(1).unwrap().capacity().unpack();
+        assert_eq!(change, 900 * ONE_CKB - fee);
+
+        // a failed transaction gives its cells back
+        wallet.release(&tx1.hash().unpack());
+        let (tx1, _) = rt
+            .block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None))
+            .unwrap();
+
+        // the change of a sent transaction is spent by the next one
+        let tx1_hash: H256 = tx1.hash().unpack();
+        wallet.sent(&tx1_hash);
+        let (tx2, _) = rt
+            .block_on(wallet.complete_tx(&rpc_client, payment(100), 0, None))
+            .unwrap();
+        let previous_output: JsonOutPoint = tx2.inputs().get(0).unwrap().previous_output().into();
+        assert_eq!(previous_output.tx_hash, tx1_hash);
+
+        send(&rt, &rpc_client, tx1);
+        send(&rt, &rpc_client, tx2);
+    }
+
+    #[test]
+    fn test_split_fee_cells() {
+        let (rt, rpc_client, wallet) = setup(&[1000]);
+        let tx = rt
+            .block_on(wallet.split_tx(&rpc_client, 4, 100 * ONE_CKB))
+            .unwrap()
+            .unwrap();
+        let hash = send(&rt, &rpc_client, tx);
+        wallet.release(&hash);
+
+        let split = rt
+            .block_on(wallet.split_tx(&rpc_client, 4, 100 * ONE_CKB))
+            .unwrap();
+        assert!(split.is_none());
+    }
+
+    #[test]
+    fn test_sudt_amount() {
+        let amount = 1_000u128.to_le_bytes();
+        assert_eq!(sudt_amount(&amount), Some(1_000));
+        // the extension data is ignored
+        assert_eq!(sudt_amount(&[&amount[..], b"ext"].concat()), Some(1_000));
+        assert_eq!(sudt_amount(&amount[..15]), None);
+        assert_eq!(sudt_amount(&[]), None);
+    }
+}
@@ -26,10 +26,9 @@ impl ClientSettings {
         dst_chain_config: &ChainConfig,
     ) -> Self {
         match (src_chain_config.r#type(), dst_chain_config.r#type()) {
-            (ChainType::CosmosSdk, ChainType::CosmosSdk) => {
-                // Currently, only Tendermint chain pairs are supported by
-                // ForeignClient::build_create_client_and_send. Support for
-                // heterogeneous chains is left for future revisions.
+            (ChainType::CosmosSdk, ChainType::CosmosSdk | ChainType::Axon | ChainType::Ckb4Ibc) => {
+                // Tendermint clients are hosted by Cosmos chains, by the IBC
+                // handler contract of Axon and by the light client cells of CKB
                 ClientSettings::Tendermint(cosmos::client::Settings::for_create_command(
                     options,
                     src_chain_config,
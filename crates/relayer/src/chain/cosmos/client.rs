@@ -6,7 +6,7 @@ use tracing::warn;
 
 use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
 
-use crate::config::ChainConfig;
+use crate::config::{default, ChainConfig};
 use crate::foreign_client::CreateOptions;
 use crate::util::pretty::PrettyDuration;
 
@@ -27,7 +27,7 @@ impl Settings {
         let max_clock_drift = match options.max_clock_drift {
             None => calculate_client_state_drift(src_chain_config, dst_chain_config),
             Some(user_value) => {
-                if user_value > dst_chain_config.cosmos().max_block_time {
+                if user_value > dst_chain_config.max_block_time() {
                     warn!(
                         "user specified max_clock_drift ({}) exceeds max_block_time \
                         of the destination chain {}",
@@ -58,6 +58,10 @@ fn calculate_client_state_drift(
     dst_chain_config: &ChainConfig,
 ) -> Duration {
     let src_chain_config = src_chain_config.cosmos();
-    let dst_chain_config = dst_chain_config.cosmos();
-    src_chain_config.clock_drift + dst_chain_config.clock_drift + dst_chain_config.max_block_time
+    // non-Cosmos destinations, e.g. Axon, have no clock drift setting
+    let dst_clock_drift = match dst_chain_config {
+        ChainConfig::Cosmos(config) => config.clock_drift,
+        _ => default::clock_drift(),
+    };
+    src_chain_config.clock_drift + dst_clock_drift + dst_chain_config.max_block_time()
 }
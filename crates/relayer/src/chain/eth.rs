@@ -200,10 +200,10 @@ impl ChainEndpoint for EthChain {
             .into_iter()
             .map(|update| IdentifiedAnyClientState {
                 client_id: ClientId::new(ClientType::Eth, 0).unwrap(),
-                client_state: AnyClientState::Eth(EthClientState {
-                    chain_id: self.config.id.clone(),
-                    lightclient_update: update,
-                }),
+                client_state: AnyClientState::Eth(EthClientState::new(
+                    self.config.id.clone(),
+                    update,
+                )),
             })
             .collect();
         Ok(client_states)
@@ -387,10 +387,7 @@ impl ChainEndpoint for EthChain {
             .light_client
             .get_finality_update(slot.revision_height())?
         {
-            let client_state = EthClientState {
-                chain_id: self.config.id.clone(),
-                lightclient_update: update,
-            };
+            let client_state = EthClientState::new(self.config.id.clone(), update);
             Ok(client_state)
         } else {
             Err(Error::ics07(
@@ -91,7 +91,7 @@ where
     }
 
     /// Initializes a runtime for a given chain, and spawns the associated thread
-    fn init<Handle: ChainHandle>(
+    pub(crate) fn init<Handle: ChainHandle>(
         chain: Endpoint,
         rt: Arc<TokioRuntime>,
     ) -> (Handle, thread::JoinHandle<()>) {
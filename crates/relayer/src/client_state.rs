@@ -139,9 +139,9 @@ impl AnyClientState {
     pub fn refresh_period(&self) -> Option<Duration> {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.refresh_time(),
-            AnyClientState::Eth(_) => None,
-            AnyClientState::Ckb(_) => None,
-            AnyClientState::Axon(_) => None,
+            AnyClientState::Eth(state) => state.refresh_time(),
+            AnyClientState::Ckb(state) => state.refresh_time(),
+            AnyClientState::Axon(state) => state.refresh_time(),
 
             #[cfg(test)]
             AnyClientState::Mock(mock_state) => mock_state.refresh_time(),
@@ -163,6 +163,12 @@ impl TryFrom<Any> for AnyClientState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
+            // hosted by Cosmos chains counterparty to Ckb4Ibc chains
+            CKB_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Ckb(CkbClientState::try_from(raw)?)),
+
+            // kept in the light client cells of Ckb4Ibc chains
+            AXON_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Axon(AxonClientState::try_from(raw)?)),
+
             #[cfg(test)]
             MOCK_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Mock(
                 Protobuf::<RawMockClientState>::decode_vec(&raw.value)
@@ -254,9 +260,9 @@ impl ClientState for AnyClientState {
                 upgrade_options.as_tm_upgrade_options().unwrap(),
                 chain_id,
             ),
-            AnyClientState::Eth(_) => todo!(),
-            AnyClientState::Ckb(_) => todo!(),
-            AnyClientState::Axon(_) => todo!(),
+            AnyClientState::Eth(state) => state.upgrade(upgrade_height, upgrade_options, chain_id),
+            AnyClientState::Ckb(state) => state.upgrade(upgrade_height, upgrade_options, chain_id),
+            AnyClientState::Axon(state) => state.upgrade(upgrade_height, upgrade_options, chain_id),
 
             #[cfg(test)]
             AnyClientState::Mock(mock_state) => {
@@ -268,9 +274,9 @@ impl ClientState for AnyClientState {
     fn expired(&self, elapsed_since_latest: Duration) -> bool {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.expired(elapsed_since_latest),
-            AnyClientState::Eth(_) => todo!(),
-            AnyClientState::Ckb(_) => false,
-            AnyClientState::Axon(_) => false,
+            AnyClientState::Eth(state) => state.expired(elapsed_since_latest),
+            AnyClientState::Ckb(state) => state.expired(elapsed_since_latest),
+            AnyClientState::Axon(state) => state.expired(elapsed_since_latest),
 
             #[cfg(test)]
             AnyClientState::Mock(mock_state) => mock_state.expired(elapsed_since_latest),
@@ -431,6 +437,10 @@ mod tests {
     use ibc_proto::google::protobuf::Any;
     use ibc_relayer_types::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
     use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use ibc_relayer_types::{
+        clients::ics07_ckb::client_state::CkbClientState, core::ics24_host::identifier::ChainId,
+        Height,
+    };
     use test_log::test;
 
     use super::AnyClientState;
@@ -443,5 +453,14 @@ mod tests {
         let raw: Any = tm_client_state.clone().into();
         let tm_client_state_back = AnyClientState::try_from(raw).unwrap();
         assert_eq!(tm_client_state, tm_client_state_back);
+
+        let ckb_client_state: AnyClientState = CkbClientState::new(
+            ChainId::from_string("ckb4ibc-0"),
+            Height::from_noncosmos_height(10),
+        )
+        .with_genesis_hash(vec![0xab; 32])
+        .into();
+        let raw: Any = ckb_client_state.clone().into();
+        assert_eq!(ckb_client_state, AnyClientState::try_from(raw).unwrap());
     }
 }
@@ -1,11 +1,13 @@
 //! Relayer configuration
 pub mod axon;
+pub mod balance;
 pub mod ckb;
 pub mod ckb4ibc;
 pub mod cosmos;
 pub mod error;
 pub mod eth;
 pub mod filter;
+pub mod online;
 
 use alloc::collections::BTreeMap;
 use core::{
@@ -31,6 +33,7 @@ use ibc_relayer_types::timestamp::ZERO_DURATION;
 use crate::chain::ChainType;
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
+use crate::keyring::Store;
 
 pub use crate::config::Error as ConfigError;
 use axon::AxonChainConfig;
@@ -41,11 +44,17 @@ pub use error::Error;
 use eth::EthChainConfig;
 use tokio::sync::OnceCell;
 
+use self::balance::BalanceConfig;
 use self::filter::PacketFilter;
 
 // FIXME: This is a bad workaround to update config.
 pub static GLOBAL_CONFIG_PATH: OnceCell<PathBuf> = OnceCell::const_new();
 
+lazy_static::lazy_static! {
+    static ref ALLOW_ALL_PACKETS: PacketFilter = PacketFilter::default();
+    static ref NO_BALANCE_ALERTS: BalanceConfig = BalanceConfig::default();
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
     pub price: f64,
@@ -215,13 +224,23 @@ impl ChainConfig {
     pub fn packet_filter(&self) -> &PacketFilter {
         match self {
             ChainConfig::Cosmos(c) => &c.packet_filter,
-            ChainConfig::Eth(_) => todo!(),
-            ChainConfig::Ckb(_) => todo!(),
+            // Eth and CKB only relay light client headers, no packet goes through them
+            ChainConfig::Eth(_) | ChainConfig::Ckb(_) => &ALLOW_ALL_PACKETS,
             ChainConfig::Axon(c) => &c.packet_filter,
             ChainConfig::Ckb4Ibc(c) => &c.packet_filter,
         }
     }
 
+    pub fn balance(&self) -> &BalanceConfig {
+        match self {
+            ChainConfig::Axon(c) => &c.balance,
+            ChainConfig::Ckb4Ibc(c) => &c.balance,
+            ChainConfig::Cosmos(_) | ChainConfig::Eth(_) | ChainConfig::Ckb(_) => {
+                &NO_BALANCE_ALERTS
+            }
+        }
+    }
+
     pub fn key_name(&self) -> &str {
         match self {
             ChainConfig::Cosmos(c) => &c.key_name,
@@ -232,6 +251,15 @@ impl ChainConfig {
         }
     }
 
+    pub fn key_store_type(&self) -> Store {
+        match self {
+            ChainConfig::Cosmos(c) => c.key_store_type,
+            ChainConfig::Axon(c) => c.key_store_type,
+            ChainConfig::Ckb4Ibc(c) => c.key_store_type,
+            ChainConfig::Eth(_) | ChainConfig::Ckb(_) => Store::default(),
+        }
+    }
+
     pub fn downcast_cosmos(self) -> CosmosChainConfig {
         if let ChainConfig::Cosmos(c) = self {
             c
@@ -1,9 +1,16 @@
+use core::time::Duration;
+use std::path::PathBuf;
+
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 use tendermint_rpc::WebSocketClientUrl;
 
+use super::balance::BalanceConfig;
 use super::filter::PacketFilter;
+use crate::keyring::Store;
+
+pub const DIAGNOSTICS_DEFAULT_FOLDER: &str = ".forcerelay/diagnostics/";
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AxonChainConfig {
@@ -12,10 +19,173 @@ pub struct AxonChainConfig {
     pub rpc_addr: Url,
     pub contract_address: ethers::types::Address,
     pub transfer_contract_address: ethers::types::Address,
+    /// The ICS29 fee middleware contract, packets aren't incentivized on Axon if unset
+    #[serde(default)]
+    pub fee_contract_address: Option<ethers::types::Address>,
     pub restore_block_count: u64,
     pub key_name: String,
+    #[serde(default)]
+    pub key_store_type: Store,
     pub store_prefix: String,
 
+    /// Where the event monitor gets IBC events from
+    #[serde(default)]
+    pub event_source: EventSource,
+
+    /// Interval between two queries of the event monitor in `polling` mode
+    #[serde(default = "default::poll_interval", with = "humantime_serde")]
+    pub poll_interval: Duration,
+
+    /// How long the client states of this chain are trusted after their latest
+    /// update, they never expire if unset
+    #[serde(default, with = "humantime_serde")]
+    pub trusting_period: Option<Duration>,
+
+    // Note: tables need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML.
+    #[serde(default)]
+    pub gas: GasConfig,
+
+    #[serde(default)]
+    pub diagnostics: DiagnosticsConfig,
+
+    #[serde(default)]
+    pub balance: BalanceConfig,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 }
+
+/// Where the ingredients of Axon block proofs failing to be verified are saved.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DiagnosticsConfig {
+    #[serde(default = "default::diagnostics_enabled")]
+    pub enabled: bool,
+
+    /// Defaults to `~/.forcerelay/diagnostics/<chain-id>`
+    pub dir: Option<PathBuf>,
+
+    /// Number of bundles retained in the directory, older ones are removed, 0 keeps all of them
+    #[serde(default = "default::max_diagnostics_bundles")]
+    pub max_bundles: usize,
+}
+
+impl DiagnosticsConfig {
+    /// The directory of diagnostics bundles, or `None` if diagnostics are disabled
+    pub fn dir(&self, chain_id: &ChainId) -> Option<PathBuf> {
+        if !self.enabled {
+            return None;
+        }
+        match &self.dir {
+            Some(dir) => Some(dir.clone()),
+            None => dirs_next::home_dir().map(|home| {
+                home.join(DIAGNOSTICS_DEFAULT_FOLDER)
+                    .join(chain_id.as_str())
+            }),
+        }
+    }
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default::diagnostics_enabled(),
+            dir: None,
+            max_bundles: default::max_diagnostics_bundles(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSource {
+    /// Subscribe to the logs of the IBC contract through `websocket_addr`
+    #[default]
+    WebSocket,
+    /// Periodically query the logs of the IBC contract through `rpc_addr`
+    Polling,
+}
+
+/// Gas and fee settings applied to every transaction sent to Axon.
+///
+/// All prices are denominated in wei.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasConfig {
+    /// Multiplier applied to the result of `eth_estimateGas`
+    #[serde(default = "default::gas_multiplier")]
+    pub gas_multiplier: f64,
+
+    /// Transactions whose (multiplied) gas estimation exceeds this limit are not sent
+    pub max_gas_limit: Option<u64>,
+
+    /// Send EIP-1559 transactions instead of legacy ones
+    #[serde(default)]
+    pub eip1559: bool,
+
+    /// Cap of the legacy gas price, or of `max_fee_per_gas` if EIP-1559 is enabled
+    pub max_gas_price: Option<u64>,
+
+    /// Cap of `max_priority_fee_per_gas`, only used if EIP-1559 is enabled
+    pub max_priority_fee_per_gas: Option<u64>,
+
+    /// How long a transaction may stay pending before it is replaced with bumped fees
+    #[serde(default = "default::tx_replacement_timeout", with = "humantime_serde")]
+    pub tx_replacement_timeout: Duration,
+
+    /// Percentage by which fees are increased on each replacement
+    #[serde(default = "default::fee_bump_percent")]
+    pub fee_bump_percent: u64,
+
+    /// Number of replacements before giving up on a transaction
+    #[serde(default = "default::max_tx_replacements")]
+    pub max_tx_replacements: u32,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            gas_multiplier: default::gas_multiplier(),
+            max_gas_limit: None,
+            eip1559: false,
+            max_gas_price: None,
+            max_priority_fee_per_gas: None,
+            tx_replacement_timeout: default::tx_replacement_timeout(),
+            fee_bump_percent: default::fee_bump_percent(),
+            max_tx_replacements: default::max_tx_replacements(),
+        }
+    }
+}
+
+pub mod default {
+    use super::*;
+
+    pub fn poll_interval() -> Duration {
+        Duration::from_secs(3)
+    }
+
+    pub fn diagnostics_enabled() -> bool {
+        true
+    }
+
+    pub fn max_diagnostics_bundles() -> usize {
+        10
+    }
+
+    pub fn gas_multiplier() -> f64 {
+        1.2
+    }
+
+    pub fn tx_replacement_timeout() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    // most Ethereum-compatible nodes require at least 10% to accept a replacement
+    pub fn fee_bump_percent() -> u64 {
+        20
+    }
+
+    pub fn max_tx_replacements() -> u32 {
+        3
+    }
+}
@@ -0,0 +1,54 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Balances of the relayer account watched by the wallet worker.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceConfig {
+    /// Tokens reported along with the native balance, sUDT symbols on Ckb4Ibc
+    /// chains and ERC20 contract addresses on Axon chains
+    #[serde(default)]
+    pub tokens: Vec<String>,
+
+    /// Warn when the native balance available for fees drops below this amount,
+    /// in the smallest unit of the native token, i.e. shannons or wei
+    pub min_balance: Option<f64>,
+
+    /// Stop relaying packets from and to the chain while its native balance is
+    /// below `min_balance`, relaying resumes once the account is refilled
+    #[serde(default)]
+    pub pause_on_low_balance: bool,
+}
+
+impl BalanceConfig {
+    /// Whether `amount` of the native token is below the configured minimum
+    pub fn is_low(&self, amount: f64) -> bool {
+        self.min_balance
+            .map_or(false, |min_balance| amount < min_balance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_balance_config() {
+        let config: BalanceConfig = toml::from_str(
+            r#"
+            tokens = ["0x4d7a28bbd0efa9ef5ab5a1c2cb4dd40b8b8e0d5b"]
+            min_balance = 1e20
+            pause_on_low_balance = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.tokens.len(), 1);
+        assert!(config.is_low(5e19));
+        assert!(!config.is_low(1e20));
+        assert!(config.pause_on_low_balance);
+
+        let config: BalanceConfig = toml::from_str("").unwrap();
+        assert!(config.tokens.is_empty());
+        assert!(!config.is_low(0.0));
+        assert!(toml::from_str::<BalanceConfig>("min_amount = 1.0").is_err());
+    }
+}
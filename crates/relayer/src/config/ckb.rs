@@ -15,6 +15,11 @@ pub struct ChainConfig {
     pub minimal_updates_count: u8,
     pub key_name: String,
     pub data_dir: PathBuf,
+    /// Remove the MMR nodes only needed to prove the beacon headers before the
+    /// minimal slot of the on-chain multi-client, which makes `data_dir` stop
+    /// growing but prevents the storage from being rolled back to older slots
+    #[serde(default)]
+    pub mmr_pruning: bool,
     pub client_type_args: ClientTypeArgs,
 }
 
@@ -12,10 +12,13 @@ use ibc_relayer_types::core::{
 use serde::ser::SerializeMap;
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use tendermint_rpc::Url;
 
 use crate::error::Error;
+use crate::keyring::Store;
 
+use super::balance::BalanceConfig;
 use super::filter::PacketFilter;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +34,8 @@ pub struct ChainConfig {
     pub ckb_rpc: Url,
     pub ckb_indexer_rpc: Url,
     pub key_name: String,
+    #[serde(default)]
+    pub key_store_type: Store,
     pub store_prefix: String,
 
     pub client_code_hash: H256,
@@ -38,6 +43,20 @@ pub struct ChainConfig {
     pub channel_type_args: H256,
     pub packet_type_args: H256,
 
+    /// How long the client states of this chain are trusted after their latest
+    /// update, they never expire if unset
+    #[serde(default, with = "humantime_serde")]
+    pub trusting_period: Option<Duration>,
+
+    #[serde(default)]
+    pub reclaim: ReclaimConfig,
+
+    #[serde(default)]
+    pub wallet: WalletConfig,
+
+    #[serde(default)]
+    pub balance: BalanceConfig,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
@@ -45,6 +64,75 @@ pub struct ChainConfig {
     pub onchain_light_clients: HashMap<ClientType, LightClientItem>,
 }
 
+/// The least number of blocks, about a day, the applications are given to consume their
+/// `Ack` and timed out packet cells before the relayer may reclaim them
+pub const MIN_ABANDONED_BLOCK_GAP: u64 = 10_000;
+
+/// Reclamation of the capacity locked by the packet cells of finished packets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReclaimConfig {
+    /// Reclaim finished packet cells in the background while relaying, which
+    /// reclaims nothing unless `abandoned_block_gap` is set too
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Number of blocks a `WriteAck` packet cell is kept before a `RecvPacket`
+    /// transaction recycles it
+    #[serde(default = "default::reclaim_block_gap")]
+    pub block_gap: u64,
+
+    /// Number of blocks after which the `Ack` and timed out packet cells not consumed
+    /// by their applications are considered abandoned and consumed by the relayer.
+    /// These cells settle the packets of the applications, so they're never
+    /// reclaimed if unset, and the gap can't be less than `MIN_ABANDONED_BLOCK_GAP`
+    #[serde(default)]
+    pub abandoned_block_gap: Option<u64>,
+
+    /// Maximum number of packet cells consumed by a single transaction
+    #[serde(default = "default::reclaim_batch_size")]
+    pub batch_size: usize,
+
+    /// Interval between two rounds of the background reclaimer
+    #[serde(default = "default::reclaim_interval", with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+impl Default for ReclaimConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            block_gap: default::reclaim_block_gap(),
+            abandoned_block_gap: None,
+            batch_size: default::reclaim_batch_size(),
+            interval: default::reclaim_interval(),
+        }
+    }
+}
+
+/// Management of the cells paying the fees of the relayer transactions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WalletConfig {
+    /// Number of cells the relayer wallet is split into at startup, so that
+    /// several transactions can be in flight at once, 0 disables splitting
+    #[serde(default = "default::wallet_fee_cells")]
+    pub fee_cells: usize,
+
+    /// Capacity of each of the split cells, in CKB
+    #[serde(default = "default::wallet_fee_cell_capacity")]
+    pub fee_cell_capacity: u64,
+}
+
+impl Default for WalletConfig {
+    fn default() -> Self {
+        Self {
+            fee_cells: default::wallet_fee_cells(),
+            fee_cell_capacity: default::wallet_fee_cell_capacity(),
+        }
+    }
+}
+
 impl ChainConfig {
     pub fn lc_chain_id_by_client_id(&self, client_id: &str) -> Result<ChainId, Error> {
         let chain_id = self
@@ -173,3 +261,27 @@ fn calc_type_hash(client_code_hash: &H256, client_type_args: &H256) -> H256 {
         .calc_script_hash();
     client_type_hash.unpack()
 }
+
+pub mod default {
+    use super::*;
+
+    pub fn reclaim_block_gap() -> u64 {
+        15
+    }
+
+    pub fn reclaim_batch_size() -> usize {
+        10
+    }
+
+    pub fn reclaim_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn wallet_fee_cells() -> usize {
+        4
+    }
+
+    pub fn wallet_fee_cell_capacity() -> u64 {
+        1000
+    }
+}
@@ -21,5 +21,9 @@ define_error! {
         Invalid
             { detail: String }
             |e| { format!("invaid config: {}", e.detail) },
+
+        UnresolvedContract
+            { field: String, reason: String }
+            |e| { format!("contract of `{}` cannot be resolved on-chain: {}", e.field, e.reason) },
     }
 }
@@ -21,15 +21,17 @@ pub fn array_hex_deserialize<'de, D, const N: usize>(deserializer: D) -> Result<
 where
     D: serde::Deserializer<'de>,
 {
-    let val: String = serde::Deserialize::deserialize(deserializer)?;
-    let val = val.strip_prefix("0x").unwrap();
-    let v = hex::decode(val).unwrap();
+    use serde::de::Error;
 
-    let result = v.try_into().unwrap_or_else(|v: Vec<u8>| {
-        panic!("Expected a Vec of length {} but it was {}", N, v.len())
-    });
+    let val: String = serde::Deserialize::deserialize(deserializer)?;
+    let hex_str = val
+        .strip_prefix("0x")
+        .ok_or_else(|| D::Error::custom(format!("expected a 0x-prefixed hex string: {val}")))?;
+    let v = hex::decode(hex_str)
+        .map_err(|e| D::Error::custom(format!("invalid hex string {val}: {e}")))?;
 
-    Ok(result)
+    v.try_into()
+        .map_err(|v: Vec<u8>| D::Error::custom(format!("expected {} bytes but got {}", N, v.len())))
 }
 
 impl EthChainConfig {
@@ -86,3 +88,33 @@ impl EthChainConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Checkpoint {
+        #[serde(deserialize_with = "super::array_hex_deserialize")]
+        checkpoint: [u8; 4],
+    }
+
+    fn parse(checkpoint: &str) -> Result<[u8; 4], String> {
+        serde_json::from_value::<Checkpoint>(serde_json::json!({ "checkpoint": checkpoint }))
+            .map(|c| c.checkpoint)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_array_hex_deserialize() {
+        assert_eq!(parse("0x01020304"), Ok([1, 2, 3, 4]));
+
+        assert!(parse("01020304").unwrap_err().contains("0x-prefixed"));
+        assert!(parse("0x0102030g")
+            .unwrap_err()
+            .contains("invalid hex string"));
+        assert!(parse("0x010203")
+            .unwrap_err()
+            .contains("expected 4 bytes but got 3"));
+    }
+}
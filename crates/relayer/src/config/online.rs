@@ -0,0 +1,135 @@
+//! Resolution of the contracts referenced by a chain configuration on the chain itself
+
+use ckb_sdk::{
+    constants::TYPE_ID_CODE_HASH,
+    rpc::ckb_indexer::{ScriptSearchMode, ScriptType, SearchKey},
+};
+use ckb_types::{
+    core::ScriptHashType,
+    packed::Script,
+    prelude::{Builder, Entity, Pack},
+    H256,
+};
+use ethers::providers::{Http, Middleware, Provider};
+use tokio::runtime::Runtime as TokioRuntime;
+
+use super::{
+    axon::AxonChainConfig, ckb::ChainConfig as CkbChainConfig,
+    ckb4ibc::ChainConfig as Ckb4IbcChainConfig, ChainConfig, Error,
+};
+use crate::chain::ckb::{prelude::CkbReader, rpc_client::RpcClient};
+
+/// Check that every contract the configuration of a chain points to is deployed
+/// on that chain. Cosmos and Eth configurations reference no contract.
+pub fn resolve_contracts(rt: &TokioRuntime, config: &ChainConfig) -> Result<(), Error> {
+    match config {
+        ChainConfig::Cosmos(_) | ChainConfig::Eth(_) => Ok(()),
+        ChainConfig::Axon(config) => rt.block_on(resolve_axon_contracts(config)),
+        ChainConfig::Ckb(config) => rt.block_on(resolve_ckb_contracts(config)),
+        ChainConfig::Ckb4Ibc(config) => rt.block_on(resolve_ckb4ibc_contracts(config)),
+    }
+}
+
+async fn resolve_axon_contracts(config: &AxonChainConfig) -> Result<(), Error> {
+    let client = Provider::<Http>::try_from(config.rpc_addr.to_string())
+        .map_err(|e| Error::unresolved_contract("rpc_addr".to_owned(), e.to_string()))?;
+    let contracts = [
+        ("contract_address", Some(config.contract_address)),
+        (
+            "transfer_contract_address",
+            Some(config.transfer_contract_address),
+        ),
+        ("fee_contract_address", config.fee_contract_address),
+    ];
+    for (field, address) in contracts
+        .into_iter()
+        .filter_map(|(field, address)| Some((field, address?)))
+    {
+        let code = client
+            .get_code(address, None)
+            .await
+            .map_err(|e| Error::unresolved_contract(field.to_owned(), e.to_string()))?;
+        if code.is_empty() {
+            return Err(Error::unresolved_contract(
+                field.to_owned(),
+                format!("no contract deployed at {address:?}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn resolve_ckb_contracts(config: &CkbChainConfig) -> Result<(), Error> {
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    let contracts = [
+        (
+            "lightclient_contract_typeargs",
+            &config.lightclient_contract_typeargs,
+        ),
+        (
+            "lightclient_lock_typeargs",
+            &config.lightclient_lock_typeargs,
+        ),
+    ];
+    for (field, type_args) in contracts {
+        resolve_cell(&rpc_client, field, type_id_script(type_args)).await?;
+    }
+    Ok(())
+}
+
+async fn resolve_ckb4ibc_contracts(config: &Ckb4IbcChainConfig) -> Result<(), Error> {
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+    let contracts = [
+        ("connection_type_args", &config.connection_type_args),
+        ("channel_type_args", &config.channel_type_args),
+        ("packet_type_args", &config.packet_type_args),
+    ];
+    for (field, type_args) in contracts {
+        resolve_cell(&rpc_client, field, type_id_script(type_args)).await?;
+    }
+    for (client_type, light_client) in &config.onchain_light_clients {
+        let client_script = Script::new_builder()
+            .code_hash(config.client_code_hash.pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(light_client.client_cell_type_args.as_bytes().pack())
+            .build();
+        let field = format!("onchain_light_clients.{client_type}.client_cell_type_args");
+        resolve_cell(&rpc_client, &field, client_script).await?;
+    }
+    Ok(())
+}
+
+/// The type script of the cell holding a contract deployed with a type id
+fn type_id_script(type_args: &H256) -> Script {
+    Script::new_builder()
+        .code_hash(TYPE_ID_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(type_args.as_bytes().pack())
+        .build()
+}
+
+async fn resolve_cell(
+    rpc_client: &RpcClient,
+    field: &str,
+    type_script: Script,
+) -> Result<(), Error> {
+    let search_key = SearchKey {
+        script: type_script.into(),
+        script_type: ScriptType::Type,
+        script_search_mode: Some(ScriptSearchMode::Exact),
+        filter: None,
+        with_data: Some(false),
+        group_by_transaction: None,
+    };
+    let cells = rpc_client
+        .fetch_live_cells(search_key, 1, None)
+        .await
+        .map_err(|e| Error::unresolved_contract(field.to_owned(), e.to_string()))?;
+    if cells.objects.is_empty() {
+        return Err(Error::unresolved_contract(
+            field.to_owned(),
+            "no live cell found".to_owned(),
+        ));
+    }
+    Ok(())
+}
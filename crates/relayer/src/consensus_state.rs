@@ -45,9 +45,9 @@ impl AnyConsensusState {
     pub fn timestamp(&self) -> Timestamp {
         match self {
             Self::Tendermint(cs_state) => cs_state.timestamp.into(),
-            Self::Eth(_) => todo!(),
+            Self::Eth(eth_state) => eth_state.timestamp(),
             Self::Ckb(ckb_state) => ckb_state.timestamp(),
-            Self::Axon(_) => Default::default(),
+            Self::Axon(axon_state) => axon_state.timestamp(),
 
             #[cfg(test)]
             Self::Mock(mock_state) => mock_state.timestamp(),
@@ -81,6 +81,14 @@ impl TryFrom<Any> for AnyConsensusState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
+            CKB_CONSENSUS_STATE_TYPE_URL => {
+                Ok(AnyConsensusState::Ckb(CkbConsensusState::try_from(value)?))
+            }
+
+            AXON_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Axon(
+                AxonConsensusState::try_from(value)?,
+            )),
+
             #[cfg(test)]
             MOCK_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Mock(
                 Protobuf::<RawMockConsensusState>::decode_vec(&value.value)
@@ -218,9 +226,9 @@ impl ConsensusState for AnyConsensusState {
     fn root(&self) -> &CommitmentRoot {
         match self {
             Self::Tendermint(cs_state) => cs_state.root(),
-            Self::Eth(_) => todo!(),
-            Self::Ckb(_) => todo!(),
-            Self::Axon(_) => todo!(),
+            Self::Eth(cs_state) => cs_state.root(),
+            Self::Ckb(cs_state) => cs_state.root(),
+            Self::Axon(cs_state) => cs_state.root(),
 
             #[cfg(test)]
             Self::Mock(mock_state) => mock_state.root(),
@@ -1,15 +1,19 @@
 pub mod errors;
 pub use any_signing_key_pair::AnySigningKeyPair;
 pub use ed25519_key_pair::Ed25519KeyPair;
+pub use encrypted::{passphrase, Encrypted, PASSPHRASE_ENV, PASSPHRASE_FILE_ENV};
 pub use key_type::KeyType;
+pub use remote_signer::RemoteSigner;
 pub use secp256k1_key_pair::{Secp256k1AddressType, Secp256k1KeyPair};
 pub use signing_key_pair::{SigningKeyPair, SigningKeyPairSized};
 
 mod any_signing_key_pair;
 mod ed25519_key_pair;
+mod encrypted;
 mod key_type;
 mod key_utils;
 mod pub_key;
+mod remote_signer;
 mod secp256k1_key_pair;
 mod signing_key_pair;
 
@@ -26,6 +30,7 @@ use errors::Error;
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".forcerelay/keys/";
 pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_ENCRYPTED_BACKEND: &str = "keyring-encrypted";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
 /// JSON key seed file
@@ -185,6 +190,9 @@ impl<S: SigningKeyPairSized> KeyStore<S> for Test {
 pub enum Store {
     Memory,
     Test,
+    /// Keys encrypted with the passphrase from `FORCERELAY_KEYSTORE_PASSPHRASE`
+    /// or `FORCERELAY_KEYSTORE_PASSPHRASE_FILE`
+    Encrypted,
 }
 
 impl Default for Store {
@@ -197,6 +205,7 @@ impl Default for Store {
 pub enum KeyRing<S> {
     Memory(Memory<S>),
     Test(Test),
+    Encrypted(Encrypted),
 }
 
 impl<S: SigningKeyPairSized> KeyRing<S> {
@@ -205,22 +214,22 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
             Store::Memory => Ok(Self::Memory(Memory::new(account_prefix.to_string()))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_id.as_str())?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Error::key_file_io(
-                        keys_folder.display().to_string(),
-                        "failed to create keys folder".to_string(),
-                        e,
-                    )
-                })?;
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_DISK_BACKEND)?;
 
                 Ok(Self::Test(Test::new(
                     account_prefix.to_string(),
                     keys_folder,
                 )))
             }
+
+            Store::Encrypted => {
+                let keys_folder = disk_store_path(chain_id.as_str(), KEYSTORE_ENCRYPTED_BACKEND)?;
+
+                Ok(Self::Encrypted(Encrypted::new(
+                    account_prefix.to_string(),
+                    keys_folder,
+                )))
+            }
         }
     }
 
@@ -228,6 +237,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.get_key(key_name),
             Self::Test(d) => d.get_key(key_name),
+            Self::Encrypted(e) => e.get_key(key_name),
         }
     }
 
@@ -235,6 +245,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.add_key(key_name, key_entry),
             Self::Test(d) => d.add_key(key_name, key_entry),
+            Self::Encrypted(e) => e.add_key(key_name, key_entry),
         }
     }
 
@@ -242,6 +253,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.remove_key(key_name),
             Self::Test(d) => <Test as KeyStore<S>>::remove_key(d, key_name),
+            Self::Encrypted(e) => <Encrypted as KeyStore<S>>::remove_key(e, key_name),
         }
     }
 
@@ -249,6 +261,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => m.keys(),
             Self::Test(d) => d.keys(),
+            Self::Encrypted(e) => e.keys(),
         }
     }
 
@@ -256,6 +269,7 @@ impl<S: SigningKeyPairSized> KeyRing<S> {
         match self {
             Self::Memory(m) => &m.account_prefix,
             Self::Test(d) => &d.account_prefix,
+            Self::Encrypted(e) => &e.account_prefix,
         }
     }
 }
@@ -289,7 +303,7 @@ pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)
         ChainType::Ckb4Ibc => "ckb4ibc",
     };
     let keys = {
-        let keyring = KeyRing::new_secp256k1(Store::Test, account_prefix, config.id())?;
+        let keyring = KeyRing::new_secp256k1(config.key_store_type(), account_prefix, config.id())?;
         keyring
             .keys()?
             .into_iter()
@@ -299,13 +313,22 @@ pub fn list_keys(config: &ChainConfig) -> Result<Vec<(String, AnySigningKeyPair)
     Ok(keys)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+/// The folder of the keys of a chain in the backend, created if it does not exist
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir().ok_or_else(Error::home_location_unavailable)?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
+
+    fs::create_dir_all(&folder).map_err(|e| {
+        Error::key_file_io(
+            folder.display().to_string(),
+            "failed to create keys folder".to_string(),
+            e,
+        )
+    })?;
 
     Ok(folder)
 }
@@ -0,0 +1,207 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use secp256k1::rand::thread_rng;
+use serde::{Deserialize, Serialize};
+
+use super::{errors::Error, KeyStore, SigningKeyPairSized, KEYSTORE_FILE_EXTENSION};
+
+/// Environment variable holding the passphrase of encrypted keys
+pub const PASSPHRASE_ENV: &str = "FORCERELAY_KEYSTORE_PASSPHRASE";
+/// Environment variable holding the path of a file containing the passphrase of encrypted keys
+pub const PASSPHRASE_FILE_ENV: &str = "FORCERELAY_KEYSTORE_PASSPHRASE_FILE";
+
+/// Keys encrypted at rest, each key file is a Web3 keystore (scrypt, AES-128-CTR)
+/// whose plaintext is the JSON of the key pair.
+///
+/// The passphrase is read from the environment on every access, so it never
+/// lives in the configuration nor in the key ring itself.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Encrypted {
+    pub(super) account_prefix: String,
+    store: PathBuf,
+}
+
+impl Encrypted {
+    pub fn new(account_prefix: String, store: PathBuf) -> Self {
+        Self {
+            account_prefix,
+            store,
+        }
+    }
+
+    fn key_file(&self, key_name: &str) -> PathBuf {
+        let mut key_file = self.store.join(key_name);
+        key_file.set_extension(KEYSTORE_FILE_EXTENSION);
+        key_file
+    }
+}
+
+/// The passphrase of encrypted keys, `FORCERELAY_KEYSTORE_PASSPHRASE` takes
+/// precedence over `FORCERELAY_KEYSTORE_PASSPHRASE_FILE`
+pub fn passphrase() -> Result<String, Error> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let path = std::env::var(PASSPHRASE_FILE_ENV).map_err(|_| Error::missing_passphrase())?;
+    let passphrase = fs::read_to_string(&path)
+        .map_err(|e| Error::key_file_io(path, "failed to read passphrase file".to_string(), e))?;
+    Ok(passphrase.trim_end_matches(['\r', '\n']).to_string())
+}
+
+impl<S: SigningKeyPairSized> KeyStore<S> for Encrypted {
+    fn get_key(&self, key_name: &str) -> Result<S, Error> {
+        let key_file = self.key_file(key_name);
+        if !key_file.as_path().exists() {
+            return Err(Error::key_file_not_found(format!("{}", key_file.display())));
+        }
+
+        let plaintext = eth_keystore::decrypt_key(&key_file, passphrase()?)
+            .map_err(|e| Error::keystore(key_file.display().to_string(), e))?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| Error::key_file_decode(format!("{}", key_file.display()), e))
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: S) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+        let file_path = key_file.display().to_string();
+
+        let plaintext = serde_json::to_vec(&key_entry)
+            .map_err(|e| Error::key_file_encode(file_path.clone(), e))?;
+        let file_name = key_file
+            .file_name()
+            .and_then(OsStr::to_str)
+            .ok_or_else(|| Error::invalid_key_name(key_name.to_string()))?;
+        eth_keystore::encrypt_key(
+            &self.store,
+            &mut thread_rng(),
+            plaintext,
+            passphrase()?,
+            Some(file_name),
+        )
+        .map_err(|e| Error::keystore(file_path, e))?;
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        let key_file = self.key_file(key_name);
+
+        fs::remove_file(&key_file)
+            .map_err(|e| Error::remove_io_fail(key_file.display().to_string(), e))?;
+
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, S)>, Error> {
+        let dir = fs::read_dir(&self.store).map_err(|e| {
+            Error::key_file_io(
+                self.store.display().to_string(),
+                "failed to list keys".to_string(),
+                e,
+            )
+        })?;
+
+        let ext = OsStr::new(KEYSTORE_FILE_EXTENSION);
+
+        dir.into_iter()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension() == Some(ext))
+            .flat_map(|path| path.file_stem().map(OsStr::to_owned))
+            .flat_map(|stem| stem.to_str().map(ToString::to_string))
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::config::AddressType;
+    use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+    const SECRET_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    /// The passphrase is read from the process environment, shared by the tests
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn set_passphrase(passphrase: Option<&str>, file: Option<&PathBuf>) {
+        match passphrase {
+            Some(passphrase) => std::env::set_var(PASSPHRASE_ENV, passphrase),
+            None => std::env::remove_var(PASSPHRASE_ENV),
+        }
+        match file {
+            Some(file) => std::env::set_var(PASSPHRASE_FILE_ENV, file),
+            None => std::env::remove_var(PASSPHRASE_FILE_ENV),
+        }
+    }
+
+    fn key() -> Secp256k1KeyPair {
+        Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Axon).unwrap()
+    }
+
+    #[test]
+    fn test_encrypted_key_round_trip() {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = Encrypted::new(String::new(), dir.path().to_path_buf());
+        set_passphrase(Some("secret"), None);
+
+        store.add_key("relayer", key()).unwrap();
+        // the private key isn't stored in plaintext
+        let content = fs::read_to_string(dir.path().join("relayer.json")).unwrap();
+        assert!(!content.contains(SECRET_KEY));
+
+        let stored: Secp256k1KeyPair = store.get_key("relayer").unwrap();
+        assert_eq!(stored.account(), key().account());
+        let keys: Vec<(String, Secp256k1KeyPair)> = store.keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].0, "relayer");
+        assert_eq!(keys[0].1.account(), key().account());
+
+        <Encrypted as KeyStore<Secp256k1KeyPair>>::remove_key(&mut store, "relayer").unwrap();
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&store, "relayer").is_err());
+        assert!(KeyStore::<Secp256k1KeyPair>::keys(&store)
+            .unwrap()
+            .is_empty());
+        set_passphrase(None, None);
+    }
+
+    #[test]
+    fn test_encrypted_key_wrong_passphrase() {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = Encrypted::new(String::new(), dir.path().to_path_buf());
+        set_passphrase(Some("secret"), None);
+        store.add_key("relayer", key()).unwrap();
+
+        set_passphrase(Some("wrong"), None);
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&store, "relayer").is_err());
+        set_passphrase(None, None);
+        assert!(KeyStore::<Secp256k1KeyPair>::get_key(&store, "relayer").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_key_passphrase_file() {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let passphrase_file = dir.path().join("passphrase");
+        fs::write(&passphrase_file, "secret\r\n").unwrap();
+        let mut store = Encrypted::new(String::new(), dir.path().join("keys"));
+        fs::create_dir(dir.path().join("keys")).unwrap();
+
+        set_passphrase(None, Some(&passphrase_file));
+        assert_eq!(passphrase().unwrap(), "secret");
+        store.add_key("relayer", key()).unwrap();
+
+        // the trailing newline isn't part of the passphrase
+        set_passphrase(Some("secret"), None);
+        let stored: Secp256k1KeyPair = store.get_key("relayer").unwrap();
+        assert_eq!(stored.account(), key().account());
+        set_passphrase(None, None);
+    }
+}
@@ -92,6 +92,13 @@ define_error! {
                     e.file_path)
             },
 
+        InvalidKeyName
+            { key_name: String }
+            |e| {
+                format!("invalid key name '{}'",
+                    e.key_name)
+            },
+
         HomeLocationUnavailable
             |_| { "home location is unavailable" },
 
@@ -140,6 +147,30 @@ define_error! {
             [ TraceError<bs58::decode::Error> ]
             |_| { "bs58 decode error" },
 
+        Keystore
+            { file_path: String }
+            [ DisplayOnly<eth_keystore::KeystoreError> ]
+            |e| {
+                format!("cannot decrypt keystore file at '{}'",
+                    e.file_path)
+            },
+
+        MissingPassphrase
+            |_| {
+                format!("the passphrase of encrypted keys is not set, set either `{}` or `{}`",
+                    super::PASSPHRASE_ENV, super::PASSPHRASE_FILE_ENV)
+            },
+
+        RemoteSigner
+            {
+                url: String,
+                reason: String,
+            }
+            |e| {
+                format!("remote signer at '{}' failed: {}",
+                    e.url, e.reason)
+            },
+
         Secp256k1
             { message: String }
             |e| { format!("secp256k1 error: {}", e.message) },
@@ -0,0 +1,258 @@
+use core::any::Any;
+use core::time::Duration;
+
+use hdpath::StandardHDPath;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId, Signature},
+    PublicKey, Secp256k1,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    errors::Error,
+    secp256k1_key_pair::{encode_address, get_address, signing_message},
+    KeyFile, KeyType, Secp256k1AddressType, SigningKeyPair,
+};
+use crate::config::AddressType;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// Hex of the compressed public key, identifies the key held by the service
+    pub public_key: String,
+    /// Hex of the 32-byte digest to sign
+    pub digest: String,
+    /// Whether a 65-byte recoverable signature is expected instead of a 64-byte compact one
+    pub recoverable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignResponse {
+    /// Hex of the signature, the recovery id is the last byte of recoverable ones
+    pub signature: String,
+}
+
+/// A secp256k1 key whose private part is held by an HTTP signing service.
+///
+/// The key file only stores the service URL and the public key, the service is
+/// asked to sign with a [`SignRequest`] posted to the URL and its signature is
+/// checked against the public key before being used.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteSigner {
+    pub url: String,
+    pub public_key: PublicKey,
+    address_type: Secp256k1AddressType,
+    account: String,
+}
+
+impl RemoteSigner {
+    pub fn new(
+        url: String,
+        public_key: PublicKey,
+        address_type: &AddressType,
+        account_prefix: &str,
+    ) -> Result<Self, Error> {
+        let address_type: Secp256k1AddressType = address_type.try_into()?;
+        let address = get_address(&public_key, address_type);
+        let account = match address_type {
+            Secp256k1AddressType::Cosmos => encode_address(account_prefix, &address)?,
+            _ => hex::encode(address),
+        };
+        Ok(Self {
+            url,
+            public_key,
+            address_type,
+            account,
+        })
+    }
+
+    fn request_signature(&self, request: &SignRequest) -> Result<Vec<u8>, Error> {
+        let remote_error = |reason: String| Error::remote_signer(self.url.clone(), reason);
+        let response: SignResponse = reqwest::blocking::Client::new()
+            .post(&self.url)
+            .timeout(REQUEST_TIMEOUT)
+            .json(request)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json())
+            .map_err(|e| remote_error(e.to_string()))?;
+        hex::decode(response.signature.trim_start_matches("0x"))
+            .map_err(|e| remote_error(format!("invalid signature: {e}")))
+    }
+}
+
+impl SigningKeyPair for RemoteSigner {
+    const KEY_TYPE: KeyType = KeyType::Secp256k1;
+
+    fn from_secret_key(_secret_key: &str, _address_type: &AddressType) -> Result<Self, Error> {
+        Err(Error::secp256k1(
+            "a remote signer holds no secret key".to_owned(),
+        ))
+    }
+
+    fn from_key_file(_key_file: KeyFile, _hd_path: &StandardHDPath) -> Result<Self, Error> {
+        Err(Error::secp256k1(
+            "a remote signer holds no secret key".to_owned(),
+        ))
+    }
+
+    fn from_mnemonic(
+        _mnemonic: &str,
+        _hd_path: &StandardHDPath,
+        _address_type: &AddressType,
+        _account_prefix: &str,
+    ) -> Result<Self, Error> {
+        Err(Error::secp256k1(
+            "a remote signer holds no secret key".to_owned(),
+        ))
+    }
+
+    fn account(&self) -> String {
+        self.account.to_owned()
+    }
+
+    /// Same signatures as [`Secp256k1KeyPair`](super::Secp256k1KeyPair) for the address type
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let message = signing_message(self.address_type, message)?;
+        let recoverable = self.address_type == Secp256k1AddressType::Ckb;
+        let signature = self.request_signature(&SignRequest {
+            public_key: hex::encode(self.public_key.serialize()),
+            digest: hex::encode(message.as_ref()),
+            recoverable,
+        })?;
+
+        let secp = Secp256k1::verification_only();
+        let verified = if recoverable {
+            signature.len() == 65
+                && RecoveryId::from_i32(signature[64] as i32)
+                    .and_then(|id| RecoverableSignature::from_compact(&signature[..64], id))
+                    .and_then(|signature| secp.recover_ecdsa(&message, &signature))
+                    .map_or(false, |public_key| public_key == self.public_key)
+        } else {
+            Signature::from_compact(&signature)
+                .and_then(|signature| secp.verify_ecdsa(&message, &signature, &self.public_key))
+                .is_ok()
+        };
+        if !verified {
+            return Err(Error::remote_signer(
+                self.url.clone(),
+                "signature doesn't match the public key".to_owned(),
+            ));
+        }
+        Ok(signature)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::str::FromStr;
+    use std::thread;
+
+    use secp256k1::{Message, SecretKey};
+
+    use super::*;
+    use crate::keyring::Secp256k1KeyPair;
+
+    const SECRET_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    /// Serve `count` sign requests with `secret_key`, returns the URL of the stub
+    fn spawn_stub_signer(secret_key: SecretKey, count: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: SignRequest = serde_json::from_slice(&body).unwrap();
+
+                let digest = Message::from_slice(&hex::decode(request.digest).unwrap()).unwrap();
+                let secp = Secp256k1::signing_only();
+                let signature = if request.recoverable {
+                    let (id, data) = secp
+                        .sign_ecdsa_recoverable(&digest, &secret_key)
+                        .serialize_compact();
+                    [&data[..], &[id.to_i32() as u8]].concat()
+                } else {
+                    secp.sign_ecdsa(&digest, &secret_key)
+                        .serialize_compact()
+                        .to_vec()
+                };
+                let body = serde_json::to_string(&SignResponse {
+                    signature: hex::encode(signature),
+                })
+                .unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_remote_signatures_match_local_ones() {
+        for address_type in [AddressType::Axon, AddressType::Ckb] {
+            let local = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &address_type).unwrap();
+            let url = spawn_stub_signer(local.private_key, 1);
+            let remote = RemoteSigner::new(url, local.public_key, &address_type, "").unwrap();
+            assert_eq!(remote.account(), local.account());
+
+            let message = [7u8; 32];
+            assert_eq!(
+                remote.sign(&message).unwrap(),
+                local.sign(&message).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_remote_signature_verifies_against_public_key() {
+        // the local key pair only provides the keys, the Cosmos digest is SHA-256
+        let local = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Axon).unwrap();
+        let url = spawn_stub_signer(local.private_key, 1);
+        let remote =
+            RemoteSigner::new(url, local.public_key, &AddressType::Cosmos, "cosmos").unwrap();
+
+        let message = b"message";
+        let signature = Signature::from_compact(&remote.sign(message).unwrap()).unwrap();
+        let digest = signing_message(Secp256k1AddressType::Cosmos, message).unwrap();
+        Secp256k1::verification_only()
+            .verify_ecdsa(&digest, &signature, &local.public_key)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_reject_signature_of_other_key() {
+        let local = Secp256k1KeyPair::from_secret_key(SECRET_KEY, &AddressType::Axon).unwrap();
+        let other_key = SecretKey::from_str(&"11".repeat(32)).unwrap();
+        let url = spawn_stub_signer(other_key, 1);
+        let remote = RemoteSigner::new(url, local.public_key, &AddressType::Axon, "").unwrap();
+        assert!(remote.sign(b"message").is_err());
+    }
+}
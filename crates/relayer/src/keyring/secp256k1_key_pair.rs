@@ -1,4 +1,5 @@
 use core::any::Any;
+use std::path::Path;
 use std::str::FromStr;
 
 use bip39::{Language, Mnemonic, Seed};
@@ -129,13 +130,39 @@ pub fn get_address(public_key: &PublicKey, address_type: Secp256k1AddressType) -
     }
 }
 
+/// The digest actually signed for a message, according to the address type
+pub(crate) fn signing_message(
+    address_type: Secp256k1AddressType,
+    message: &[u8],
+) -> Result<Message, Error> {
+    let message = match address_type {
+        Secp256k1AddressType::Ethermint
+        | Secp256k1AddressType::Cosmos
+        | Secp256k1AddressType::Axon => {
+            let hashed_message: GenericArray<u8, U32> = match address_type {
+                Secp256k1AddressType::Ethermint | Secp256k1AddressType::Axon => {
+                    keccak256_hash(message).into()
+                }
+                Secp256k1AddressType::Cosmos => Sha256::digest(message),
+                _ => unreachable!("checked"),
+            };
+
+            // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`,
+            // so `unwrap` is safe.
+            Message::from_slice(hashed_message.as_slice()).unwrap()
+        }
+        Secp256k1AddressType::Ckb => Message::from_slice(message)?,
+    };
+    Ok(message)
+}
+
 // Cosmos address: bech32("cosmos", ripemd160(sha256(public_key)))
 // - For bech32, data must be in 5-bit chunks, with zeroes padded at the end.
 //
 // Ethermint address: bech32("evmos", keccak256(public_key)[12:])
 // - They also have ETH-compatible addresses using base64 instead of bech32.
 //   Hex addresses contain a 0x prefix.
-fn encode_address(account_prefix: &str, address: &[u8]) -> Result<String, Error> {
+pub(crate) fn encode_address(account_prefix: &str, address: &[u8]) -> Result<String, Error> {
     encode_bech32(account_prefix, address)
 }
 
@@ -258,6 +285,17 @@ impl Secp256k1KeyPair {
         )
     }
 
+    /// Import the secret key of an encrypted Web3 keystore file, either scrypt or pbkdf2
+    pub fn from_keystore(
+        path: &Path,
+        passphrase: &str,
+        address_type: &AddressType,
+    ) -> Result<Self, Error> {
+        let secret_key = eth_keystore::decrypt_key(path, passphrase)
+            .map_err(|e| Error::keystore(path.display().to_string(), e))?;
+        Self::from_secret_key(&hex::encode(secret_key), address_type)
+    }
+
     pub fn into_ckb_keypair(self, network: NetworkType) -> Self {
         if let Secp256k1AddressType::Ckb = self.address_type {
             return self;
@@ -370,24 +408,7 @@ impl SigningKeyPair for Secp256k1KeyPair {
     // - https://github.com/evmos/ethermint/blob/main/crypto/ethsecp256k1/ethsecp256k1.go
     // - informalsystems/hermes#2863.
     fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
-        let message = match self.address_type {
-            Secp256k1AddressType::Ethermint
-            | Secp256k1AddressType::Cosmos
-            | Secp256k1AddressType::Axon => {
-                let hashed_message: GenericArray<u8, U32> = match self.address_type {
-                    Secp256k1AddressType::Ethermint | Secp256k1AddressType::Axon => {
-                        keccak256_hash(message).into()
-                    }
-                    Secp256k1AddressType::Cosmos => Sha256::digest(message),
-                    _ => unreachable!("checked"),
-                };
-
-                // SAFETY: hashed_message is 32 bytes, as expected in `Message::from_slice`,
-                // so `unwrap` is safe.
-                Message::from_slice(hashed_message.as_slice()).unwrap()
-            }
-            Secp256k1AddressType::Ckb => Message::from_slice(message)?,
-        };
+        let message = signing_message(self.address_type, message)?;
 
         let signature = match self.address_type {
             Secp256k1AddressType::Ethermint
@@ -7,7 +7,7 @@ use core::ops::Deref;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawTmHeader;
 use ibc_proto::protobuf::Protobuf as ErasedProtobuf;
-use ibc_relayer_types::clients::ics07_axon::header::AxonHeader;
+use ibc_relayer_types::clients::ics07_axon::header::{AxonHeader, AXON_HEADER_TYPE_URL};
 use ibc_relayer_types::clients::ics07_ckb::header::CkbHeader;
 use ibc_relayer_types::clients::ics07_eth::header::Header as EthHeader;
 use ibc_relayer_types::clients::ics07_tendermint::header::{
@@ -71,9 +71,11 @@ pub trait LightClient<C: ChainEndpoint>: Send + Sync {
 
 /// Decodes an encoded header into a known `Header` type,
 pub fn decode_header(header_bytes: &[u8]) -> Result<Box<dyn Header>, Error> {
-    // For now, we only have tendermint; however when there is more than one, we
-    // can try decoding into all the known types, and return an error only if
-    // none work
+    // the type url of the encoded `Any` tells the Axon headers apart, any other
+    // header is expected to be a Tendermint one
+    if let Ok(header) = <AxonHeader as ErasedProtobuf<Any>>::decode(header_bytes) {
+        return Ok(Box::new(header));
+    }
     let header: TendermintHeader =
         ErasedProtobuf::<Any>::decode(header_bytes).map_err(Error::invalid_raw_header)?;
 
@@ -131,6 +133,8 @@ impl TryFrom<Any> for AnyHeader {
                 Ok(AnyHeader::Tendermint(val))
             }
 
+            AXON_HEADER_TYPE_URL => Ok(AnyHeader::Axon(raw.try_into()?)),
+
             _ => Err(Error::unknown_header_type(raw.type_url)),
         }
     }
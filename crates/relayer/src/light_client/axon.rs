@@ -1,17 +1,23 @@
 #![allow(dead_code, unused_variables, unused_imports)]
 
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
 
 use axon_tools::types::Header as AxonChainHeader;
 use ethers::prelude::k256::ecdsa::SigningKey;
 use ethers::prelude::*;
 use futures::TryFutureExt;
-use ibc_relayer_types::clients::ics07_axon::{header::AxonHeader, light_block::AxonLightBlock};
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::clients::ics07_axon::{
+    header::AxonHeader,
+    light_block::AxonLightBlock,
+    misbehaviour::{AxonMisbehaviour, AxonSignedBlock},
+};
+use ibc_relayer_types::core::ics02_client::events::UpdateClient;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::chain::axon::{AxonChain, AxonRpc};
 use crate::chain::endpoint::ChainEndpoint;
@@ -22,10 +28,24 @@ use crate::misbehaviour::MisbehaviourEvidence;
 
 use super::Verified;
 
+/// Number of the latest observed blocks kept to detect conflicting ones
+const MAX_OBSERVED_BLOCKS: usize = 1024;
+
+/// Verified blocks the relayer has seen, by block number
+#[derive(Default)]
+struct ObservedBlocks {
+    blocks: BTreeMap<u64, AxonSignedBlock>,
+    /// The first pair of different blocks observed at each height
+    conflicts: BTreeMap<u64, (AxonSignedBlock, AxonSignedBlock)>,
+    /// Heights of the conflicts already reported for each client
+    reported: HashMap<ClientId, BTreeSet<u64>>,
+}
+
 pub struct LightClient {
     rt: Arc<TokioRuntime>,
     chain_id: ChainId,
     header_updaters: Arc<RwLock<Vec<Sender<AxonChainHeader>>>>,
+    observed: Mutex<ObservedBlocks>,
 }
 
 impl LightClient {
@@ -34,9 +54,35 @@ impl LightClient {
             rt,
             chain_id: config.id.clone(),
             header_updaters: Arc::new(RwLock::new(vec![])),
+            observed: Mutex::new(ObservedBlocks::default()),
         })
     }
 
+    /// Record a block whose proof has been verified, a different block already
+    /// observed at the same height is kept as evidence of misbehaviour
+    pub fn observe(&self, block: AxonSignedBlock) {
+        let mut observed = self.observed.lock().expect("poisoned observed blocks");
+        let number = block.number();
+        match observed.blocks.get(&number) {
+            Some(known) if known.hash() != block.hash() => {
+                warn!(
+                    "axon: conflicting blocks #{number} {:#x} and {:#x} are both signed by validators",
+                    known.hash(),
+                    block.hash()
+                );
+                let conflict = (known.clone(), block);
+                observed.conflicts.entry(number).or_insert(conflict);
+            }
+            Some(_) => {}
+            None => {
+                observed.blocks.insert(number, block);
+                while observed.blocks.len() > MAX_OBSERVED_BLOCKS {
+                    observed.blocks.pop_first();
+                }
+            }
+        }
+    }
+
     pub fn subscribe(&mut self) -> Receiver<AxonChainHeader> {
         let (tx, rx) = channel(1);
         self.rt.block_on(self.header_updaters.write()).push(tx);
@@ -110,15 +156,130 @@ impl super::LightClient<AxonChain> for LightClient {
         })
     }
 
+    /// Validators signing conflicting blocks compromise the client whatever the
+    /// height of the update is, so the earliest conflict not yet reported for the
+    /// updated client is reported, once per client
     fn check_misbehaviour(
         &mut self,
-        update: &ibc_relayer_types::core::ics02_client::events::UpdateClient,
+        update: &UpdateClient,
         client_state: &AnyClientState,
     ) -> Result<Option<MisbehaviourEvidence>, Error> {
-        Ok(None)
+        let client_id = update.client_id();
+        let mut observed = self.observed.lock().expect("poisoned observed blocks");
+        let ObservedBlocks {
+            conflicts,
+            reported,
+            ..
+        } = &mut *observed;
+        let reported = reported.entry(client_id.clone()).or_default();
+        let Some((&number, (block1, block2))) = conflicts
+            .iter()
+            .find(|(number, _)| !reported.contains(*number))
+        else {
+            return Ok(None);
+        };
+        reported.insert(number);
+        Ok(Some(MisbehaviourEvidence {
+            misbehaviour: AxonMisbehaviour {
+                client_id: client_id.clone(),
+                block1: block1.clone(),
+                block2: block2.clone(),
+            }
+            .into(),
+            supporting_headers: vec![],
+        }))
     }
 
     fn fetch(&mut self, height: ibc_relayer_types::Height) -> Result<AxonLightBlock, Error> {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ethers::types::H256;
+    use ibc_relayer_types::core::ics02_client::events::Attributes;
+    use ibc_relayer_types::core::ics02_client::misbehaviour::Misbehaviour;
+    use ibc_relayer_types::Height;
+
+    use super::*;
+    use crate::light_client::LightClient as _;
+
+    fn signed_block(number: u64, hash: u8) -> AxonSignedBlock {
+        let mut signed = AxonSignedBlock {
+            block: Default::default(),
+            previous_state_root: Default::default(),
+            proof: Default::default(),
+        };
+        signed.block.header.number = number;
+        signed.proof.number = number;
+        signed.proof.block_hash = H256::repeat_byte(hash);
+        signed
+    }
+
+    #[test]
+    fn test_conflicting_blocks() {
+        let mut light_client = LightClient {
+            rt: Arc::new(TokioRuntime::new().unwrap()),
+            chain_id: ChainId::new("axon".to_owned(), 0),
+            header_updaters: Default::default(),
+            observed: Default::default(),
+        };
+        let update = UpdateClient::from(Attributes {
+            consensus_height: Height::from_noncosmos_height(12),
+            ..Default::default()
+        });
+        let client_state = AnyClientState::Axon(
+            ibc_relayer_types::clients::ics07_axon::client_state::AxonClientState::new(
+                light_client.chain_id.clone(),
+                Height::from_noncosmos_height(12),
+            ),
+        );
+
+        light_client.observe(signed_block(10, 1));
+        light_client.observe(signed_block(10, 1));
+        light_client.observe(signed_block(12, 3));
+        assert!(light_client
+            .check_misbehaviour(&update, &client_state)
+            .unwrap()
+            .is_none());
+
+        light_client.observe(signed_block(12, 4));
+        light_client.observe(signed_block(10, 2));
+        light_client.observe(signed_block(10, 5));
+        let evidence = light_client
+            .check_misbehaviour(&update, &client_state)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            evidence.misbehaviour.height(),
+            Height::from_noncosmos_height(10)
+        );
+        assert!(evidence.supporting_headers.is_empty());
+
+        // every conflict is reported once to each client
+        let evidence = light_client
+            .check_misbehaviour(&update, &client_state)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            evidence.misbehaviour.height(),
+            Height::from_noncosmos_height(12)
+        );
+        assert!(light_client
+            .check_misbehaviour(&update, &client_state)
+            .unwrap()
+            .is_none());
+        let other_update = UpdateClient::from(Attributes {
+            client_id: "07-axon-1".parse().unwrap(),
+            consensus_height: Height::from_noncosmos_height(12),
+            ..Default::default()
+        });
+        let evidence = light_client
+            .check_misbehaviour(&other_update, &client_state)
+            .unwrap()
+            .unwrap();
+        assert_eq!(evidence.misbehaviour.client_id(), other_update.client_id());
+        assert!(evidence.supporting_headers.is_empty());
+    }
+}
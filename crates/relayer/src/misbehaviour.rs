@@ -1,4 +1,7 @@
 use ibc_proto::{google::protobuf::Any, protobuf::Protobuf};
+use ibc_relayer_types::clients::ics07_axon::misbehaviour::{
+    AxonMisbehaviour, AXON_MISBEHAVIOUR_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::{
     Misbehaviour as TmMisbehaviour, TENDERMINT_MISBEHAVIOR_TYPE_URL,
 };
@@ -25,6 +28,7 @@ pub struct MisbehaviourEvidence {
 #[allow(clippy::large_enum_variant)]
 pub enum AnyMisbehaviour {
     Tendermint(TmMisbehaviour),
+    Axon(AxonMisbehaviour),
 
     #[cfg(test)]
     Mock(MockMisbehaviour),
@@ -34,6 +38,7 @@ impl Misbehaviour for AnyMisbehaviour {
     fn client_id(&self) -> &ClientId {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.client_id(),
+            Self::Axon(misbehaviour) => misbehaviour.client_id(),
 
             #[cfg(test)]
             Self::Mock(misbehaviour) => misbehaviour.client_id(),
@@ -43,6 +48,7 @@ impl Misbehaviour for AnyMisbehaviour {
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.height(),
+            Self::Axon(misbehaviour) => misbehaviour.height(),
 
             #[cfg(test)]
             Self::Mock(misbehaviour) => misbehaviour.height(),
@@ -61,6 +67,8 @@ impl TryFrom<Any> for AnyMisbehaviour {
                 TmMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
             )),
 
+            AXON_MISBEHAVIOUR_TYPE_URL => Ok(AnyMisbehaviour::Axon(raw.try_into()?)),
+
             #[cfg(test)]
             MOCK_MISBEHAVIOUR_TYPE_URL => Ok(AnyMisbehaviour::Mock(
                 MockMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
@@ -81,6 +89,8 @@ impl From<AnyMisbehaviour> for Any {
                     .expect("encoding to `Any` from `AnyMisbehavior::Tendermint`"),
             },
 
+            AnyMisbehaviour::Axon(misbehaviour) => misbehaviour.into(),
+
             #[cfg(test)]
             AnyMisbehaviour::Mock(misbehaviour) => Any {
                 type_url: MOCK_MISBEHAVIOUR_TYPE_URL.to_string(),
@@ -96,6 +106,7 @@ impl core::fmt::Display for AnyMisbehaviour {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         match self {
             AnyMisbehaviour::Tendermint(tm) => write!(f, "{tm}"),
+            AnyMisbehaviour::Axon(axon) => write!(f, "{axon}"),
 
             #[cfg(test)]
             AnyMisbehaviour::Mock(mock) => write!(f, "{mock:?}"),
@@ -109,6 +120,12 @@ impl From<TmMisbehaviour> for AnyMisbehaviour {
     }
 }
 
+impl From<AxonMisbehaviour> for AnyMisbehaviour {
+    fn from(misbehaviour: AxonMisbehaviour) -> Self {
+        Self::Axon(misbehaviour)
+    }
+}
+
 #[cfg(test)]
 impl From<MockMisbehaviour> for AnyMisbehaviour {
     fn from(misbehaviour: MockMisbehaviour) -> Self {
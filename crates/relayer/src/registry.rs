@@ -51,6 +51,12 @@ impl<Chain: ChainHandle> Registry<Chain> {
         self.handles.values()
     }
 
+    /// Get the [`ChainHandle`] associated with the given [`ChainId`],
+    /// only if its runtime has already been spawned.
+    pub fn get(&self, chain_id: &ChainId) -> Option<&Chain> {
+        self.handles.get(chain_id)
+    }
+
     /// Get the [`ChainHandle`] associated with the given [`ChainId`].
     ///
     /// If there is no handle yet, this will first spawn the runtime and then
@@ -1,11 +1,18 @@
 use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
+use ibc_relayer_types::core::{
+    ics03_connection::connection::IdentifiedConnectionEnd,
+    ics04_channel::channel::IdentifiedChannelEnd,
+    ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+};
+
 use crate::{
+    client_state::IdentifiedAnyClientState,
     config::Config,
     rest::request::ReplySender,
-    rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    rest::request::{PendingPacketCounts, Request, VersionInfo},
+    supervisor::dump_state::{SupervisorState, WorkerDesc},
 };
 
 pub mod request;
@@ -31,6 +38,13 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    QueryClients(ChainId, ReplySender<Vec<IdentifiedAnyClientState>>),
+    QueryConnections(ChainId, ReplySender<Vec<IdentifiedConnectionEnd>>),
+    QueryChannels(ChainId, ReplySender<Vec<IdentifiedChannelEnd>>),
+    QueryPendingPackets(ChainId, PortId, ChannelId, ReplySender<PendingPacketCounts>),
+    DumpWorkers(ChainId, ReplySender<Vec<WorkerDesc>>),
+    ClearPackets(ChainId, ReplySender<()>),
+    RefreshClient(ChainId, ClientId, ReplySender<bool>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +96,59 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::GetClients { chain_id, reply_to } => {
+                trace!("GetClients {}", chain_id);
+
+                return Some(Command::QueryClients(chain_id, reply_to));
+            }
+
+            Request::GetConnections { chain_id, reply_to } => {
+                trace!("GetConnections {}", chain_id);
+
+                return Some(Command::QueryConnections(chain_id, reply_to));
+            }
+
+            Request::GetChannels { chain_id, reply_to } => {
+                trace!("GetChannels {}", chain_id);
+
+                return Some(Command::QueryChannels(chain_id, reply_to));
+            }
+
+            Request::GetPendingPackets {
+                chain_id,
+                port_id,
+                channel_id,
+                reply_to,
+            } => {
+                trace!("GetPendingPackets {} {}/{}", chain_id, port_id, channel_id);
+
+                return Some(Command::QueryPendingPackets(
+                    chain_id, port_id, channel_id, reply_to,
+                ));
+            }
+
+            Request::GetWorkers { chain_id, reply_to } => {
+                trace!("GetWorkers {}", chain_id);
+
+                return Some(Command::DumpWorkers(chain_id, reply_to));
+            }
+
+            Request::ClearPackets { chain_id, reply_to } => {
+                trace!("ClearPackets {}", chain_id);
+
+                return Some(Command::ClearPackets(chain_id, reply_to));
+            }
+
+            Request::RefreshClient {
+                chain_id,
+                client_id,
+                reply_to,
+            } => {
+                trace!("RefreshClient {} {}", chain_id, client_id);
+
+                return Some(Command::RefreshClient(chain_id, client_id, reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -17,12 +17,24 @@ pub enum RestApiError {
     #[error("could not find configuration for chain: {0}")]
     ChainConfigNotFound(ChainId),
 
+    #[error("chain {0} is not managed by the supervisor")]
+    ChainNotRunning(ChainId),
+
     #[error("failed to parse the string {0} into a valid chain identifier: {1}")]
     InvalidChainId(String, ValidationErrorDetail),
 
+    #[error("failed to parse the string {0} into a valid identifier: {1}")]
+    InvalidIdentifier(String, ValidationErrorDetail),
+
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to query chain: {0}")]
+    Query(String),
+
+    #[error("failed to refresh client: {0}")]
+    ClientRefresh(String),
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -34,8 +46,12 @@ impl RestApiError {
             RestApiError::ChannelRecv(_) => "ChannelRecv",
             RestApiError::Serialization(_) => "Serialization",
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
+            RestApiError::ChainNotRunning(_) => "ChainNotRunning",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
+            RestApiError::InvalidIdentifier(_, _) => "InvalidIdentifier",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::Query(_) => "Query",
+            RestApiError::ClientRefresh(_) => "ClientRefresh",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
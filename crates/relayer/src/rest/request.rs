@@ -1,8 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::{
+    ics03_connection::connection::IdentifiedConnectionEnd,
+    ics04_channel::channel::IdentifiedChannelEnd,
+    ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+};
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    client_state::IdentifiedAnyClientState,
+    config::ChainConfig,
+    rest::RestApiError,
+    supervisor::dump_state::{SupervisorState, WorkerDesc},
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -17,6 +26,16 @@ pub struct VersionInfo {
     pub version: String,
 }
 
+/// Number of packets pending at one end of a channel
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingPacketCounts {
+    /// Packets not yet received on the counterparty chain
+    pub unreceived_packets: usize,
+    /// Packets received on the counterparty chain whose acknowledgement
+    /// is not yet received on the local chain
+    pub unreceived_acks: usize,
+}
+
 /// REST API request variants
 #[derive(Clone, Debug)]
 pub enum Request {
@@ -36,4 +55,43 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    GetClients {
+        chain_id: ChainId,
+        reply_to: ReplySender<Vec<IdentifiedAnyClientState>>,
+    },
+
+    GetConnections {
+        chain_id: ChainId,
+        reply_to: ReplySender<Vec<IdentifiedConnectionEnd>>,
+    },
+
+    GetChannels {
+        chain_id: ChainId,
+        reply_to: ReplySender<Vec<IdentifiedChannelEnd>>,
+    },
+
+    GetPendingPackets {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        reply_to: ReplySender<PendingPacketCounts>,
+    },
+
+    GetWorkers {
+        chain_id: ChainId,
+        reply_to: ReplySender<Vec<WorkerDesc>>,
+    },
+
+    ClearPackets {
+        chain_id: ChainId,
+        reply_to: ReplySender<()>,
+    },
+
+    /// Replies whether a client update was submitted
+    RefreshClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+        reply_to: ReplySender<bool>,
+    },
 }
@@ -10,21 +10,36 @@ use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
 use ibc_relayer_types::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::{
+        ics02_client::client_state::ClientState,
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
+    },
     events::IbcEvent,
     Height,
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
+    chain::{
+        counterparty::{
+            channel_connection_client, pending_packet_summary, ChannelConnectionClient,
+        },
+        endpoint::HealthCheck,
+        handle::ChainHandle,
+        requests::{
+            IncludeProof, QueryChannelsRequest, QueryClientStateRequest, QueryClientStatesRequest,
+            QueryConnectionsRequest, QueryHeight,
+        },
+        tracking::TrackingId,
+    },
     config::Config,
     event::{
         monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
+    foreign_client::ForeignClient,
     object::Object,
     registry::{Registry, SharedRegistry},
-    rest,
+    rest::{self, request::PendingPacketCounts, RestApiError},
     supervisor::scan::ScanMode,
     telemetry,
     util::{
@@ -41,7 +56,7 @@ pub mod error;
 pub use error::{Error, ErrorDetail};
 
 pub mod dump_state;
-use dump_state::SupervisorState;
+use dump_state::{SupervisorState, WorkerDesc};
 
 pub mod scan;
 pub mod spawn;
@@ -271,7 +286,7 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&config, &registry, &workers.acquire_read(), &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -670,7 +685,7 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 
 fn handle_rest_requests<Chain: ChainHandle>(
     config: &Config,
-    registry: &Registry<Chain>,
+    registry: &SharedRegistry<Chain>,
     workers: &WorkerMap,
     rest_rx: &rest::Receiver,
 ) {
@@ -681,10 +696,11 @@ fn handle_rest_requests<Chain: ChainHandle>(
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
 fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
+    shared_registry: &SharedRegistry<Chain>,
     workers: &WorkerMap,
     m: rest::Command,
 ) {
+    let registry = &shared_registry.read();
     match m {
         rest::Command::DumpState(reply) => {
             let state = state(registry, workers);
@@ -692,9 +708,142 @@ fn handle_rest_cmd<Chain: ChainHandle>(
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+
+        rest::Command::QueryClients(chain_id, reply) => {
+            let result = rest_chain(registry, &chain_id).and_then(|chain| {
+                chain
+                    .query_clients(QueryClientStatesRequest { pagination: None })
+                    .map_err(|e| RestApiError::Query(e.to_string()))
+            });
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::QueryConnections(chain_id, reply) => {
+            let result = rest_chain(registry, &chain_id).and_then(|chain| {
+                chain
+                    .query_connections(QueryConnectionsRequest { pagination: None })
+                    .map_err(|e| RestApiError::Query(e.to_string()))
+            });
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::QueryChannels(chain_id, reply) => {
+            let result = rest_chain(registry, &chain_id).and_then(|chain| {
+                chain
+                    .query_channels(QueryChannelsRequest { pagination: None })
+                    .map_err(|e| RestApiError::Query(e.to_string()))
+            });
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        // the queries of both chains may take long, so they don't hold up the other requests
+        rest::Command::QueryPendingPackets(chain_id, port_id, channel_id, reply) => {
+            let registry = shared_registry.clone();
+            std::thread::spawn(move || {
+                let result = pending_packet_counts(&registry, &chain_id, &port_id, &channel_id);
+                reply
+                    .send(result)
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
+
+        rest::Command::DumpWorkers(chain_id, reply) => {
+            let descs = workers
+                .workers_for_chain(&chain_id)
+                .into_iter()
+                .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned()))
+                .collect();
+            reply
+                .send(Ok(descs))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::ClearPackets(chain_id, reply) => {
+            let result = rest_chain(registry, &chain_id).map(|_| {
+                for worker in workers.workers_for_chain(&chain_id) {
+                    worker.clear_pending_packets();
+                }
+            });
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::RefreshClient(chain_id, client_id, reply) => {
+            let registry = shared_registry.clone();
+            std::thread::spawn(move || {
+                let result = refresh_client(&registry, &chain_id, &client_id);
+                reply
+                    .send(result)
+                    .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+            });
+        }
     }
 }
 
+fn rest_chain<'a, Chain: ChainHandle>(
+    registry: &'a Registry<Chain>,
+    chain_id: &ChainId,
+) -> Result<&'a Chain, RestApiError> {
+    registry
+        .get(chain_id)
+        .ok_or_else(|| RestApiError::ChainNotRunning(chain_id.clone()))
+}
+
+/// The handles are cloned out of the registry, which isn't locked during the queries
+fn pending_packet_counts<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<PendingPacketCounts, RestApiError> {
+    let chain = rest_chain(&registry.read(), chain_id)?.clone();
+    let ChannelConnectionClient {
+        channel, client, ..
+    } = channel_connection_client(&chain, port_id, channel_id)
+        .map_err(|e| RestApiError::Query(e.to_string()))?;
+    let counterparty_chain = rest_chain(&registry.read(), &client.client_state.chain_id())?.clone();
+
+    let pending = pending_packet_summary(&chain, &counterparty_chain, &channel)
+        .map_err(|e| RestApiError::Query(e.to_string()))?;
+
+    Ok(PendingPacketCounts {
+        unreceived_packets: pending.unreceived_packets.len(),
+        unreceived_acks: pending.unreceived_acks.len(),
+    })
+}
+
+fn refresh_client<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> Result<bool, RestApiError> {
+    let chain = rest_chain(&registry.read(), chain_id)?.clone();
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| RestApiError::Query(e.to_string()))?;
+    let src_chain = rest_chain(&registry.read(), &client_state.chain_id())?.clone();
+
+    let mut client = ForeignClient::restore(client_id.clone(), chain, src_chain);
+    let events = client
+        .refresh()
+        .map_err(|e| RestApiError::ClientRefresh(e.to_string()))?;
+
+    Ok(events.is_some())
+}
+
 #[instrument(
     name = "supervisor.clear_pending_packets",
     level = "error",
@@ -11,6 +11,7 @@ use crate::client_state::IdentifiedAnyClientState;
 use crate::config::ChainConfig;
 use crate::error::{Error, ErrorDetail::LightClientVerification};
 use crate::event::monitor::EventBatch;
+use crate::telemetry;
 use tendermint_light_client::errors::ErrorDetail;
 
 const MAX_HEADERS_IN_BATCH: u64 = 256;
@@ -47,7 +48,10 @@ pub fn handle_eth_ckb_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
                 create_ethereum_light_client(src_chain, dst_chain, event_batch);
             }
         }
-        IbcEvent::NewBlock(_) => update_ethereum_headers(src_chain, dst_chain, event_batch),
+        IbcEvent::NewBlock(_) => {
+            update_ethereum_headers(src_chain, dst_chain, event_batch);
+            report_ethereum_header_lag(dst_chain, event_batch.height.revision_height());
+        }
         _ => warn!("receiving unrecognized event"),
     }
 }
@@ -198,6 +202,26 @@ fn update_ethereum_headers<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
 }
 
+/// Reports how far the on-chain light client lags behind `finalized_slot`
+fn report_ethereum_header_lag<Chain: ChainHandle>(dst_chain: &Arc<Chain>, finalized_slot: u64) {
+    let request = QueryClientStatesRequest { pagination: None };
+    match dst_chain.query_clients(request) {
+        Ok(clients) => {
+            if let Some(client) = clients.first() {
+                let onchain_slot = client.client_state.latest_height().revision_height();
+                debug!("on-chain header slot {onchain_slot}, finalized slot {finalized_slot}");
+                telemetry!(
+                    ethereum_header_lag,
+                    &dst_chain.id(),
+                    finalized_slot,
+                    onchain_slot
+                );
+            }
+        }
+        Err(err) => debug!("skip reporting header lag, dst_chain.query_clients: {err}"),
+    }
+}
+
 fn send_messages<Chain: ChainHandle>(
     chain: &Arc<Chain>,
     client_states: Vec<IdentifiedAnyClientState>,
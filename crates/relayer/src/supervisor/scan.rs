@@ -586,7 +586,11 @@ impl<'a, Chain: ChainHandle> ChainScanner<'a, Chain> {
         if !self.filtering_enabled() {
             return None;
         }
-        if !matches!(chain_config, ChainConfig::Cosmos(_)) {
+        // Eth and Ckb chains only relay headers, they have no channels to query
+        if !matches!(
+            chain_config,
+            ChainConfig::Cosmos(_) | ChainConfig::Axon(_) | ChainConfig::Ckb4Ibc(_)
+        ) {
             return None;
         }
         match chain_config.packet_filter().channel_policy {
@@ -941,3 +945,69 @@ fn init_telemetry(
 
     telemetry!(init_worker_by_type, WorkerType::Wallet);
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+
+    use super::*;
+    use crate::chain::ckb4ibc::tests::config as ckb4ibc_config;
+    use crate::chain::handle::BaseChainHandle;
+    use crate::config::axon::AxonChainConfig;
+    use crate::config::filter::PacketFilter;
+
+    fn allow(list: &str) -> PacketFilter {
+        toml::from_str(&format!("policy = 'allow'\nlist = {list}")).unwrap()
+    }
+
+    fn axon_config(packet_filter: PacketFilter) -> ChainConfig {
+        let mut config: AxonChainConfig = toml::from_str(
+            r#"
+            id = 'axon-0'
+            websocket_addr = 'ws://127.0.0.1:8010'
+            rpc_addr = 'http://127.0.0.1:8000'
+            contract_address = '0x1111111111111111111111111111111111111111'
+            transfer_contract_address = '0x2222222222222222222222222222222222222222'
+            restore_block_count = 10000
+            key_name = 'relayer'
+            key_store_type = 'Memory'
+            store_prefix = 'forcerelay'
+            "#,
+        )
+        .unwrap();
+        config.packet_filter = packet_filter;
+        ChainConfig::Axon(config)
+    }
+
+    #[test]
+    fn test_axon_and_ckb4ibc_use_allow_list() {
+        let exact = allow("[['transfer', 'channel-0']]");
+        let mut ckb4ibc = ckb4ibc_config(ClientType::Axon);
+        ckb4ibc.packet_filter = exact.clone();
+        let chains = vec![axon_config(exact), ChainConfig::Ckb4Ibc(ckb4ibc)];
+        let config = Config {
+            chains: chains.clone(),
+            ..Default::default()
+        };
+        let mut registry = Registry::<BaseChainHandle>::new(config.clone());
+        let mut client_state_filter = FilterPolicy::default();
+        let scanner = ChainScanner::new(
+            &config,
+            &mut registry,
+            &mut client_state_filter,
+            ScanMode::Auto,
+        );
+
+        // only the allowed channels are queried
+        for chain in &chains {
+            let filters = scanner.use_allow_list(chain).expect("exact allow list");
+            assert_eq!(
+                filters.iter_exact().collect::<Vec<_>>(),
+                [(&PortId::transfer(), &ChannelId::new(0))]
+            );
+        }
+        // the chains allowing wildcards are scanned for all their clients
+        let wildcard = axon_config(allow("[['transfer', 'channel-*']]"));
+        assert!(scanner.use_allow_list(&wildcard).is_none());
+    }
+}
@@ -71,10 +71,20 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             self.spawn_workers_for_client(chain.clone(), client_scan);
         }
 
-        // Let's only spawn the wallet worker if telemetry is enabled,
-        // otherwise the worker just ends up issuing queries to the node
-        // without making anything of the result
-        telemetry!(self.spawn_wallet_worker(chain));
+        // Let's only spawn the wallet worker if telemetry is enabled or the
+        // balance of the chain is watched, otherwise the worker just ends up
+        // issuing queries to the node without making anything of the result
+        let watches_balance = self
+            .config
+            .find_chain(&scan.chain_id)
+            .map_or(false, |chain_config| {
+                chain_config.balance().min_balance.is_some()
+            });
+        if watches_balance {
+            self.spawn_wallet_worker(chain);
+        } else {
+            telemetry!(self.spawn_wallet_worker(chain));
+        }
     }
 
     pub fn spawn_wallet_worker(&mut self, chain: Chain) {
@@ -184,7 +184,11 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         Object::Wallet(wallet) => {
             assert_eq!(wallet.chain_id, chains.a.id());
 
-            let wallet_task = wallet::spawn_wallet_worker(chains.a);
+            let balance_config = config
+                .find_chain(&wallet.chain_id)
+                .map(|chain_config| chain_config.balance().clone())
+                .unwrap_or_default();
+            let wallet_task = wallet::spawn_wallet_worker(chains.a, balance_config);
             task_handles.push(wallet_task);
 
             (None, None)
@@ -4,6 +4,7 @@ use crate::chain::requests::{
     CrossChainQueryRequest, IncludeProof, QueryConnectionRequest, QueryHeight,
 };
 use crate::chain::tracking::TrackedMsgs;
+use crate::config::ChainConfig;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::ForeignClient;
@@ -59,6 +60,27 @@ pub fn spawn_cross_chain_query_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     )
 }
 
+/// Height the client of the queried chain is updated to, so that it verifies the proofs
+/// of the responses at `proof_height`
+fn update_target_height<Chain: ChainHandle>(
+    queried_chain: &Chain,
+    proof_height: i64,
+) -> Result<Height, Error> {
+    let proof_height = u64::try_from(proof_height).map_err(|_| {
+        Error::ics31(ibc_relayer_types::applications::ics31_icq::error::Error::parse())
+    })?;
+    match queried_chain.config()? {
+        // the proofs of Cosmos are checked against the app hash of the next block
+        ChainConfig::Cosmos(_) => {
+            let height = Height::new(queried_chain.id().version(), proof_height)
+                .map_err(|e| Error::other_error(e.to_string()))?;
+            Ok(height.increment())
+        }
+        // the storage proofs of Axon are against the state root of the queried block
+        _ => Ok(Height::from_noncosmos_height(proof_height)),
+    }
+}
+
 fn handle_cross_chain_query<ChainA: ChainHandle, ChainB: ChainHandle>(
     chain_a_handle: ChainA,
     chain_b_handle: ChainB,
@@ -98,12 +120,11 @@ fn handle_cross_chain_query<ChainA: ChainHandle, ChainB: ChainHandle>(
                 )
                 .map_err(|_| TaskError::Fatal(RunError::query()))?;
 
-                let target_height = Height::new(
-                    chain_b_handle.id().version(),
-                    cross_chain_query_responses.get(0).unwrap().height as u64,
+                let target_height = update_target_height(
+                    &chain_b_handle,
+                    cross_chain_query_responses.get(0).unwrap().height,
                 )
-                .map_err(|_| TaskError::Fatal(RunError::query()))?
-                .increment();
+                .map_err(|_| TaskError::Fatal(RunError::query()))?;
 
                 // Push update client msg
                 let mut chain_a_msgs = client_a
@@ -135,3 +156,32 @@ fn handle_cross_chain_query<ChainA: ChainHandle, ChainB: ChainHandle>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::runtime::Runtime as TokioRuntime;
+
+    use super::*;
+    use crate::chain::axon::tests::setup as setup_axon;
+    use crate::chain::handle::BaseChainHandle;
+    use crate::chain::runtime::ChainRuntime;
+
+    #[test]
+    fn test_update_target_height_of_axon_proofs() {
+        let (_node, axon) = setup_axon();
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let (axon, _) = ChainRuntime::init::<BaseChainHandle>(axon, rt);
+
+        // the client is updated to the height of the block the storage is proven in
+        let height = update_target_height(&axon, 42).unwrap();
+        assert_eq!(height, Height::from_noncosmos_height(42));
+        assert_eq!(
+            axon.query_latest_height().unwrap().revision_number(),
+            height.revision_number()
+        );
+        assert!(update_target_height(&axon, -1).is_err());
+        axon.shutdown().unwrap();
+    }
+}
@@ -29,6 +29,7 @@ use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 
 use super::error::RunError;
+use super::wallet::is_relaying_paused;
 use super::WorkerCmd;
 
 const INCENTIVIZED_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
@@ -52,18 +53,27 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     resubmit: Resubmit,
 ) -> TaskHandle {
-    let span = {
+    let (span, chain_ids) = {
         let relay_path = &link.lock().unwrap().a_to_b;
-        error_span!(
+        let span = error_span!(
             "worker.packet",
             src_chain = %relay_path.src_chain().id(),
             src_port = %relay_path.src_port_id(),
             src_channel = %relay_path.src_channel_id(),
             dst_chain = %relay_path.dst_chain().id(),
+        );
+        (
+            span,
+            [relay_path.src_chain().id(), relay_path.dst_chain().id()],
         )
     };
 
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
+        // the scheduled operational data is kept until the balance is refilled
+        if chain_ids.iter().any(is_relaying_paused) {
+            trace!("relaying is paused on low balance");
+            return Ok(Next::Continue);
+        }
         handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
         Ok(Next::Continue)
     })
@@ -1,15 +1,35 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
 use std::time::Duration;
 
-use tracing::{error_span, trace, warn};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tracing::{error_span, info, trace, warn};
 
 use crate::{
-    chain::handle::ChainHandle,
+    account::Balance,
+    chain::{handle::ChainHandle, ChainType},
+    config::balance::BalanceConfig,
     telemetry,
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
 
-pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
+lazy_static::lazy_static! {
+    /// Chains whose relayer account can no longer pay the fees, see [`is_relaying_paused`]
+    static ref PAUSED_CHAINS: RwLock<HashSet<ChainId>> = RwLock::default();
+}
+
+/// Whether packet relaying from and to `chain_id` is paused because the balance of the
+/// relayer account dropped below `balance.min_balance`
+pub fn is_relaying_paused(chain_id: &ChainId) -> bool {
+    PAUSED_CHAINS.read().unwrap().contains(chain_id)
+}
+
+pub fn spawn_wallet_worker<Chain: ChainHandle>(
+    chain: Chain,
+    balance_config: BalanceConfig,
+) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
+    let mut alert = LowBalanceAlert::new(chain.id(), balance_config);
 
     spawn_background_task(span, Some(Duration::from_secs(5)), move || {
         let key = chain.get_key().map_err(|e| {
@@ -20,38 +40,110 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
             TaskError::Ignore(format!("failed to query balance for the account: {e}"))
         })?;
 
-        match balance.amount.parse::<f64>() {
-            Ok(amount) => {
-                telemetry!(
-                    wallet_balance,
-                    &chain.id(),
-                    &key.account(),
-                    amount,
-                    &balance.denom,
-                );
-                trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
-                telemetry!(
-                    update_period_fees,
-                    &chain.id(),
-                    &key.account(),
-                    &balance.denom
-                );
-            }
-            Err(e) => {
-                warn!(
-                    %balance.amount, denom = %balance.denom, account = %key.account(),
-                    "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
-                );
+        if let Some(amount) = report_balance(&chain, &key.account(), &balance) {
+            telemetry!(
+                update_period_fees,
+                &chain.id(),
+                &key.account(),
+                &balance.denom
+            );
+            alert.update(&key.account(), amount);
+        }
+
+        // the tokens of `balance.tokens`, along with the locked capacity on CKB
+        let reports_all_balances = matches!(
+            chain.config().map(|config| config.r#type()),
+            Ok(ChainType::Axon | ChainType::Ckb4Ibc)
+        );
+        if reports_all_balances {
+            let balances = chain.query_all_balances(None).map_err(|e| {
+                TaskError::Ignore(format!("failed to query balances for the account: {e}"))
+            })?;
+            for other in balances.iter().filter(|other| other.denom != balance.denom) {
+                report_balance(&chain, &key.account(), other);
             }
         }
         Ok(Next::Continue)
     })
 }
 
+/// Report `balance` to telemetry, returns its amount if it could be parsed
+fn report_balance<Chain: ChainHandle>(
+    chain: &Chain,
+    account: &str,
+    balance: &Balance,
+) -> Option<f64> {
+    match balance.amount.parse::<f64>() {
+        Ok(amount) => {
+            telemetry!(wallet_balance, &chain.id(), account, amount, &balance.denom);
+            trace!(%amount, denom = %balance.denom, %account, "wallet balance");
+            Some(amount)
+        }
+        Err(e) => {
+            warn!(
+                %balance.amount, denom = %balance.denom, %account,
+                "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
+            );
+            None
+        }
+    }
+}
+
+/// Watches the native balance of a chain against `balance.min_balance`
+struct LowBalanceAlert {
+    chain_id: ChainId,
+    config: BalanceConfig,
+    low: bool,
+}
+
+impl LowBalanceAlert {
+    fn new(chain_id: ChainId, config: BalanceConfig) -> Self {
+        Self {
+            chain_id,
+            config,
+            low: false,
+        }
+    }
+
+    /// Warn once the balance drops below the minimum, and pause relaying if configured
+    /// so, until the balance is back above it
+    fn update(&mut self, account: &str, amount: f64) {
+        let Some(min_balance) = self.config.min_balance else {
+            return;
+        };
+        let low = self.config.is_low(amount);
+        telemetry!(wallet_low_balance, &self.chain_id, account, low);
+        if low == self.low {
+            return;
+        }
+        self.low = low;
+        if low {
+            warn!(
+                %account, %amount, %min_balance,
+                "the balance of the relayer is too low to keep paying the fees on {}{}",
+                self.chain_id,
+                if self.config.pause_on_low_balance { ", pause relaying packets" } else { "" }
+            );
+        } else {
+            info!(%account, %amount, "the balance of the relayer on {} is refilled", self.chain_id);
+        }
+        if self.config.pause_on_low_balance {
+            let mut paused_chains = PAUSED_CHAINS.write().unwrap();
+            if low {
+                paused_chains.insert(self.chain_id.clone());
+            } else {
+                paused_chains.remove(&self.chain_id);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ibc_relayer_types::bigint::U256;
 
+    use super::*;
+
     // Test to confirm that any u256 fits in f64
     #[test]
     fn compare_f64_max_to_u256_max() {
@@ -60,4 +152,38 @@ mod tests {
 
         assert!(f64_max > u256_max.to_string().parse::<f64>().unwrap());
     }
+
+    #[test]
+    fn test_pause_relaying_on_low_balance() {
+        let chain_id = ChainId::from_string("ckb4ibc-low-balance");
+        let mut alert = LowBalanceAlert::new(
+            chain_id.clone(),
+            BalanceConfig {
+                tokens: vec![],
+                min_balance: Some(1000.0),
+                pause_on_low_balance: true,
+            },
+        );
+        alert.update("relayer", 2000.0);
+        assert!(!is_relaying_paused(&chain_id));
+        alert.update("relayer", 999.0);
+        assert!(is_relaying_paused(&chain_id));
+        alert.update("relayer", 500.0);
+        assert!(is_relaying_paused(&chain_id));
+        alert.update("relayer", 1000.0);
+        assert!(!is_relaying_paused(&chain_id));
+
+        // only warn when pausing isn't enabled
+        let chain_id = ChainId::from_string("axon-low-balance");
+        let mut alert = LowBalanceAlert::new(
+            chain_id.clone(),
+            BalanceConfig {
+                min_balance: Some(1000.0),
+                ..Default::default()
+            },
+        );
+        alert.update("relayer", 0.0);
+        assert!(alert.low);
+        assert!(!is_relaying_paused(&chain_id));
+    }
 }
@@ -127,6 +127,9 @@ pub struct TelemetryState {
     /// Number of messages submitted to a specific chain
     total_messages_submitted: Counter<u64>,
 
+    /// Amount of gas spent by committed transactions, per chain and message type
+    tx_gas_used: Counter<u64>,
+
     /// The balance of each wallet Forcerelay uses per chain
     wallet_balance: ObservableGauge<f64>,
 
@@ -192,6 +195,31 @@ pub struct TelemetryState {
 
     /// Sum of rewarded fees over the past FEE_LIFETIME seconds
     period_fees: ObservableGauge<u64>,
+
+    /// Number of beacon slots the Ethereum light client on CKB lags behind
+    /// the latest finalized slot, per CKB chain
+    ethereum_header_lag: ObservableGauge<u64>,
+
+    /// Number of nodes of the beacon header MMR stored by the relayer, per CKB chain
+    beacon_mmr_size: ObservableGauge<u64>,
+
+    /// Cycles consumed by committed CKB transactions, per chain and message type
+    ckb_tx_cycles: ObservableGauge<u64>,
+
+    /// Fees paid by committed CKB transactions in shannons, per chain and message type
+    ckb_tx_fees: Counter<u64>,
+
+    /// Capacity and sUDT balances of the relayer on CKB, per chain, address and denom
+    ckb_balance: ObservableGauge<f64>,
+
+    /// Time spent fetching and verifying the proofs of an Axon block. Milliseconds.
+    axon_proof_latency: ObservableGauge<u64>,
+
+    /// Time spent by the Ckb4Ibc event monitor scanning IBC cells once. Milliseconds.
+    ckb4ibc_monitor_scan_duration: ObservableGauge<u64>,
+
+    /// Whether the native balance of the relayer is below the configured minimum, per chain
+    wallet_low_balance: ObservableGauge<u64>,
 }
 
 impl TelemetryState {
@@ -470,6 +498,18 @@ impl TelemetryState {
         self.total_messages_submitted.add(&cx, count, labels);
     }
 
+    /// Amount of gas spent by a committed transaction, per chain and message type
+    pub fn tx_gas_used(&self, chain_id: &ChainId, msg_type: &str, gas_used: u64) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("msg_type", msg_type.to_string()),
+        ];
+
+        self.tx_gas_used.add(&cx, gas_used, labels);
+    }
+
     /// The balance in each wallet that Forcerelay is using, per account, denom and chain.
     /// The amount given is of unit: 10^6 * `denom`
     pub fn wallet_balance(&self, chain_id: &ChainId, account: &str, amount: f64, denom: &str) {
@@ -484,6 +524,92 @@ impl TelemetryState {
         self.wallet_balance.observe(&cx, amount, labels);
     }
 
+    /// How many beacon slots the Ethereum light client on CKB lags behind the finalized slot
+    pub fn ethereum_header_lag(&self, chain_id: &ChainId, finalized_slot: u64, onchain_slot: u64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.ethereum_header_lag
+            .observe(&cx, finalized_slot.saturating_sub(onchain_slot), labels);
+    }
+
+    /// Number of nodes of the beacon header MMR stored by the relayer
+    pub fn beacon_mmr_size(&self, chain_id: &ChainId, size: u64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.beacon_mmr_size.observe(&cx, size, labels);
+    }
+
+    /// Cycles and fee of a committed CKB transaction, per chain and message type.
+    /// The cycles are only reported when the node returns them.
+    pub fn ckb_tx_committed(
+        &self,
+        chain_id: &ChainId,
+        msg_type: &str,
+        cycles: Option<u64>,
+        fee: u64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("msg_type", msg_type.to_string()),
+        ];
+
+        if let Some(cycles) = cycles {
+            self.ckb_tx_cycles.observe(&cx, cycles, labels);
+        }
+        self.ckb_tx_fees.add(&cx, fee, labels);
+    }
+
+    /// The capacity (denom `ckb`, in shannons) or sUDT balance of an address on CKB
+    pub fn ckb_balance(&self, chain_id: &ChainId, address: &str, amount: f64, denom: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("address", address.to_string()),
+            KeyValue::new("denom", denom.to_string()),
+        ];
+
+        self.ckb_balance.observe(&cx, amount, labels);
+    }
+
+    /// Time spent fetching and verifying the proofs of an Axon block
+    pub fn axon_proof_latency(&self, chain_id: &ChainId, latency: Duration) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.axon_proof_latency
+            .observe(&cx, latency.as_millis() as u64, labels);
+    }
+
+    /// Time spent by the Ckb4Ibc event monitor scanning connection, channel and packet cells once
+    pub fn ckb4ibc_monitor_scan_duration(&self, chain_id: &ChainId, duration: Duration) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.ckb4ibc_monitor_scan_duration
+            .observe(&cx, duration.as_millis() as u64, labels);
+    }
+
+    /// Whether the native balance of the relayer account is below `balance.min_balance`
+    pub fn wallet_low_balance(&self, chain_id: &ChainId, account: &str, low: bool) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("account", account.to_string()),
+        ];
+
+        self.wallet_low_balance.observe(&cx, low as u64, labels);
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -873,6 +999,24 @@ impl AggregatorSelector for CustomAggregatorSelector {
                 1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
             ]))),
             "ics29_period_fees" => Some(Arc::new(last_value())),
+            "ethereum_header_lag" => Some(Arc::new(last_value())),
+            "beacon_mmr_size" => Some(Arc::new(last_value())),
+            "ckb_balance" => Some(Arc::new(last_value())),
+            "wallet_low_balance" => Some(Arc::new(last_value())),
+            "ckb_tx_cycles" => Some(Arc::new(histogram(&[
+                1_000_000.0,
+                5_000_000.0,
+                10_000_000.0,
+                50_000_000.0,
+                100_000_000.0,
+                500_000_000.0,
+            ]))),
+            "axon_proof_latency" => Some(Arc::new(histogram(&[
+                100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+            ]))),
+            "ckb4ibc_monitor_scan_duration" => Some(Arc::new(histogram(&[
+                100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+            ]))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -956,6 +1100,11 @@ impl Default for TelemetryState {
                 .with_description("Number of messages submitted to a specific chain")
                 .init(),
 
+            tx_gas_used: meter
+                .u64_counter("tx_gas_used")
+                .with_description("Amount of gas spent by committed transactions per message type")
+                .init(),
+
             wallet_balance: meter
                 .f64_observable_gauge("wallet_balance")
                 .with_description("The balance of each wallet Forcerelay uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
@@ -1038,6 +1187,49 @@ impl Default for TelemetryState {
                 .u64_observable_gauge("ics29_period_fees")
                 .with_description("Amount of ICS29 fees rewarded over the past 7 days")
                 .init(),
+
+            ethereum_header_lag: meter
+                .u64_observable_gauge("ethereum_header_lag")
+                .with_description("Number of beacon slots the Ethereum light client on CKB lags behind the latest finalized slot")
+                .init(),
+
+            beacon_mmr_size: meter
+                .u64_observable_gauge("beacon_mmr_size")
+                .with_description("Number of nodes of the beacon header MMR stored by the relayer")
+                .init(),
+
+            ckb_tx_cycles: meter
+                .u64_observable_gauge("ckb_tx_cycles")
+                .with_description("Cycles consumed by committed CKB transactions per message type")
+                .init(),
+
+            ckb_tx_fees: meter
+                .u64_counter("ckb_tx_fees")
+                .with_unit(Unit::new("shannons"))
+                .with_description("Fees paid by committed CKB transactions per message type")
+                .init(),
+
+            ckb_balance: meter
+                .f64_observable_gauge("ckb_balance")
+                .with_description("Capacity and sUDT balances of the relayer on CKB. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
+                .init(),
+
+            axon_proof_latency: meter
+                .u64_observable_gauge("axon_proof_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("Time spent fetching and verifying the proofs of an Axon block. Milliseconds.")
+                .init(),
+
+            ckb4ibc_monitor_scan_duration: meter
+                .u64_observable_gauge("ckb4ibc_monitor_scan_duration")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("Time spent by the Ckb4Ibc event monitor scanning IBC cells once. Milliseconds.")
+                .init(),
+
+            wallet_low_balance: meter
+                .u64_observable_gauge("wallet_low_balance")
+                .with_description("Whether the native balance of the relayer is below the configured minimum (1) or not (0)")
+                .init(),
         }
     }
 }
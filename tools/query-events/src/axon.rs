@@ -0,0 +1,86 @@
+use clap::Args;
+use ethers::{
+    contract::{EthLogDecode, LogMeta},
+    providers::{Http, Middleware, Provider},
+    types::Filter,
+};
+use tracing::{info, warn};
+
+use ibc_relayer::{
+    chain::axon::contract::OwnableIBCHandlerEvents, config::ChainConfig, event::IbcEventWithHeight,
+};
+use ibc_relayer_types::Height;
+
+use crate::{
+    filter::{print_events, EventFilter},
+    BoxError, ChainOpts,
+};
+
+/// Maximum number of blocks queried by a single `eth_getLogs` request
+const MAX_LOGS_BLOCK_RANGE: u64 = 1000;
+
+/// Query the events of the IBC contract deployed on an Axon chain
+#[derive(Debug, Args)]
+pub struct AxonOpts {
+    #[clap(flatten)]
+    chain: ChainOpts,
+
+    /// The first block whose logs should be queried
+    #[clap(long, default_value = "0")]
+    from_block: u64,
+
+    /// The last block whose logs should be queried, the latest block if unset
+    #[clap(long)]
+    to_block: Option<u64>,
+
+    #[clap(flatten)]
+    filter: EventFilter,
+}
+
+pub async fn run(opts: AxonOpts) -> Result<(), BoxError> {
+    let ChainConfig::Axon(config) = opts.chain.chain_config()? else {
+        return Err(format!("chain '{}' is not an Axon chain", opts.chain.chain).into());
+    };
+
+    info!("Connecting to {}", config.rpc_addr);
+    let client = Provider::<Http>::try_from(config.rpc_addr.to_string())?;
+    let to_block = match opts.to_block {
+        Some(to_block) => to_block,
+        None => client.get_block_number().await?.as_u64(),
+    };
+
+    info!(
+        "Searching for logs of contract {:?} from block {} to block {}",
+        config.contract_address, opts.from_block, to_block
+    );
+    let mut events = vec![];
+    let mut from_block = opts.from_block;
+    while from_block <= to_block {
+        let end_block = to_block.min(from_block + MAX_LOGS_BLOCK_RANGE - 1);
+        let filter = Filter::new()
+            .address(config.contract_address)
+            .from_block(from_block)
+            .to_block(end_block);
+        for log in client.get_logs(&filter).await? {
+            let meta = LogMeta::from(&log);
+            let event = match OwnableIBCHandlerEvents::decode_log(&log.into()) {
+                Ok(event) => event.into(),
+                Err(err) => {
+                    warn!("skip undecodable log {meta:?}: {err}");
+                    continue;
+                }
+            };
+            if opts.filter.matches(&event) {
+                events.push(IbcEventWithHeight::new_with_tx_hash(
+                    event,
+                    Height::from_noncosmos_height(meta.block_number.as_u64()),
+                    meta.transaction_hash.into(),
+                ));
+            }
+        }
+        from_block = end_block + 1;
+    }
+
+    print_events(events);
+    Ok(())
+}
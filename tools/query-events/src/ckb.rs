@@ -0,0 +1,137 @@
+use std::str::FromStr;
+
+use ckb_types::{
+    core::ScriptHashType,
+    packed::Script,
+    prelude::{Builder, Entity},
+    H256,
+};
+use clap::Args;
+use tracing::{info, warn};
+
+use ibc_relayer::{
+    chain::{
+        ckb::{prelude::CkbReader, rpc_client::RpcClient},
+        ckb4ibc::utils::{
+            fetch_transaction_by_hash, get_connection_search_key, get_prefix_search_key,
+            get_script_hash, transaction_to_event,
+        },
+    },
+    config::ChainConfig,
+    event::IbcEventWithHeight,
+};
+use ibc_relayer_types::{core::ics23_commitment::commitment::CommitmentPrefix, Height};
+
+use crate::{
+    filter::{print_events, EventFilter},
+    BoxError, ChainOpts,
+};
+
+/// Number of cells fetched by a single indexer request
+const CELLS_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellKind {
+    Connection,
+    Channel,
+    Packet,
+}
+
+impl FromStr for CellKind {
+    type Err = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "connection" => Ok(Self::Connection),
+            "channel" => Ok(Self::Channel),
+            "packet" => Ok(Self::Packet),
+            invalid => Err(format!("unrecognized cell kind: {invalid}").into()),
+        }
+    }
+}
+
+/// Query the events of the IBC cells living on a CKB chain
+///
+/// Each live connection, channel and packet cell is traced back to the
+/// transaction which created it, whose envelope is decoded into an IBC event.
+#[derive(Debug, Args)]
+pub struct CkbOpts {
+    #[clap(flatten)]
+    chain: ChainOpts,
+
+    /// Kinds of cells to scan, can be repeated.
+    /// Scan all of them by default (available: connection, channel, packet).
+    #[clap(long, value_name = "KIND", multiple_values = true)]
+    cells: Vec<CellKind>,
+
+    #[clap(flatten)]
+    filter: EventFilter,
+}
+
+pub async fn run(opts: CkbOpts) -> Result<(), BoxError> {
+    let ChainConfig::Ckb4Ibc(config) = opts.chain.chain_config()? else {
+        return Err(format!("chain '{}' is not a CKB chain", opts.chain.chain).into());
+    };
+    let prefix = CommitmentPrefix::try_from(config.store_prefix.as_bytes().to_vec())
+        .map_err(|_| "empty store prefix")?;
+
+    info!("Connecting to {}", config.ckb_rpc);
+    let rpc_client = RpcClient::new(&config.ckb_rpc, &config.ckb_indexer_rpc);
+
+    let kinds = if opts.cells.is_empty() {
+        vec![CellKind::Connection, CellKind::Channel, CellKind::Packet]
+    } else {
+        opts.cells
+    };
+
+    let mut events = vec![];
+    for kind in kinds {
+        info!("Scanning {kind:?} cells");
+        let search_key = match kind {
+            CellKind::Connection => get_connection_search_key(&config, None)?,
+            CellKind::Channel => get_prefix_search_key(lock_script(&config.channel_type_args)),
+            CellKind::Packet => get_prefix_search_key(lock_script(&config.packet_type_args)),
+        };
+        let mut cursor = None;
+        loop {
+            let cells = rpc_client
+                .fetch_live_cells(search_key.clone(), CELLS_PAGE_SIZE, cursor)
+                .await?;
+            for cell in &cells.objects {
+                let tx_hash = cell.out_point.tx_hash.clone();
+                let tx = fetch_transaction_by_hash(&rpc_client, &tx_hash).await?;
+                let event = match transaction_to_event(&tx, &prefix) {
+                    Ok(event) => event,
+                    Err(err) => {
+                        warn!("skip undecodable transaction {tx_hash:#x}: {err}");
+                        continue;
+                    }
+                };
+                if opts.filter.matches(&event) {
+                    let block_number: u64 = cell.block_number.into();
+                    events.push(IbcEventWithHeight::new_with_tx_hash(
+                        event,
+                        Height::from_noncosmos_height(block_number),
+                        tx_hash.0,
+                    ));
+                }
+            }
+            if cells.objects.len() < CELLS_PAGE_SIZE as usize {
+                break;
+            }
+            cursor = Some(cells.last_cursor);
+        }
+    }
+
+    events.sort_by_key(|event| event.height);
+    print_events(events);
+    Ok(())
+}
+
+/// The lock script prefix shared by all the cells of a kind
+fn lock_script(type_args: &H256) -> Script {
+    Script::new_builder()
+        .code_hash(get_script_hash(type_args))
+        .hash_type(ScriptHashType::Type.into())
+        .build()
+}
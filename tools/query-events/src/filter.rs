@@ -0,0 +1,182 @@
+use clap::Args;
+use itertools::Itertools;
+use tracing::info;
+
+use ibc_relayer::event::IbcEventWithHeight;
+use ibc_relayer_types::{
+    core::{ics04_channel::packet::Sequence, ics24_host::identifier::ChannelId},
+    events::{IbcEvent, IbcEventType},
+};
+
+/// Filters applied on the IBC events decoded from Axon logs or CKB cells
+#[derive(Debug, Default, Args)]
+pub struct EventFilter {
+    /// Only show events of this type, e.g. `send_packet` or `channel_open_init`
+    #[clap(long)]
+    pub event_type: Option<IbcEventType>,
+
+    /// Only show events of this channel, on either end of the channel
+    #[clap(long)]
+    pub channel: Option<ChannelId>,
+
+    /// Only show packet events with this sequence
+    #[clap(long)]
+    pub sequence: Option<Sequence>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &IbcEvent) -> bool {
+        if let Some(event_type) = &self.event_type {
+            if &event.event_type() != event_type {
+                return false;
+            }
+        }
+        if let Some(channel_id) = &self.channel {
+            let matched = channel_ends(event)
+                .into_iter()
+                .any(|id| id.as_ref() == Some(channel_id));
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(sequence) = self.sequence {
+            if event.packet().map(|packet| packet.sequence) != Some(sequence) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The channel identifiers of both ends of a channel or packet event
+fn channel_ends(event: &IbcEvent) -> Vec<Option<ChannelId>> {
+    if let Some(packet) = event.packet() {
+        return vec![
+            Some(packet.source_channel.clone()),
+            Some(packet.destination_channel.clone()),
+        ];
+    }
+    match event {
+        IbcEvent::CloseInitChannel(ev) => vec![
+            Some(ev.channel_id.clone()),
+            ev.counterparty_channel_id.clone(),
+        ],
+        IbcEvent::CloseConfirmChannel(ev) => {
+            vec![ev.channel_id.clone(), ev.counterparty_channel_id.clone()]
+        }
+        event => match event.clone().channel_attributes() {
+            Some(attrs) => vec![attrs.channel_id, attrs.counterparty_channel_id],
+            None => vec![],
+        },
+    }
+}
+
+/// Print the events grouped by block, in the same layout for every chain type
+pub fn print_events(events: Vec<IbcEventWithHeight>) {
+    if events.is_empty() {
+        info!("Found no matching events");
+        return;
+    }
+    let blocks = events.into_iter().group_by(|event| event.height);
+    for (height, events) in &blocks {
+        let events = events.collect_vec();
+        info!(
+            "Block {}: found {} matching events",
+            height.revision_height(),
+            events.len()
+        );
+        for event in events {
+            info!("tx 0x{}: {:#?}", hex::encode(event.tx_hash), event.event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ibc_relayer_types::core::ics04_channel::{
+        events::{CloseInit, OpenInit, SendPacket},
+        packet::Packet,
+    };
+    use ibc_relayer_types::core::ics24_host::identifier::{ConnectionId, PortId};
+
+    use super::*;
+
+    fn send_packet() -> IbcEvent {
+        IbcEvent::SendPacket(SendPacket {
+            packet: Packet {
+                sequence: 3.into(),
+                source_port: PortId::transfer(),
+                source_channel: ChannelId::new(0),
+                destination_port: PortId::transfer(),
+                destination_channel: ChannelId::new(5),
+                ..Default::default()
+            },
+        })
+    }
+
+    #[test]
+    fn test_filter_packet_events() {
+        let event = send_packet();
+        assert!(EventFilter::default().matches(&event));
+
+        let filter = EventFilter {
+            event_type: Some(IbcEventType::from_str("send_packet").unwrap()),
+            channel: Some(ChannelId::new(5)),
+            sequence: Some(3.into()),
+        };
+        assert!(filter.matches(&event));
+
+        let filter = EventFilter {
+            channel: Some(ChannelId::new(1)),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&event));
+
+        let filter = EventFilter {
+            sequence: Some(4.into()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&event));
+
+        let filter = EventFilter {
+            event_type: Some(IbcEventType::WriteAck),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&event));
+    }
+
+    #[test]
+    fn test_filter_channel_events() {
+        let connection_id = ConnectionId::from_str("connection-0").unwrap();
+        let open_init = IbcEvent::OpenInitChannel(OpenInit {
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(2)),
+            connection_id: connection_id.clone(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: None,
+        });
+        let close_init = IbcEvent::CloseInitChannel(CloseInit {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(3),
+            connection_id,
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: Some(ChannelId::new(2)),
+        });
+
+        let filter = EventFilter {
+            channel: Some(ChannelId::new(2)),
+            ..Default::default()
+        };
+        assert!(filter.matches(&open_init));
+        assert!(filter.matches(&close_init));
+
+        // channel events never match a sequence
+        let filter = EventFilter {
+            sequence: Some(1.into()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&open_init));
+    }
+}
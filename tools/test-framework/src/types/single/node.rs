@@ -196,12 +196,17 @@ impl FullNode {
             ckb_rpc: ckb_rpc.clone(),
             ckb_indexer_rpc: ckb_rpc,
             key_name: "relayer_ckb_wallet".to_string(),
+            key_store_type: Store::Test,
             store_prefix: "ibc".to_string(),
             client_code_hash: h256_env("CLIENT_CODE_HASH").into(),
             connection_type_args: h256_env("CONNECTION_TYPE_ARGS").into(),
             channel_type_args: h256_env("CHANNEL_TYPE_ARGS").into(),
             packet_type_args: h256_env("PACKET_TYPE_ARGS").into(),
             onchain_light_clients,
+            trusting_period: None,
+            reclaim: Default::default(),
+            wallet: Default::default(),
+            balance: Default::default(),
             packet_filter: Default::default(),
         };
 
@@ -234,13 +239,21 @@ impl FullNode {
         let axon_config = config::axon::AxonChainConfig {
             id: self.chain_driver.chain_id.clone(),
             key_name: "relayer".to_string(),
+            key_store_type: Store::Test,
             store_prefix: "ibc".to_string(),
             packet_filter: Default::default(),
             websocket_addr,
             rpc_addr,
             contract_address,
             transfer_contract_address,
+            fee_contract_address: None,
             restore_block_count,
+            event_source: Default::default(),
+            poll_interval: config::axon::default::poll_interval(),
+            trusting_period: None,
+            gas: Default::default(),
+            diagnostics: Default::default(),
+            balance: Default::default(),
         };
         Ok(config::ChainConfig::Axon(axon_config))
     }